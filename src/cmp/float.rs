@@ -19,16 +19,74 @@ pub const fn is_equal(a: f64, b: f64) -> bool {
 
     let diff = math::abs(a - b);
 
+    let magnitude = math::max(math::abs(a), math::abs(b));
+
     // For very small numbers near zero, use absolute epsilon
-    if math::max(a, math::abs(b)) < 1.0 {
+    if magnitude < 1.0 {
         return diff < EPSILON;
     }
 
     // For larger numbers, use relative epsilon to maintain precision
     // This prevents issues when comparing large coordinate values
-    let relative_epsilon = EPSILON * math::max(a, math::abs(b));
+    let relative_epsilon = EPSILON * magnitude;
 
     // Use the larger of absolute and relative epsilon
     // This handles edge cases around 1.0 and ensures consistent behavior
     diff < math::max(EPSILON, relative_epsilon)
 }
+
+/// Returns whether `a` and `b` differ by less than `epsilon`.
+///
+/// Unlike [`is_equal`], the tolerance is purely absolute, which makes it easy
+/// to loosen or tighten for a particular comparison.
+pub const fn is_equal_epsilon(a: f64, b: f64, epsilon: f64) -> bool { a == b || math::abs(a - b) < epsilon }
+
+/// Equality within a caller-chosen tolerance.
+///
+/// `PartialEq` on the geometry types uses the fixed [`EPSILON`]; this trait
+/// lets a comparison use a looser (or tighter) bound, e.g. after a long chain
+/// of transforms has accumulated rounding error.
+pub trait ApproxEq {
+    /// Returns whether every component of `self` is within `epsilon` of the
+    /// matching component of `other`.
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        is_equal_epsilon(*self, *other, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_negative_numbers_compare_relatively() {
+        assert!(is_equal(-1e6, -1e6 + 0.001));
+        assert!(is_equal(-1e6 + 0.001, -1e6));
+        assert!(!is_equal(-1e6, -1e6 + 100.0));
+    }
+
+    #[test]
+    fn test_is_equal_is_symmetric_for_negative_operands() {
+        let (a, b) = (-1e6 - 10.00005, -1e6);
+        assert!(is_equal(a, b));
+        assert_eq!(is_equal(a, b), is_equal(b, a));
+    }
+
+    #[test]
+    fn test_small_numbers_compare_absolutely() {
+        assert!(is_equal(-0.5, -0.500_001));
+        assert!(!is_equal(-0.5, -0.5001));
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        assert!(!is_equal(f64::NAN, f64::NAN));
+        assert!(is_equal(f64::INFINITY, f64::INFINITY));
+        assert!(!is_equal(f64::NEG_INFINITY, -1e300));
+    }
+}
@@ -57,6 +57,12 @@ pub enum GraphicsError {
         height:   usize,
         max_size: usize,
     },
+    /// Scale factor is zero or does not divide the canvas dimensions
+    InvalidScaleFactor {
+        factor: usize,
+        width:  usize,
+        height: usize,
+    },
     /// Pixel coordinates are out of bounds
     PixelOutOfBounds {
         x:      usize,
@@ -252,6 +258,16 @@ impl fmt::Display for GraphicsError {
             } => {
                 write!(f, "Invalid canvas dimensions: {w}x{h} (max size: {size})",)
             },
+            Self::InvalidScaleFactor {
+                factor,
+                width: w,
+                height: h,
+            } => {
+                write!(
+                    f,
+                    "Invalid scale factor {factor} for {w}x{h} canvas (must divide both dimensions)",
+                )
+            },
             Self::PixelOutOfBounds {
                 x,
                 y,
@@ -378,11 +394,9 @@ impl fmt::Display for IoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::FileOperation {
-                operation,
-                filename,
-                source,
+                operation, filename, ..
             } => {
-                write!(f, "Failed to {operation} file '{filename}': {source}")
+                write!(f, "Failed to {operation} file '{filename}'")
             },
             Self::UnsupportedFormat {
                 filename,
@@ -447,9 +461,34 @@ impl Error for GraphicsError {}
 impl Error for GeometryError {}
 impl Error for ShadingError {}
 impl Error for WorldError {}
-impl Error for IoError {}
+impl Error for IoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::FileOperation { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 impl Error for ConfigError {}
 
+impl Error for TracerError {
+    /// Forwards to the wrapped error's source. The wrapped error's message is
+    /// already part of this error's `Display`, so it is not a link of its own
+    /// in the chain.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Math(e) => e.source(),
+            Self::Graphics(e) => e.source(),
+            Self::Geometry(e) => e.source(),
+            Self::Shading(e) => e.source(),
+            Self::World(e) => e.source(),
+            Self::Io(e) => e.source(),
+            Self::Config(e) => e.source(),
+            Self::Other(_) => None,
+        }
+    }
+}
+
 impl From<io::Error> for TracerError {
     fn from(error: io::Error) -> Self {
         Self::Io(IoError::FileOperation {
@@ -495,3 +534,39 @@ impl From<String> for TracerError {
 impl From<&str> for TracerError {
     fn from(e: &str) -> Self { Self::Other(e.to_string()) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_without_an_underlying_cause_have_no_source() {
+        let err = TracerError::from(MathError::MatrixNotInvertible {
+            matrix: "4x4".to_string(),
+        });
+
+        assert!(err.source().is_none());
+        assert!(TracerError::from("message").source().is_none());
+    }
+
+    #[test]
+    fn test_source_chain_reaches_the_underlying_io_error() {
+        let err = TracerError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+
+        let io_error = core::iter::successors(err.source(), |&e| e.source())
+            .find_map(|e| e.downcast_ref::<io::Error>())
+            .expect("chain contains the io::Error");
+        assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_source_chain_does_not_repeat_messages() {
+        let err = TracerError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+
+        let messages: Vec<_> = core::iter::successors(Some(&err as &dyn Error), |&e| e.source())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert!(!messages[0].contains(&messages[1]), "{messages:?}");
+    }
+}
@@ -0,0 +1,348 @@
+use crate::geometry::Ray;
+use crate::math::{self, Interval};
+use crate::primitives::{Mat4, Point3};
+
+/// An axis-aligned bounding box.
+///
+/// An empty box has `min` above `max` on every axis so that merging anything
+/// into it yields the other operand unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Bounds {
+    /// A box containing nothing.
+    pub const EMPTY: Self = Self::new(Point3::INFINITY, Point3::NEG_INFINITY);
+    /// A box containing everything.
+    pub const INFINITE: Self = Self::new(Point3::NEG_INFINITY, Point3::INFINITY);
+
+    /// Creates a box spanning from `min` to `max`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(min: Point3, max: Point3) -> Self { Self { min, max } }
+
+    /// Returns whether the box contains no points at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.x() > self.max.x() || self.min.y() > self.max.y() || self.min.z() > self.max.z()
+    }
+
+    /// Returns the smallest box containing both `self` and `point`.
+    #[must_use]
+    pub fn merge_point(self, point: Point3) -> Self {
+        Self::new(
+            Point3::new(
+                math::min(self.min.x(), point.x()),
+                math::min(self.min.y(), point.y()),
+                math::min(self.min.z(), point.z()),
+            ),
+            Point3::new(
+                math::max(self.max.x(), point.x()),
+                math::max(self.max.y(), point.y()),
+                math::max(self.max.z(), point.z()),
+            ),
+        )
+    }
+
+    /// Returns the smallest box containing both `self` and `other`.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        if other.is_empty() {
+            return self;
+        }
+        self.merge_point(other.min).merge_point(other.max)
+    }
+
+    /// Returns whether `point` lies inside the box or on its boundary.
+    pub fn contains_point(&self, point: Point3) -> bool {
+        (self.min.x()..=self.max.x()).contains(&point.x())
+            && (self.min.y()..=self.max.y()).contains(&point.y())
+            && (self.min.z()..=self.max.z()).contains(&point.z())
+    }
+
+    /// Returns whether `other` lies entirely inside the box.
+    pub fn contains_box(&self, other: &Self) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Returns the axis-aligned box enclosing this box after applying `m`.
+    ///
+    /// All eight corners are transformed, so the result may be looser than
+    /// the transformed contents. A box that is unbounded on any axis becomes
+    /// [`INFINITE`](Self::INFINITE).
+    #[must_use]
+    pub fn transform(&self, m: &Mat4) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        let (lo, hi) = (self.min, self.max);
+        if ![lo.x(), lo.y(), lo.z(), hi.x(), hi.y(), hi.z()]
+            .iter()
+            .all(|c| c.is_finite())
+        {
+            return Self::INFINITE;
+        }
+
+        let corners = [
+            Point3::new(lo.x(), lo.y(), lo.z()),
+            Point3::new(lo.x(), lo.y(), hi.z()),
+            Point3::new(lo.x(), hi.y(), lo.z()),
+            Point3::new(lo.x(), hi.y(), hi.z()),
+            Point3::new(hi.x(), lo.y(), lo.z()),
+            Point3::new(hi.x(), lo.y(), hi.z()),
+            Point3::new(hi.x(), hi.y(), lo.z()),
+            Point3::new(hi.x(), hi.y(), hi.z()),
+        ];
+        corners
+            .into_iter()
+            .fold(Self::EMPTY, |bounds, corner| bounds.merge_point(m * corner))
+    }
+
+    /// Splits the box in half across its widest axis, returning the
+    /// `(left, right)` halves. Ties favour `x`, then `y`.
+    #[must_use]
+    pub fn split(&self) -> (Self, Self) {
+        let (lo, hi) = (self.min, self.max);
+        let (dx, dy, dz) = (hi.x() - lo.x(), hi.y() - lo.y(), hi.z() - lo.z());
+        let greatest = math::max(dx, math::max(dy, dz));
+
+        let (mut x0, mut y0, mut z0) = (lo.x(), lo.y(), lo.z());
+        let (mut x1, mut y1, mut z1) = (hi.x(), hi.y(), hi.z());
+        if greatest == dx {
+            x0 = lo.x() + dx / 2.0;
+            x1 = x0;
+        } else if greatest == dy {
+            y0 = lo.y() + dy / 2.0;
+            y1 = y0;
+        } else {
+            z0 = lo.z() + dz / 2.0;
+            z1 = z0;
+        }
+
+        (
+            Self::new(lo, Point3::new(x1, y1, z1)),
+            Self::new(Point3::new(x0, y0, z0), hi),
+        )
+    }
+
+    /// Returns whether `ray` passes through the box, using the slab test.
+    ///
+    /// Unlike [`Cube`](crate::geometry::Cube), this divides exactly instead
+    /// of treating nearly parallel rays as parallel, so it never rejects a
+    /// ray that grazes into the box at a shallow angle.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let (origin, direction) = (ray.origin(), ray.direction());
+        let x = slab(origin.x(), direction.x(), self.min.x(), self.max.x());
+        let y = slab(origin.y(), direction.y(), self.min.y(), self.max.y());
+        let z = slab(origin.z(), direction.z(), self.min.z(), self.max.z());
+        x.overlaps(&y.intersection(&z))
+    }
+}
+
+/// Returns the range of `t` over which a ray is inside the slab between
+/// `min` and `max` along a single axis.
+///
+/// A ray parallel to the slab is inside it everywhere or nowhere. That is
+/// what dividing by a zero `direction` gives, except for the `0 / 0` of an
+/// origin on the slab's face, so parallel rays are handled up front.
+fn slab(origin: f64, direction: f64, min: f64, max: f64) -> Interval {
+    if direction == 0.0 {
+        return if min <= origin && origin <= max {
+            Interval::UNIVERSE
+        } else {
+            Interval::EMPTY
+        };
+    }
+
+    let (t0, t1) = ((min - origin) / direction, (max - origin) / direction);
+    Interval::new(math::min(t0, t1), math::max(t0, t1))
+}
+
+impl Default for Bounds {
+    #[inline(always)]
+    fn default() -> Self { Self::EMPTY }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{FRAC_PI_4, point, vector};
+
+    #[test]
+    fn test_creating_an_empty_bounding_box() {
+        let b = Bounds::default();
+        assert_eq!(b.min, Point3::INFINITY);
+        assert_eq!(b.max, Point3::NEG_INFINITY);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_adding_points_to_an_empty_bounding_box() {
+        let b = Bounds::EMPTY
+            .merge_point(point(-5, 2, 0))
+            .merge_point(point(7, 0, -3));
+        assert_eq!(b, Bounds::new(point(-5, 0, -3), point(7, 2, 0)));
+    }
+
+    #[test]
+    fn test_merging_one_bounding_box_with_another() {
+        let b1 = Bounds::new(point(-5, -2, 0), point(7, 4, 4));
+        let b2 = Bounds::new(point(8, -7, -2), point(14, 2, 8));
+        assert_eq!(b1.merge(b2), Bounds::new(point(-5, -7, -2), point(14, 4, 8)));
+        assert_eq!(b1.merge(Bounds::EMPTY), b1);
+        assert_eq!(Bounds::EMPTY.merge(b1), b1);
+    }
+
+    #[test]
+    fn test_checking_whether_a_box_contains_a_point() {
+        let b = Bounds::new(point(5, -2, 0), point(11, 4, 7));
+        let cases = [
+            (point(5, -2, 0), true),
+            (point(11, 4, 7), true),
+            (point(8, 1, 3), true),
+            (point(3, 0, 3), false),
+            (point(8, -4, 3), false),
+            (point(8, 1, -1), false),
+            (point(13, 1, 3), false),
+            (point(8, 5, 3), false),
+            (point(8, 1, 8), false),
+        ];
+        for (p, expected) in cases {
+            assert_eq!(b.contains_point(p), expected, "{p:?}");
+        }
+    }
+
+    #[test]
+    fn test_checking_whether_a_box_contains_another_box() {
+        let b = Bounds::new(point(5, -2, 0), point(11, 4, 7));
+        let cases = [
+            (point(5, -2, 0), point(11, 4, 7), true),
+            (point(6, -1, 1), point(10, 3, 6), true),
+            (point(4, -3, -1), point(10, 3, 6), false),
+            (point(6, -1, 1), point(12, 5, 8), false),
+        ];
+        for (min, max, expected) in cases {
+            assert_eq!(
+                b.contains_box(&Bounds::new(min, max)),
+                expected,
+                "{min:?}..{max:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_transforming_a_bounding_box() {
+        let b = Bounds::new(point(-1, -1, -1), point(1, 1, 1));
+        let m = Mat4::rotation_x(FRAC_PI_4) * Mat4::rotation_y(FRAC_PI_4);
+        let t = b.transform(&m);
+        let (sqrt2, k) = (2_f64.sqrt(), 1.0 + 2_f64.sqrt() / 2.0);
+        assert_eq!(t.min, point(-sqrt2, -k, -k));
+        assert_eq!(t.max, point(sqrt2, k, k));
+    }
+
+    #[test]
+    fn test_transforming_an_unbounded_box() {
+        let b = Bounds::new(
+            point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            point(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        assert_eq!(b.transform(&Mat4::translation(0.0, 1.0, 0.0)), Bounds::INFINITE);
+    }
+
+    #[test]
+    fn test_intersecting_a_ray_with_a_bounding_box_at_the_origin() {
+        let b = Bounds::new(point(-1, -1, -1), point(1, 1, 1));
+        let cases = [
+            (point(5.0, 0.5, 0.0), vector(-1, 0, 0), true),
+            (point(-5.0, 0.5, 0.0), vector(1, 0, 0), true),
+            (point(0.5, 5.0, 0.0), vector(0, -1, 0), true),
+            (point(0.5, -5.0, 0.0), vector(0, 1, 0), true),
+            (point(0.5, 0.0, 5.0), vector(0, 0, -1), true),
+            (point(0.5, 0.0, -5.0), vector(0, 0, 1), true),
+            (point(0.0, 0.5, 0.0), vector(0, 0, 1), true),
+            (point(-2, 0, 0), vector(2, 4, 6), false),
+            (point(0, -2, 0), vector(6, 2, 4), false),
+            (point(0, 0, -2), vector(4, 6, 2), false),
+            (point(2, 0, 2), vector(0, 0, -1), false),
+            (point(0, 2, 2), vector(0, -1, 0), false),
+            (point(2, 2, 0), vector(-1, 0, 0), false),
+        ];
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(
+                b.intersects(&r),
+                expected,
+                "ray from {origin:?} along {direction:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_intersecting_a_ray_with_a_non_cubic_bounding_box() {
+        let b = Bounds::new(point(5, -2, 0), point(11, 4, 7));
+        let cases = [
+            (point(15, 1, 2), vector(-1, 0, 0), true),
+            (point(-5, -1, 4), vector(1, 0, 0), true),
+            (point(7, 6, 5), vector(0, -1, 0), true),
+            (point(9, -5, 6), vector(0, 1, 0), true),
+            (point(8, 2, 12), vector(0, 0, -1), true),
+            (point(6, 0, -5), vector(0, 0, 1), true),
+            (point(8.0, 1.0, 3.5), vector(0, 0, 1), true),
+            (point(9, -1, -8), vector(2, 4, 6), false),
+            (point(8, 3, -4), vector(6, 2, 4), false),
+            (point(9, -1, -2), vector(4, 6, 2), false),
+            (point(4, 0, 9), vector(0, 0, -1), false),
+            (point(8, 6, -1), vector(0, -1, 0), false),
+            (point(12, 5, 4), vector(-1, 0, 0), false),
+        ];
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(
+                b.intersects(&r),
+                expected,
+                "ray from {origin:?} along {direction:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_box_is_never_hit() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert!(!Bounds::EMPTY.intersects(&r));
+        assert!(Bounds::INFINITE.intersects(&r));
+    }
+
+    #[test]
+    fn test_splitting_a_perfect_cube() {
+        let (left, right) = Bounds::new(point(-1, -4, -5), point(9, 6, 5)).split();
+        assert_eq!(left, Bounds::new(point(-1, -4, -5), point(4, 6, 5)));
+        assert_eq!(right, Bounds::new(point(4, -4, -5), point(9, 6, 5)));
+    }
+
+    #[test]
+    fn test_splitting_an_x_wide_box() {
+        let (left, right) = Bounds::new(point(-1, -2, -3), point(9, 5.5, 3)).split();
+        assert_eq!(left, Bounds::new(point(-1, -2, -3), point(4, 5.5, 3)));
+        assert_eq!(right, Bounds::new(point(4, -2, -3), point(9, 5.5, 3)));
+    }
+
+    #[test]
+    fn test_splitting_a_y_wide_box() {
+        let (left, right) = Bounds::new(point(-1, -2, -3), point(5, 8, 3)).split();
+        assert_eq!(left, Bounds::new(point(-1, -2, -3), point(5, 3, 3)));
+        assert_eq!(right, Bounds::new(point(-1, 3, -3), point(5, 8, 3)));
+    }
+
+    #[test]
+    fn test_splitting_a_z_wide_box() {
+        let (left, right) = Bounds::new(point(-1, -2, -3), point(5, 3, 7)).split();
+        assert_eq!(left, Bounds::new(point(-1, -2, -3), point(5, 3, 2)));
+        assert_eq!(right, Bounds::new(point(-1, -2, 2), point(5, 3, 7)));
+    }
+}
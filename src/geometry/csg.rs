@@ -0,0 +1,318 @@
+use crate::geometry::shape::includes;
+use crate::geometry::{Bounds, Intersection, Ray, Shape, intersections};
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// How a [`Csg`] combines its two operands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CsgOperation {
+    /// Everything in either operand.
+    Union,
+    /// Only what the operands have in common.
+    Intersection,
+    /// The left operand with the right one carved out of it.
+    Difference,
+}
+
+/// A constructive solid geometry shape built from two other shapes.
+///
+/// Like [`Group`](crate::geometry::Group), the operands have the CSG's
+/// transform folded into their own.
+#[derive(Debug)]
+pub struct Csg {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+    operation: CsgOperation,
+    children:  [Box<dyn Shape>; 2],
+    /// Union of the operands' bounds, kept in the CSG's parent space.
+    bounds:    Bounds,
+}
+
+impl Csg {
+    /// Creates a new shape combining `left` and `right` with `operation`.
+    pub fn new(operation: CsgOperation, left: impl Shape + 'static, right: impl Shape + 'static) -> Self {
+        let bounds = left.parent_space_bounds().merge(right.parent_space_bounds());
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+            material: Material::new(),
+            operation,
+            children: [Box::new(left), Box::new(right)],
+            bounds,
+        }
+    }
+
+    /// Returns the shape with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns how the operands are combined.
+    #[inline]
+    pub const fn operation(&self) -> CsgOperation { self.operation }
+
+    /// Returns the left operand.
+    #[inline]
+    pub fn left(&self) -> &dyn Shape { self.children[0].as_ref() }
+
+    /// Returns the right operand.
+    #[inline]
+    pub fn right(&self) -> &dyn Shape { self.children[1].as_ref() }
+
+    /// Keeps only the intersections in `xs` that lie on the surface of the
+    /// combined shape, preserving their order.
+    pub fn filter_intersections<'a>(&self, xs: &[Intersection<'a>]) -> Vec<Intersection<'a>> {
+        // Start outside both operands.
+        let (mut inl, mut inr) = (false, false);
+
+        let mut result = Vec::new();
+        for i in xs {
+            let lhit = includes(self.left(), i.object());
+            if intersection_allowed(self.operation, lhit, inl, inr) {
+                result.push(*i);
+            }
+
+            if lhit {
+                inl = !inl;
+            } else {
+                inr = !inr;
+            }
+        }
+        result
+    }
+}
+
+impl Shape for Csg {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    /// Sets the transform and carries the change down to both operands.
+    fn set_transform(&mut self, transform: Mat4) {
+        let inverse = transform.inverse().expect("shape transform must be invertible");
+        let change = transform * self.inverse;
+
+        self.bounds = Bounds::EMPTY;
+        for child in &mut self.children {
+            child.set_transform(change * child.transform());
+            self.bounds = self.bounds.merge(child.parent_space_bounds());
+        }
+
+        self.transform = transform;
+        self.inverse = inverse;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        let ray = ray.transform(&self.transform);
+        self.intersect(&ray).iter().map(Intersection::t).collect()
+    }
+
+    /// # Panics
+    ///
+    /// Always panics: normals are taken from the operand that was hit.
+    fn local_normal_at(&self, _point: Point3) -> Vec3 {
+        panic!("a CSG shape has no surface; take the normal from the operand that was hit")
+    }
+
+    #[inline]
+    fn bounds(&self) -> Bounds { self.bounds.transform(&self.inverse) }
+
+    #[inline]
+    fn parent_space_bounds(&self) -> Bounds { self.bounds }
+
+    /// Intersects `ray` with both operands and keeps the hits that lie on
+    /// the combined surface.
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        if !self.bounds.intersects(ray) {
+            return Vec::new();
+        }
+
+        let xs = intersections(self.children.iter().flat_map(|child| child.intersect(ray)));
+        self.filter_intersections(&xs)
+    }
+
+    #[inline]
+    fn children(&self) -> &[Box<dyn Shape>] { &self.children }
+
+    /// Divides both operands; the CSG itself always keeps exactly two.
+    fn divide(&mut self, threshold: usize) {
+        for child in &mut self.children {
+            child.divide(threshold);
+        }
+    }
+}
+
+/// Returns whether a hit on the left operand (`lhit`) or the right one
+/// belongs to the surface of the combined shape, given whether the ray is
+/// currently inside the left (`inl`) and right (`inr`) operands.
+pub fn intersection_allowed(operation: CsgOperation, lhit: bool, inl: bool, inr: bool) -> bool {
+    match operation {
+        CsgOperation::Union => (lhit && !inr) || (!lhit && !inl),
+        CsgOperation::Intersection => (lhit && inr) || (!lhit && inl),
+        CsgOperation::Difference => (lhit && !inr) || (!lhit && inl),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Cube, Group, Sphere};
+    use crate::prelude::{point, vector};
+
+    #[test]
+    fn test_csg_is_created_with_an_operation_and_two_shapes() {
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new());
+        assert_eq!(c.operation(), CsgOperation::Union);
+        assert_eq!(c.children().len(), 2);
+        assert_eq!(c.left().bounds(), Sphere::new().bounds());
+    }
+
+    #[test]
+    fn test_evaluating_the_rule_for_a_csg_operation() {
+        use CsgOperation::{Difference, Intersection, Union};
+
+        #[rustfmt::skip]
+        let cases = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+        for (op, lhit, inl, inr, expected) in cases {
+            assert_eq!(
+                intersection_allowed(op, lhit, inl, inr),
+                expected,
+                "{op:?} {lhit} {inl} {inr}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_filtering_a_list_of_intersections() {
+        let cases = [
+            (CsgOperation::Union, [0, 3]),
+            (CsgOperation::Intersection, [1, 2]),
+            (CsgOperation::Difference, [0, 1]),
+        ];
+        for (op, [x0, x1]) in cases {
+            let c = Csg::new(op, Sphere::new(), Cube::new());
+            let (s1, s2) = (c.left(), c.right());
+            let xs = [
+                Intersection::new(1.0, s1),
+                Intersection::new(2.0, s2),
+                Intersection::new(3.0, s1),
+                Intersection::new(4.0, s2),
+            ];
+            assert_eq!(c.filter_intersections(&xs), [xs[x0], xs[x1]], "{op:?}");
+        }
+    }
+
+    #[test]
+    fn test_filtering_recognizes_hits_inside_nested_children() {
+        let left = Group::new().with_child(Sphere::new());
+        let c = Csg::new(CsgOperation::Difference, left, Cube::new());
+        let (s1, s2) = (c.left().children()[0].as_ref(), c.right());
+        let xs = [
+            Intersection::new(1.0, s1),
+            Intersection::new(2.0, s2),
+            Intersection::new(3.0, s1),
+            Intersection::new(4.0, s2),
+        ];
+        assert_eq!(c.filter_intersections(&xs), [xs[0], xs[1]]);
+    }
+
+    #[test]
+    fn test_ray_misses_a_csg_object() {
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new());
+        let r = Ray::new(point(0, 2, -5), vector(0, 0, 1));
+        assert!(c.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_hits_a_csg_object() {
+        let s2 = Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 0.5));
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), s2);
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t(), 4.0);
+        assert!(core::ptr::addr_eq(xs[0].object(), c.left()));
+        assert_eq!(xs[1].t(), 6.5);
+        assert!(core::ptr::addr_eq(xs[1].object(), c.right()));
+    }
+
+    #[test]
+    fn test_csg_has_a_bounding_box_that_contains_its_children() {
+        let right = Sphere::new().with_transform(Mat4::translation(2.0, 3.0, 4.0));
+        let c = Csg::new(CsgOperation::Difference, Sphere::new(), right);
+        assert_eq!(c.bounds(), Bounds::new(point(-1, -1, -1), point(3, 4, 5)));
+    }
+
+    #[test]
+    fn test_transforming_a_csg_moves_its_operands() {
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new())
+            .with_transform(Mat4::translation(0.0, 5.0, 0.0));
+        assert_eq!(c.left().transform(), Mat4::translation(0.0, 5.0, 0.0));
+        assert_eq!(
+            c.parent_space_bounds(),
+            Bounds::new(point(-1, 4, -1), point(1, 6, 1))
+        );
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert!(c.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_subdividing_a_csg_shape_subdivides_its_children() {
+        let left = Group::new()
+            .with_child(Sphere::new().with_transform(Mat4::translation(-1.5, 0.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(1.5, 0.0, 0.0)));
+        let right = Group::new()
+            .with_child(Sphere::new().with_transform(Mat4::translation(0.0, 0.0, -1.5)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 1.5)));
+        let mut shape = Csg::new(CsgOperation::Difference, left, right);
+
+        shape.divide(1);
+        for operand in [shape.left(), shape.right()] {
+            assert_eq!(operand.children().len(), 2);
+            for subgroup in operand.children() {
+                assert_eq!(subgroup.children().len(), 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crate::cmp::epsilon::EPSILON;
+use crate::geometry::{Bounds, Ray, Shape};
+use crate::math::{self, Interval};
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// An axis-aligned cube spanning `-1` to `1` on every axis in object space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cube {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+}
+
+impl Cube {
+    /// Creates a new cube with an identity transform.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+            material:  Material::new(),
+        }
+    }
+
+    /// Returns the cube with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the cube with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Shape for Cube {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    /// Returns zero or two `t` values in increasing order, found by clipping
+    /// the ray against the pair of planes bounding each axis.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        let (origin, direction) = (ray.origin(), ray.direction());
+        let span = check_axis(origin.x(), direction.x(), -1.0, 1.0)
+            .intersection(&check_axis(origin.y(), direction.y(), -1.0, 1.0))
+            .intersection(&check_axis(origin.z(), direction.z(), -1.0, 1.0));
+        if span.is_empty() {
+            return Vec::new();
+        }
+
+        vec![span.min, span.max]
+    }
+
+    /// Returns the normal of the face containing `point`, which is the axis
+    /// along which the point lies farthest from the center.
+    fn local_normal_at(&self, point: Point3) -> Vec3 {
+        let (x, y, z) = (math::abs(point.x()), math::abs(point.y()), math::abs(point.z()));
+        let maxc = math::max(x, math::max(y, z));
+
+        if maxc == x {
+            Vec3::new(point.x(), 0.0, 0.0)
+        } else if maxc == y {
+            Vec3::new(0.0, point.y(), 0.0)
+        } else {
+            Vec3::new(0.0, 0.0, point.z())
+        }
+    }
+
+    #[inline]
+    fn bounds(&self) -> Bounds { Bounds::new(Point3::NEG_ONE, Point3::ONE) }
+}
+
+impl Default for Cube {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+/// Returns the range of `t` over which a ray is inside the slab between
+/// `min` and `max` along a single axis.
+///
+/// A ray parallel to the slab gets infinite bounds, signed so that it is
+/// either always or never inside.
+pub(crate) fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> Interval {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    let (tmin, tmax) = if math::abs(direction) >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (tmin_numerator * f64::INFINITY, tmax_numerator * f64::INFINITY)
+    };
+
+    if tmin > tmax {
+        Interval::new(tmax, tmin)
+    } else {
+        Interval::new(tmin, tmax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{point, vector};
+
+    #[test]
+    fn test_ray_intersects_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point(5.0, 0.5, 0.0), vector(-1, 0, 0), 4.0, 6.0), // +x
+            (point(-5.0, 0.5, 0.0), vector(1, 0, 0), 4.0, 6.0), // -x
+            (point(0.5, 5.0, 0.0), vector(0, -1, 0), 4.0, 6.0), // +y
+            (point(0.5, -5.0, 0.0), vector(0, 1, 0), 4.0, 6.0), // -y
+            (point(0.5, 0.0, 5.0), vector(0, 0, -1), 4.0, 6.0), // +z
+            (point(0.5, 0.0, -5.0), vector(0, 0, 1), 4.0, 6.0), // -z
+            (point(0.0, 0.5, 0.0), vector(0, 0, 1), -1.0, 1.0), // inside
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let xs = c.local_intersect(&Ray::new(origin, direction));
+            assert_eq!(xs, [t1, t2], "ray from {origin:?} along {direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_ray_misses_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point(-2, 0, 0), vector(0.2673, 0.5345, 0.8018)),
+            (point(0, -2, 0), vector(0.8018, 0.2673, 0.5345)),
+            (point(0, 0, -2), vector(0.5345, 0.8018, 0.2673)),
+            (point(2, 0, 2), vector(0, 0, -1)),
+            (point(0, 2, 2), vector(0, -1, 0)),
+            (point(2, 2, 0), vector(-1, 0, 0)),
+        ];
+        for (origin, direction) in cases {
+            let xs = c.local_intersect(&Ray::new(origin, direction));
+            assert!(xs.is_empty(), "ray from {origin:?} along {direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_normal_on_the_surface_of_a_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point(1.0, 0.5, -0.8), vector(1, 0, 0)),
+            (point(-1.0, -0.2, 0.9), vector(-1, 0, 0)),
+            (point(-0.4, 1.0, -0.1), vector(0, 1, 0)),
+            (point(0.3, -1.0, -0.7), vector(0, -1, 0)),
+            (point(-0.6, 0.3, 1.0), vector(0, 0, 1)),
+            (point(0.4, 0.4, -1.0), vector(0, 0, -1)),
+            (point(1, 1, 1), vector(1, 0, 0)),
+            (point(-1, -1, -1), vector(-1, 0, 0)),
+        ];
+        for (p, normal) in cases {
+            assert_eq!(c.local_normal_at(p), normal, "normal at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_intersecting_a_transformed_cube() {
+        let c = Cube::new().with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let ts: Vec<_> = c.intersect(&r).iter().map(|i| i.t()).collect();
+        assert_eq!(ts, [3.0, 7.0]);
+    }
+
+    #[test]
+    fn test_cube_has_a_bounding_box() {
+        assert_eq!(
+            Cube::new().bounds(),
+            Bounds::new(point(-1, -1, -1), point(1, 1, 1))
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::cmp::epsilon::EPSILON;
+use crate::geometry::{Bounds, Ray, Shape};
+use crate::math::{self, Interval};
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// A cylinder of radius one around the y axis in object space.
+///
+/// By default it is infinitely long and open. It may be truncated to lie
+/// strictly between `minimum` and `maximum` on the y axis, and those ends
+/// capped by making it `closed`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cylinder {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+    minimum:   f64,
+    maximum:   f64,
+    closed:    bool,
+}
+
+impl Cylinder {
+    /// Creates a new infinite, open cylinder with an identity transform.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+            material:  Material::new(),
+            minimum:   f64::NEG_INFINITY,
+            maximum:   f64::INFINITY,
+            closed:    false,
+        }
+    }
+
+    /// Returns the cylinder with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the cylinder with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Returns the cylinder truncated to the y range `minimum..maximum`.
+    #[inline]
+    #[must_use]
+    pub const fn with_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.minimum = minimum;
+        self.maximum = maximum;
+        self
+    }
+
+    /// Returns the cylinder with its ends capped if `closed` is `true`.
+    #[inline]
+    #[must_use]
+    pub const fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Returns the lower bound on y, exclusive.
+    #[inline]
+    pub const fn minimum(&self) -> f64 { self.minimum }
+
+    /// Returns the upper bound on y, exclusive.
+    #[inline]
+    pub const fn maximum(&self) -> f64 { self.maximum }
+
+    /// Returns whether the ends are capped.
+    #[inline]
+    pub const fn closed(&self) -> bool { self.closed }
+}
+
+impl Shape for Cylinder {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    /// Returns the `t` of every hit on the curved side within the y limits,
+    /// followed by any hits on the end caps.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        let (origin, direction) = (ray.origin(), ray.direction());
+        let mut xs = Vec::new();
+
+        // A ray parallel to the y axis can only strike the caps.
+        let a = direction.x() * direction.x() + direction.z() * direction.z();
+        if math::abs(a) >= EPSILON {
+            let b = 2.0 * origin.x() * direction.x() + 2.0 * origin.z() * direction.z();
+            let c = origin.x() * origin.x() + origin.z() * origin.z() - 1.0;
+
+            let Some((t0, t1)) = math::solve_quadratic(a, b, c) else {
+                return xs;
+            };
+            for t in [t0, t1] {
+                let y = origin.y() + t * direction.y();
+                if Interval::new(self.minimum, self.maximum).surrounds(y) {
+                    xs.push(t);
+                }
+            }
+        }
+
+        self.intersect_caps(ray, &mut xs);
+        xs
+    }
+
+    /// Returns the cap normal on the flat ends and the radial normal on the
+    /// curved side.
+    fn local_normal_at(&self, point: Point3) -> Vec3 {
+        let dist = point.x() * point.x() + point.z() * point.z();
+
+        if dist < 1.0 && point.y() >= self.maximum - EPSILON {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else if dist < 1.0 && point.y() <= self.minimum + EPSILON {
+            Vec3::new(0.0, -1.0, 0.0)
+        } else {
+            Vec3::new(point.x(), 0.0, point.z())
+        }
+    }
+
+    #[inline]
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point3::new(-1.0, self.minimum, -1.0),
+            Point3::new(1.0, self.maximum, 1.0),
+        )
+    }
+}
+
+impl Cylinder {
+    /// Appends the `t` of each end cap that `ray` strikes, if the cylinder is
+    /// closed.
+    fn intersect_caps(&self, ray: &Ray, xs: &mut Vec<f64>) {
+        let (origin, direction) = (ray.origin(), ray.direction());
+        if !self.closed || math::abs(direction.y()) < EPSILON {
+            return;
+        }
+
+        for y in [self.minimum, self.maximum] {
+            let t = (y - origin.y()) / direction.y();
+            if check_cap(ray, t) {
+                xs.push(t);
+            }
+        }
+    }
+}
+
+impl Default for Cylinder {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+/// Returns whether the point at `t` along `ray` lies within the unit radius
+/// of a cap.
+fn check_cap(ray: &Ray, t: f64) -> bool {
+    let x = ray.origin().x() + t * ray.direction().x();
+    let z = ray.origin().z() + t * ray.direction().z();
+    x * x + z * z <= 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmp::float::is_equal;
+    use crate::prelude::{point, vector};
+
+    #[test]
+    fn test_ray_misses_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point(1, 0, 0), vector(0, 1, 0)),
+            (point(0, 0, 0), vector(0, 1, 0)),
+            (point(0, 0, -5), vector(1, 1, 1)),
+        ];
+        for (origin, direction) in cases {
+            let xs = cyl.local_intersect(&Ray::new(origin, direction.normalize()));
+            assert!(xs.is_empty(), "ray from {origin:?} along {direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_ray_strikes_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point(1, 0, -5), vector(0, 0, 1), 5.0, 5.0),
+            (point(0, 0, -5), vector(0, 0, 1), 4.0, 6.0),
+            (point(0.5, 0.0, -5.0), vector(0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+        for (origin, direction, t0, t1) in cases {
+            let xs = cyl.local_intersect(&Ray::new(origin, direction.normalize()));
+            assert_eq!(xs.len(), 2);
+            assert!(
+                is_equal(xs[0], t0) && is_equal(xs[1], t1),
+                "{xs:?} != [{t0}, {t1}]"
+            );
+        }
+    }
+
+    #[test]
+    fn test_normal_vector_on_a_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point(1, 0, 0), vector(1, 0, 0)),
+            (point(0, 5, -1), vector(0, 0, -1)),
+            (point(0, -2, 1), vector(0, 0, 1)),
+            (point(-1, 1, 0), vector(-1, 0, 0)),
+        ];
+        for (p, normal) in cases {
+            assert_eq!(cyl.local_normal_at(p), normal, "normal at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_default_minimum_and_maximum_for_a_cylinder() {
+        let cyl = Cylinder::new();
+        assert_eq!(cyl.minimum(), f64::NEG_INFINITY);
+        assert_eq!(cyl.maximum(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_intersecting_a_constrained_cylinder() {
+        let cyl = Cylinder::new().with_limits(1.0, 2.0);
+        let cases = [
+            (point(0.0, 1.5, 0.0), vector(0.1, 1.0, 0.0), 0),
+            (point(0, 3, -5), vector(0, 0, 1), 0),
+            (point(0, 0, -5), vector(0, 0, 1), 0),
+            (point(0, 2, -5), vector(0, 0, 1), 0),
+            (point(0, 1, -5), vector(0, 0, 1), 0),
+            (point(0.0, 1.5, -2.0), vector(0, 0, 1), 2),
+        ];
+        for (origin, direction, count) in cases {
+            let xs = cyl.local_intersect(&Ray::new(origin, direction.normalize()));
+            assert_eq!(xs.len(), count, "ray from {origin:?} along {direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_default_closed_value_for_a_cylinder() {
+        assert!(!Cylinder::new().closed());
+    }
+
+    #[test]
+    fn test_intersecting_the_caps_of_a_closed_cylinder() {
+        let cyl = Cylinder::new().with_limits(1.0, 2.0).with_closed(true);
+        let cases = [
+            (point(0, 3, 0), vector(0, -1, 0)),
+            (point(0, 3, -2), vector(0, -1, 2)),
+            (point(0, 4, -2), vector(0, -1, 1)),
+            (point(0, 0, -2), vector(0, 1, 2)),
+            (point(0, -1, -2), vector(0, 1, 1)),
+        ];
+        for (origin, direction) in cases {
+            let xs = cyl.local_intersect(&Ray::new(origin, direction.normalize()));
+            assert_eq!(xs.len(), 2, "ray from {origin:?} along {direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_normal_vector_on_a_cylinders_end_caps() {
+        let cyl = Cylinder::new().with_limits(1.0, 2.0).with_closed(true);
+        let cases = [
+            (point(0, 1, 0), vector(0, -1, 0)),
+            (point(0.5, 1.0, 0.0), vector(0, -1, 0)),
+            (point(0.0, 1.0, 0.5), vector(0, -1, 0)),
+            (point(0, 2, 0), vector(0, 1, 0)),
+            (point(0.5, 2.0, 0.0), vector(0, 1, 0)),
+            (point(0.0, 2.0, 0.5), vector(0, 1, 0)),
+        ];
+        for (p, normal) in cases {
+            assert_eq!(cyl.local_normal_at(p), normal, "normal at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_cylinder_has_a_bounding_box() {
+        let b = Cylinder::new().bounds();
+        assert_eq!(b.min, point(-1.0, f64::NEG_INFINITY, -1.0));
+        assert_eq!(b.max, point(1.0, f64::INFINITY, 1.0));
+
+        let b = Cylinder::new().with_limits(-5.0, 3.0).bounds();
+        assert_eq!(b, Bounds::new(point(-1, -5, -1), point(1, 3, 1)));
+    }
+}
@@ -0,0 +1,466 @@
+use crate::geometry::{Bounds, Intersection, Ray, Shape, intersections};
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// The shapes owned by a group.
+type Children = Vec<Box<dyn Shape>>;
+
+/// A collection of shapes transformed as a single unit.
+///
+/// Rather than keeping a pointer to its parent, every child has the group's
+/// transform folded into its own when it is added, and again whenever the
+/// group's transform changes. A child's transform therefore always maps
+/// straight from its object space to the group's parent space, which keeps
+/// intersections and normals free of any parent-chain walking.
+#[derive(Debug)]
+pub struct Group {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+    children:  Vec<Box<dyn Shape>>,
+    /// Union of the children's bounds, kept in the group's parent space.
+    bounds:    Bounds,
+}
+
+impl Group {
+    /// Creates a new empty group with an identity transform.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+            material:  Material::new(),
+            children:  Vec::new(),
+            bounds:    Bounds::EMPTY,
+        }
+    }
+
+    /// Returns the group with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the group with `child` added to it.
+    #[inline]
+    #[must_use]
+    pub fn with_child(mut self, child: impl Into<Box<dyn Shape>>) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Adds `child` to the group, placing it under the group's transform.
+    pub fn add_child(&mut self, child: impl Into<Box<dyn Shape>>) {
+        let mut child = child.into();
+        child.set_transform(self.transform * child.transform());
+        self.bounds = self.bounds.merge(child.parent_space_bounds());
+        self.children.push(child);
+    }
+
+    /// Returns whether the group has no children.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    /// Removes the children that fit entirely within either half of the
+    /// group's bounds and returns them as `(left, right)`. Children that
+    /// straddle the split stay in the group.
+    fn partition_children(&mut self) -> (Children, Children) {
+        let (left_bounds, right_bounds) = self.bounds.split();
+        let (mut left, mut right, mut rest) = (Vec::new(), Vec::new(), Vec::new());
+        for child in self.children.drain(..) {
+            let bounds = child.parent_space_bounds();
+            if left_bounds.contains_box(&bounds) {
+                left.push(child);
+            } else if right_bounds.contains_box(&bounds) {
+                right.push(child);
+            } else {
+                rest.push(child);
+            }
+        }
+
+        self.children = rest;
+        self.bounds = bounds_of(&self.children);
+        (left, right)
+    }
+
+    /// Adds a new subgroup holding `children` to the group.
+    ///
+    /// The children already have this group's transform folded in, so the
+    /// subgroup shares that transform rather than applying it a second time.
+    fn make_subgroup(&mut self, children: Children) {
+        let bounds = bounds_of(&children);
+        self.bounds = self.bounds.merge(bounds);
+        self.children.push(Box::new(Self {
+            transform: self.transform,
+            inverse: self.inverse,
+            material: Material::new(),
+            children,
+            bounds,
+        }));
+    }
+}
+
+/// Returns the union of the parent-space bounds of `shapes`.
+fn bounds_of(shapes: &[Box<dyn Shape>]) -> Bounds {
+    shapes.iter().fold(Bounds::EMPTY, |bounds, shape| {
+        bounds.merge(shape.parent_space_bounds())
+    })
+}
+
+impl Shape for Group {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    /// Sets the group's transform and carries the change down to every
+    /// child, nested groups included.
+    fn set_transform(&mut self, transform: Mat4) {
+        let inverse = transform.inverse().expect("shape transform must be invertible");
+        let change = transform * self.inverse;
+        self.bounds = Bounds::EMPTY;
+        for child in &mut self.children {
+            child.set_transform(change * child.transform());
+            self.bounds = self.bounds.merge(child.parent_space_bounds());
+        }
+
+        self.transform = transform;
+        self.inverse = inverse;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        let ray = ray.transform(&self.transform);
+        self.intersect(&ray).iter().map(Intersection::t).collect()
+    }
+
+    /// # Panics
+    ///
+    /// Always panics: a group has no surface of its own, and normals are
+    /// taken from the child that was hit.
+    fn local_normal_at(&self, _point: Point3) -> Vec3 {
+        panic!("a group has no surface; take the normal from the child that was hit")
+    }
+
+    /// Returns the union of the children's bounds in the group's object
+    /// space.
+    #[inline]
+    fn bounds(&self) -> Bounds { self.bounds.transform(&self.inverse) }
+
+    /// Returns the union of the children's bounds, which are already kept in
+    /// the group's parent space.
+    #[inline]
+    fn parent_space_bounds(&self) -> Bounds { self.bounds }
+
+    /// Intersects `ray` with every child, returning all intersections sorted
+    /// by increasing `t`. Children are skipped entirely if the ray misses
+    /// their combined bounds.
+    ///
+    /// Children already include the group's transform, so the ray is passed
+    /// to them unchanged.
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        if !self.bounds.intersects(ray) {
+            return Vec::new();
+        }
+        intersections(self.children.iter().flat_map(|child| child.intersect(ray)))
+    }
+
+    #[inline]
+    fn children(&self) -> &[Box<dyn Shape>] { &self.children }
+
+    /// Once the group holds at least `threshold` children, moves those that
+    /// fit within either half of its bounds into a subgroup per half, then
+    /// divides every child in turn.
+    fn divide(&mut self, threshold: usize) {
+        // A box with no extent cannot be split, and every child would land
+        // in the same subgroup forever.
+        if threshold <= self.children.len() && self.bounds.min != self.bounds.max {
+            let (left, right) = self.partition_children();
+            if !left.is_empty() {
+                self.make_subgroup(left);
+            }
+            if !right.is_empty() {
+                self.make_subgroup(right);
+            }
+        }
+
+        for child in &mut self.children {
+            child.divide(threshold);
+        }
+    }
+}
+
+impl Default for Group {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::shape::test_shape::TestShape;
+    use crate::geometry::{Cylinder, Sphere};
+    use crate::prelude::{FRAC_PI_2, point, vector};
+
+    /// Returns the address of each shape, for checking which ones moved.
+    fn ids(shapes: &[Box<dyn Shape>]) -> Vec<*const ()> {
+        shapes
+            .iter()
+            .map(|shape| core::ptr::from_ref::<dyn Shape>(shape.as_ref()).cast())
+            .collect()
+    }
+
+    #[test]
+    fn test_creating_a_new_group() {
+        let g = Group::new();
+        assert_eq!(g.transform(), Mat4::IDENTITY);
+        assert!(g.is_empty());
+    }
+
+    #[test]
+    fn test_adding_a_child_to_a_group() {
+        let s = Sphere::new().with_transform(Mat4::translation(1.0, 0.0, 0.0));
+        let g = Group::new()
+            .with_transform(Mat4::scaling(2.0, 2.0, 2.0))
+            .with_child(s);
+        assert_eq!(g.children().len(), 1);
+        assert_eq!(
+            g.children()[0].transform(),
+            Mat4::scaling(2.0, 2.0, 2.0) * Mat4::translation(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_intersecting_a_ray_with_an_empty_group() {
+        let g = Group::new();
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        assert!(g.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_intersecting_a_ray_with_a_nonempty_group() {
+        let g = Group::new()
+            .with_child(Sphere::new())
+            .with_child(Sphere::new().with_transform(Mat4::translation(0.0, 0.0, -3.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0)));
+        let (s1, s2) = (g.children()[0].as_ref(), g.children()[1].as_ref());
+
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let xs = g.intersect(&r);
+        assert_eq!(xs.len(), 4);
+        assert!(core::ptr::addr_eq(xs[0].object(), s2));
+        assert!(core::ptr::addr_eq(xs[1].object(), s2));
+        assert!(core::ptr::addr_eq(xs[2].object(), s1));
+        assert!(core::ptr::addr_eq(xs[3].object(), s1));
+    }
+
+    #[test]
+    fn test_intersecting_a_transformed_group() {
+        let g = Group::new()
+            .with_transform(Mat4::scaling(2.0, 2.0, 2.0))
+            .with_child(Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0)));
+        let r = Ray::new(point(10, 0, -10), vector(0, 0, 1));
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn test_transforming_a_group_after_adding_children() {
+        let mut g = Group::new().with_child(Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0)));
+        g.set_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(point(10, 0, -10), vector(0, 0, 1));
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    fn nested() -> Group {
+        let mut g1 = Group::new().with_transform(Mat4::rotation_y(FRAC_PI_2));
+        let g2 = Group::new()
+            .with_transform(Mat4::scaling(1.0, 2.0, 3.0))
+            .with_child(Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0)));
+        g1.add_child(g2);
+        g1
+    }
+
+    #[test]
+    fn test_converting_a_point_from_world_to_object_space() {
+        let mut g1 = Group::new().with_transform(Mat4::rotation_y(FRAC_PI_2));
+        g1.add_child(
+            Group::new()
+                .with_transform(Mat4::scaling(2.0, 2.0, 2.0))
+                .with_child(Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0))),
+        );
+        let s = &g1.children()[0].children()[0];
+        assert_eq!(s.world_to_object(point(-2, 0, -10)), point(0, 0, -1));
+    }
+
+    #[test]
+    fn test_converting_a_normal_from_object_to_world_space() {
+        let g1 = nested();
+        let s = &g1.children()[0].children()[0];
+        let k = 3_f64.sqrt() / 3.0;
+        assert_eq!(
+            s.normal_to_world(vector(k, k, k)),
+            vector(2.0 / 7.0, 3.0 / 7.0, -6.0 / 7.0)
+        );
+    }
+
+    #[test]
+    fn test_finding_the_normal_on_a_child_object() {
+        let g1 = nested();
+        let s = &g1.children()[0].children()[0];
+        // The book gives this point to four places, so allow for that.
+        let n = s.normal_at(point(1.7321, 1.1547, -5.5774));
+        assert!((n - vector(0.2857, 0.4286, -0.8571)).length() < 1e-4, "{n:?}");
+    }
+
+    #[test]
+    fn test_group_has_a_bounding_box_that_contains_its_children() {
+        let s =
+            Sphere::new().with_transform(Mat4::translation(2.0, 5.0, -3.0) * Mat4::scaling(2.0, 2.0, 2.0));
+        let c = Cylinder::new()
+            .with_limits(-2.0, 2.0)
+            .with_transform(Mat4::translation(-4.0, -1.0, 4.0) * Mat4::scaling(0.5, 1.0, 0.5));
+        let g = Group::new().with_child(s).with_child(c);
+        assert_eq!(
+            g.bounds(),
+            Bounds::new(point(-4.5, -3.0, -5.0), point(4.0, 7.0, 4.5))
+        );
+    }
+
+    #[test]
+    fn test_group_bounds_follow_its_transform() {
+        let mut g = Group::new().with_child(Sphere::new());
+        g.set_transform(Mat4::translation(10.0, 0.0, 0.0));
+        assert_eq!(
+            g.parent_space_bounds(),
+            Bounds::new(point(9, -1, -1), point(11, 1, 1))
+        );
+        assert_eq!(g.bounds(), Bounds::new(point(-1, -1, -1), point(1, 1, 1)));
+    }
+
+    #[test]
+    fn test_intersecting_ray_and_group_skips_children_if_box_is_missed() {
+        let child = TestShape::new();
+        let saved_ray = child.recorder();
+        let g = Group::new().with_child(child);
+        let r = Ray::new(point(0, 0, -5), vector(0, 1, 0));
+        g.intersect(&r);
+        assert!(saved_ray.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_intersecting_ray_and_group_tests_children_if_box_is_hit() {
+        let child = TestShape::new();
+        let saved_ray = child.recorder();
+        let g = Group::new().with_child(child);
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        g.intersect(&r);
+        assert!(saved_ray.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_partitioning_a_groups_children() {
+        let mut g = Group::new()
+            .with_child(Sphere::new().with_transform(Mat4::translation(-2.0, 0.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(2.0, 0.0, 0.0)))
+            .with_child(Sphere::new());
+        let [s1, s2, s3] = ids(g.children())[..] else {
+            unreachable!()
+        };
+
+        let (left, right) = g.partition_children();
+        assert_eq!(ids(g.children()), [s3]);
+        assert_eq!(ids(&left), [s1]);
+        assert_eq!(ids(&right), [s2]);
+    }
+
+    #[test]
+    fn test_creating_a_subgroup_from_a_list_of_children() {
+        let mut g = Group::new();
+        let children: Vec<Box<dyn Shape>> = vec![Box::new(Sphere::new()), Box::new(Sphere::new())];
+        let expected = ids(&children);
+
+        g.make_subgroup(children);
+        assert_eq!(g.children().len(), 1);
+        assert_eq!(ids(g.children()[0].children()), expected);
+    }
+
+    #[test]
+    fn test_subdividing_a_primitive_does_nothing() {
+        let mut s = Sphere::new();
+        s.divide(1);
+        assert_eq!(s, Sphere::new());
+    }
+
+    #[test]
+    fn test_subdividing_a_group_partitions_its_children() {
+        let mut g = Group::new()
+            .with_child(Sphere::new().with_transform(Mat4::translation(-2.0, -2.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(-2.0, 2.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::scaling(4.0, 4.0, 4.0)));
+        let [s1, s2, s3] = ids(g.children())[..] else {
+            unreachable!()
+        };
+
+        g.divide(1);
+        assert_eq!(ids(&g.children()[..1]), [s3]);
+        let subgroup = g.children()[1].children();
+        assert_eq!(subgroup.len(), 2);
+        assert_eq!(ids(subgroup[0].children()), [s1]);
+        assert_eq!(ids(subgroup[1].children()), [s2]);
+    }
+
+    #[test]
+    fn test_subdividing_a_group_with_too_few_children() {
+        let subgroup = Group::new()
+            .with_child(Sphere::new().with_transform(Mat4::translation(-2.0, 0.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(2.0, 1.0, 0.0)))
+            .with_child(Sphere::new().with_transform(Mat4::translation(2.0, -1.0, 0.0)));
+        let mut g = Group::new().with_child(subgroup).with_child(Sphere::new());
+        let [s1, s2, s3] = ids(g.children()[0].children())[..] else {
+            unreachable!()
+        };
+        let before = ids(g.children());
+
+        g.divide(3);
+        assert_eq!(ids(g.children()), before);
+        let subgroup = g.children()[0].children();
+        assert_eq!(subgroup.len(), 2);
+        assert_eq!(ids(subgroup[0].children()), [s1]);
+        assert_eq!(ids(subgroup[1].children()), [s2, s3]);
+    }
+
+    #[test]
+    fn test_subdividing_a_group_keeps_its_intersections() {
+        let mut g = Group::new().with_transform(Mat4::scaling(0.5, 0.5, 0.5));
+        for i in -4..=4 {
+            for j in -4..=4 {
+                let (x, y) = (f64::from(i) * 3.0, f64::from(j) * 3.0);
+                g.add_child(Sphere::new().with_transform(Mat4::translation(x, y, 0.0)));
+            }
+        }
+        let rays = [
+            Ray::new(point(0, 0, -5), vector(0, 0, 1)),
+            Ray::new(point(3, -1.5, -5), vector(0.1, 0.2, 1).normalize()),
+            Ray::new(point(-10, 0.2, 0), vector(1, 0, 0)),
+        ];
+        let ts = |g: &Group| rays.map(|r| g.intersect(&r).iter().map(Intersection::t).collect::<Vec<_>>());
+        let before = ts(&g);
+
+        g.divide(4);
+        assert!(g.children().len() < 81);
+        assert_eq!(ts(&g), before);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::geometry::Shape;
+
+/// A record of a ray striking an object at distance `t`.
+///
+/// Shapes that need to know where on their surface they were struck, such as
+/// [`SmoothTriangle`](crate::geometry::SmoothTriangle), also record the
+/// barycentric `u` and `v` of the hit.
+#[derive(Clone, Copy, Debug)]
+pub struct Intersection<'a> {
+    t:      f64,
+    object: &'a dyn Shape,
+    uv:     Option<(f64, f64)>,
+}
+
+impl<'a> Intersection<'a> {
+    /// Creates a new intersection.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(t: f64, object: &'a dyn Shape) -> Self { Self { t, object, uv: None } }
+
+    /// Creates a new intersection that remembers the surface coordinates
+    /// `u` and `v` of the hit.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_uv(t: f64, object: &'a dyn Shape, u: f64, v: f64) -> Self {
+        Self {
+            t,
+            object,
+            uv: Some((u, v)),
+        }
+    }
+
+    /// Returns the distance along the ray.
+    #[inline]
+    pub const fn t(&self) -> f64 { self.t }
+
+    /// Returns the object that was hit.
+    #[inline]
+    pub const fn object(&self) -> &'a dyn Shape { self.object }
+
+    /// Returns the surface coordinates `(u, v)` of the hit, if the shape
+    /// recorded them.
+    #[inline]
+    pub const fn uv(&self) -> Option<(f64, f64)> { self.uv }
+}
+
+impl PartialEq for Intersection<'_> {
+    /// Two intersections are equal when they share `t` and refer to the very
+    /// same object.
+    fn eq(&self, other: &Self) -> bool {
+        self.t == other.t && core::ptr::addr_eq(self.object, other.object)
+    }
+}
+
+/// Collects intersections into a list sorted by increasing `t`.
+pub fn intersections<'a>(xs: impl IntoIterator<Item = Intersection<'a>>) -> Vec<Intersection<'a>> {
+    let mut xs: Vec<_> = xs.into_iter().collect();
+    xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+    xs
+}
+
+/// Returns the visible intersection: the one with the smallest non-negative
+/// `t`, or `None` if every intersection lies behind the ray's origin.
+pub fn hit<'a, 'b>(xs: &'b [Intersection<'a>]) -> Option<&'b Intersection<'a>> {
+    xs.iter()
+        .filter(|i| i.t >= 0.0)
+        .min_by(|a, b| a.t.total_cmp(&b.t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Sphere;
+
+    #[test]
+    fn test_intersection_encapsulates_t_and_object() {
+        let s = Sphere::new();
+        let i = Intersection::new(3.5, &s);
+        assert_eq!(i.t(), 3.5);
+        assert!(core::ptr::addr_eq(i.object(), &s));
+    }
+
+    #[test]
+    fn test_intersection_can_encapsulate_u_and_v() {
+        let s = Sphere::new();
+        let i = Intersection::with_uv(3.5, &s, 0.2, 0.4);
+        assert_eq!(i.uv(), Some((0.2, 0.4)));
+        assert_eq!(Intersection::new(3.5, &s).uv(), None);
+    }
+
+    #[test]
+    fn test_intersections_are_sorted() {
+        let s = Sphere::new();
+        let xs = intersections([Intersection::new(2.0, &s), Intersection::new(1.0, &s)]);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t(), 1.0);
+        assert_eq!(xs[1].t(), 2.0);
+    }
+
+    #[test]
+    fn test_hit_when_all_intersections_have_positive_t() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(1.0, &s);
+        let i2 = Intersection::new(2.0, &s);
+        let xs = intersections([i2, i1]);
+        assert_eq!(hit(&xs), Some(&i1));
+    }
+
+    #[test]
+    fn test_hit_when_some_intersections_have_negative_t() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(-1.0, &s);
+        let i2 = Intersection::new(1.0, &s);
+        let xs = intersections([i2, i1]);
+        assert_eq!(hit(&xs), Some(&i2));
+    }
+
+    #[test]
+    fn test_hit_when_all_intersections_have_negative_t() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(-2.0, &s);
+        let i2 = Intersection::new(-1.0, &s);
+        let xs = intersections([i2, i1]);
+        assert_eq!(hit(&xs), None);
+    }
+
+    #[test]
+    fn test_hit_is_always_the_lowest_nonnegative_intersection() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(5.0, &s);
+        let i2 = Intersection::new(7.0, &s);
+        let i3 = Intersection::new(-3.0, &s);
+        let i4 = Intersection::new(2.0, &s);
+        let xs = [i1, i2, i3, i4];
+        assert_eq!(hit(&xs), Some(&i4));
+    }
+}
@@ -1 +1,26 @@
+mod bounds;
+mod csg;
+mod cube;
+mod cylinder;
+mod group;
+mod intersection;
+mod plane;
+mod ray;
+mod shape;
+mod smooth_triangle;
+mod sphere;
+mod triangle;
 
+pub use bounds::Bounds;
+pub use csg::{Csg, CsgOperation, intersection_allowed};
+pub use cube::Cube;
+pub use cylinder::Cylinder;
+pub use group::Group;
+pub use intersection::{Intersection, hit, intersections};
+pub use plane::Plane;
+pub use ray::Ray;
+pub(crate) use shape::includes;
+pub use shape::{AsShape, Shape};
+pub use smooth_triangle::SmoothTriangle;
+pub use sphere::Sphere;
+pub use triangle::Triangle;
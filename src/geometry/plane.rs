@@ -0,0 +1,168 @@
+use crate::cmp::epsilon::EPSILON;
+use crate::geometry::{Bounds, Ray, Shape};
+use crate::math;
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// An infinite plane spanning x and z in object space, facing up along +y.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+}
+
+impl Plane {
+    /// The object-space normal shared by every point on the plane.
+    const NORMAL: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+
+    /// Creates a new plane with an identity transform.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+            material:  Material::new(),
+        }
+    }
+
+    /// Returns the plane with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the plane with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Shape for Plane {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    /// Returns the `t` of the single hit. A ray parallel to the plane,
+    /// including one lying within it, misses.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        if math::abs(ray.direction().y()) < EPSILON {
+            return Vec::new();
+        }
+
+        vec![-ray.origin().y() / ray.direction().y()]
+    }
+
+    /// Returns the same normal everywhere on the plane.
+    #[inline]
+    fn local_normal_at(&self, _point: Point3) -> Vec3 { Self::NORMAL }
+
+    /// Returns a box that is flat in y and unbounded in x and z.
+    #[inline]
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point3::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point3::new(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+}
+
+impl Default for Plane {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{FRAC_PI_2, Intersection, point, vector};
+
+    fn ts(xs: &[Intersection]) -> Vec<f64> { xs.iter().map(Intersection::t).collect() }
+
+    #[test]
+    fn test_normal_of_a_plane_is_constant_everywhere() {
+        let p = Plane::new();
+        assert_eq!(p.normal_at(point(0, 0, 0)), vector(0, 1, 0));
+        assert_eq!(p.normal_at(point(10, 0, -10)), vector(0, 1, 0));
+        assert_eq!(p.normal_at(point(-5, 0, 150)), vector(0, 1, 0));
+    }
+
+    #[test]
+    fn test_normal_of_a_transformed_plane() {
+        let p = Plane::new().with_transform(Mat4::rotation_z(FRAC_PI_2));
+        assert_eq!(p.normal_at(point(0, 0, 0)), vector(-1, 0, 0));
+    }
+
+    #[test]
+    fn test_intersect_with_a_ray_parallel_to_the_plane() {
+        let p = Plane::new();
+        let r = Ray::new(point(0, 10, 0), vector(0, 0, 1));
+        assert!(p.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_with_a_coplanar_ray() {
+        let p = Plane::new();
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        assert!(p.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_intersecting_a_plane_from_above() {
+        let p = Plane::new();
+        let r = Ray::new(point(0, 1, 0), vector(0, -1, 0));
+        assert_eq!(ts(&p.intersect(&r)), [1.0]);
+    }
+
+    #[test]
+    fn test_ray_intersecting_a_plane_from_below() {
+        let p = Plane::new();
+        let r = Ray::new(point(0, -1, 0), vector(0, 1, 0));
+        assert_eq!(ts(&p.intersect(&r)), [1.0]);
+    }
+
+    #[test]
+    fn test_intersect_sets_the_object() {
+        let p = Plane::new();
+        let r = Ray::new(point(0, 1, 0), vector(0, -1, 0));
+        let xs = p.intersect(&r);
+        assert!(core::ptr::addr_eq(xs[0].object(), &p));
+    }
+
+    #[test]
+    fn test_ray_intersecting_a_translated_plane() {
+        let p = Plane::new().with_transform(Mat4::translation(0.0, 2.0, 0.0));
+        let r = Ray::new(point(0, 5, 0), vector(0, -1, 0));
+        assert_eq!(ts(&p.intersect(&r)), [3.0]);
+    }
+
+    #[test]
+    fn test_plane_has_a_bounding_box() {
+        let b = Plane::new().bounds();
+        assert_eq!(b.min, point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY));
+        assert_eq!(b.max, point(f64::INFINITY, 0.0, f64::INFINITY));
+    }
+}
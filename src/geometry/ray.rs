@@ -0,0 +1,181 @@
+use crate::graphics::camera::Camera;
+use crate::graphics::canvas::Canvas;
+use crate::math;
+use crate::primitives::{Color3, Mat4, Point3, Vec3};
+
+/// A ray with an origin and a direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ray {
+    origin:    Point3,
+    direction: Vec3,
+}
+
+impl Ray {
+    /// Creates a new ray.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(origin: Point3, direction: Vec3) -> Self { Self { origin, direction } }
+
+    /// Returns the ray's origin.
+    #[inline]
+    pub const fn origin(&self) -> Point3 { self.origin }
+
+    /// Returns the ray's direction.
+    #[inline]
+    pub const fn direction(&self) -> Vec3 { self.direction }
+}
+
+impl Ray {
+    /// Returns the point at distance `t` along the ray.
+    #[inline]
+    #[must_use]
+    pub fn position(self, t: f64) -> Point3 { self.origin + self.direction * t }
+
+    /// Returns a new ray with `m` applied to both its origin and direction.
+    #[inline]
+    #[must_use]
+    pub fn transform(&self, m: &Mat4) -> Self { Self::new(m * self.origin, m * self.direction) }
+
+    /// Draws the ray's path as seen by `camera` onto `canvas`, e.g. to
+    /// overlay it on a render of the same camera.
+    ///
+    /// The path runs from the origin to [`PROJECTION_DISTANCE`] along the
+    /// direction, trimmed to the part in front of the camera. A ray heading
+    /// straight into or away from the camera shows up as a single point.
+    ///
+    /// [`PROJECTION_DISTANCE`]: Self::PROJECTION_DISTANCE
+    pub fn draw_projection(&self, camera: &Camera, canvas: &mut Canvas, color: Color3) {
+        // Points closer to the camera plane than this are left out, since
+        // they project arbitrarily far off the canvas.
+        const NEAR: f64 = 1e-3;
+
+        // The camera looks down -z, so the path is visible where
+        // `origin.z + t * direction.z <= -NEAR`.
+        let view = self.transform(&camera.transform());
+        let (z, dz) = (view.origin.z(), view.direction.z());
+        let (mut start, mut end) = (0.0, Self::PROJECTION_DISTANCE);
+        if dz == 0.0 {
+            if z > -NEAR {
+                return;
+            }
+        } else if dz < 0.0 {
+            start = math::max(start, (-NEAR - z) / dz);
+        } else {
+            end = math::min(end, (-NEAR - z) / dz);
+        }
+        if start > end {
+            return;
+        }
+
+        let (Some((x0, y0)), Some((x1, y1))) = (
+            camera.project(self.position(start)),
+            camera.project(self.position(end)),
+        ) else {
+            return;
+        };
+        let pixel = |v: f64| math::floor(v) as isize;
+        canvas.draw_line(pixel(x0), pixel(y0), pixel(x1), pixel(y1), color);
+    }
+}
+
+impl Ray {
+    /// How far along the ray [`draw_projection`](Self::draw_projection)
+    /// follows it.
+    pub const PROJECTION_DISTANCE: f64 = 1e6;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{FRAC_PI_2, point, vector};
+
+    #[test]
+    fn test_creating_and_querying_a_ray() {
+        let origin = point(1, 2, 3);
+        let direction = vector(4, 5, 6);
+        let r = Ray::new(origin, direction);
+
+        assert_eq!(r.origin(), origin);
+        assert_eq!(r.direction(), direction);
+    }
+
+    #[test]
+    fn test_computing_a_point_from_a_distance() {
+        let r = Ray::new(point(2, 3, 4), vector(1, 0, 0));
+
+        assert_eq!(r.position(0.0), point(2, 3, 4));
+        assert_eq!(r.position(1.0), point(3, 3, 4));
+        assert_eq!(r.position(-1.0), point(1, 3, 4));
+        assert_eq!(r.position(2.5), point(4.5, 3, 4));
+    }
+
+    #[test]
+    fn test_translating_a_ray() {
+        let r = Ray::new(point(1, 2, 3), vector(0, 1, 0));
+        let m = Mat4::translation(3.0, 4.0, 5.0);
+
+        let r2 = r.transform(&m);
+        assert_eq!(r2.origin(), point(4, 6, 8));
+        assert_eq!(r2.direction(), vector(0, 1, 0));
+    }
+
+    fn projection_camera() -> Camera {
+        let transform = Mat4::view_transform(point(0, 0, -5), point(0, 0, 0), vector(0, 1, 0));
+        Camera::new(11, 11, FRAC_PI_2).with_transform(transform)
+    }
+
+    fn drawn_pixels(canvas: &Canvas) -> Vec<(usize, usize)> {
+        (0..canvas.height())
+            .flat_map(|y| (0..canvas.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.pixel_at(x, y) != Color3::BLACK)
+            .collect()
+    }
+
+    #[test]
+    fn test_projecting_a_ray_pointing_into_the_camera_draws_a_point() {
+        let camera = projection_camera();
+        let mut canvas = Canvas::new(11, 11);
+
+        Ray::new(point(0, 0, 0), vector(0, 0, -1)).draw_projection(&camera, &mut canvas, Color3::WHITE);
+
+        assert_eq!(drawn_pixels(&canvas), [(5, 5)]);
+    }
+
+    #[test]
+    fn test_projecting_an_oblique_ray_draws_a_segment() {
+        let camera = projection_camera();
+        let mut canvas = Canvas::new(11, 11);
+
+        let r = Ray::new(point(-2, -2, 0), vector(0.2, 0.1, 1));
+        r.draw_projection(&camera, &mut canvas, Color3::WHITE);
+
+        let pixels = drawn_pixels(&canvas);
+        assert!(pixels.len() > 1);
+        assert!(pixels.contains(&(3, 7)) && pixels.contains(&(6, 4)));
+        assert!(
+            pixels
+                .iter()
+                .all(|&(x, y)| (3..=6).contains(&x) && (4..=7).contains(&y))
+        );
+    }
+
+    #[test]
+    fn test_projecting_a_ray_behind_the_camera_draws_nothing() {
+        let camera = projection_camera();
+        let mut canvas = Canvas::new(11, 11);
+
+        Ray::new(point(0, 0, -6), vector(1, 0, -1)).draw_projection(&camera, &mut canvas, Color3::WHITE);
+
+        assert!(drawn_pixels(&canvas).is_empty());
+    }
+
+    #[test]
+    fn test_scaling_a_ray() {
+        let r = Ray::new(point(1, 2, 3), vector(0, 1, 0));
+        let m = Mat4::scaling(2.0, 3.0, 4.0);
+
+        let r2 = r.transform(&m);
+        assert_eq!(r2.origin(), point(2, 6, 12));
+        assert_eq!(r2.direction(), vector(0, 3, 0));
+    }
+}
@@ -0,0 +1,275 @@
+use core::fmt::Debug;
+
+use crate::geometry::{Bounds, Intersection, Ray};
+use crate::primitives::{Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// Geometry that can be placed in a scene and struck by rays.
+///
+/// Implementors only describe themselves in object space through
+/// [`local_intersect`](Shape::local_intersect) and
+/// [`local_normal_at`](Shape::local_normal_at); the provided
+/// [`intersect`](Shape::intersect) and [`normal_at`](Shape::normal_at) take
+/// care of moving between world and object space.
+pub trait Shape: AsShape + Debug + Send + Sync {
+    /// Returns the object-to-world transform.
+    fn transform(&self) -> Mat4;
+
+    /// Returns the world-to-object transform.
+    fn inverse_transform(&self) -> Mat4;
+
+    /// Sets the object-to-world transform.
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if `transform` is not invertible.
+    fn set_transform(&mut self, transform: Mat4);
+
+    /// Returns the surface material.
+    fn material(&self) -> &Material;
+
+    /// Sets the surface material.
+    fn set_material(&mut self, material: Material);
+
+    /// Returns the `t` of every point where the object-space `ray` meets the
+    /// shape.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64>;
+
+    /// Returns the object-space normal at the object-space `point`.
+    fn local_normal_at(&self, point: Point3) -> Vec3;
+
+    /// Returns the object-space normal at the object-space `point` where
+    /// `hit` struck the shape.
+    ///
+    /// Only shapes whose normal depends on more than the point, such as ones
+    /// that interpolate across their surface, need to override this.
+    fn local_normal_at_hit(&self, point: Point3, _hit: &Intersection) -> Vec3 {
+        self.local_normal_at(point)
+    }
+
+    /// Returns the box enclosing the shape in object space.
+    fn bounds(&self) -> Bounds;
+
+    /// Returns the box enclosing the shape in its parent's space, which is
+    /// world space for a shape that is not inside a group.
+    fn parent_space_bounds(&self) -> Bounds { self.bounds().transform(&self.transform()) }
+
+    /// Intersects a world-space `ray` with the shape.
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(&self.inverse_transform());
+        self.local_intersect(&ray)
+            .into_iter()
+            .map(|t| Intersection::new(t, self.as_shape()))
+            .collect()
+    }
+
+    /// Returns the unit world-space normal at `world_point`.
+    fn normal_at(&self, world_point: Point3) -> Vec3 {
+        let local_point = self.world_to_object(world_point);
+        self.normal_to_world(self.local_normal_at(local_point))
+    }
+
+    /// Returns the unit world-space normal at `world_point` where `hit`
+    /// struck the shape.
+    fn normal_at_hit(&self, world_point: Point3, hit: &Intersection) -> Vec3 {
+        let local_point = self.world_to_object(world_point);
+        self.normal_to_world(self.local_normal_at_hit(local_point, hit))
+    }
+
+    /// Converts `point` from world space to object space.
+    ///
+    /// A shape inside a [`Group`](crate::geometry::Group) has its ancestors'
+    /// transforms folded into its own, so this accounts for every parent.
+    fn world_to_object(&self, point: Point3) -> Point3 { self.inverse_transform() * point }
+
+    /// Converts an object-space `normal` to a unit world-space normal.
+    ///
+    /// The normal is transformed by the transpose of the inverse transform so
+    /// it stays perpendicular to the surface under non-uniform scaling.
+    fn normal_to_world(&self, normal: Vec3) -> Vec3 {
+        (self.inverse_transform().transpose() * normal).normalize()
+    }
+
+    /// Returns the shapes nested directly inside this one, if any.
+    fn children(&self) -> &[Box<dyn Shape>] { &[] }
+
+    /// Reorganises any nested shapes into a bounding volume hierarchy, so
+    /// that no group holds `threshold` or more children that could be split
+    /// further. Shapes without children are left as they are.
+    fn divide(&mut self, _threshold: usize) {}
+}
+
+/// Upcasts a concrete shape to a `dyn Shape` so the provided methods on
+/// [`Shape`] can hand out intersections that refer back to it.
+pub trait AsShape {
+    /// Returns `self` as a trait object.
+    fn as_shape(&self) -> &dyn Shape;
+}
+
+impl<T: Shape> AsShape for T {
+    #[inline(always)]
+    fn as_shape(&self) -> &dyn Shape { self }
+}
+
+impl<T: Shape + 'static> From<T> for Box<dyn Shape> {
+    #[inline(always)]
+    fn from(shape: T) -> Self { Box::new(shape) }
+}
+
+/// Returns whether `object` is `shape` itself or nested anywhere inside it.
+pub(crate) fn includes(shape: &dyn Shape, object: &dyn Shape) -> bool {
+    core::ptr::addr_eq(shape, object)
+        || shape
+            .children()
+            .iter()
+            .any(|child| includes(child.as_ref(), object))
+}
+
+#[cfg(test)]
+pub(crate) mod test_shape {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::primitives::Inverse;
+
+    /// A shape that records the last object-space ray it was asked to
+    /// intersect, so the world-to-object conversion can be inspected.
+    #[derive(Debug)]
+    pub(crate) struct TestShape {
+        transform: Mat4,
+        inverse:   Mat4,
+        material:  Material,
+        saved_ray: Arc<Mutex<Option<Ray>>>,
+    }
+
+    impl TestShape {
+        pub(crate) fn new() -> Self {
+            Self {
+                transform: Mat4::IDENTITY,
+                inverse:   Mat4::IDENTITY,
+                material:  Material::new(),
+                saved_ray: Arc::default(),
+            }
+        }
+
+        pub(crate) fn saved_ray(&self) -> Option<Ray> { *self.saved_ray.lock().unwrap() }
+
+        /// Returns a handle to the recorded ray that outlives moving the
+        /// shape into a container.
+        pub(crate) fn recorder(&self) -> Arc<Mutex<Option<Ray>>> { Arc::clone(&self.saved_ray) }
+    }
+
+    impl Shape for TestShape {
+        fn transform(&self) -> Mat4 { self.transform }
+
+        fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+        fn set_transform(&mut self, transform: Mat4) {
+            self.inverse = transform.inverse().expect("shape transform must be invertible");
+            self.transform = transform;
+        }
+
+        fn material(&self) -> &Material { &self.material }
+
+        fn set_material(&mut self, material: Material) { self.material = material; }
+
+        fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+            *self.saved_ray.lock().unwrap() = Some(*ray);
+            Vec::new()
+        }
+
+        fn local_normal_at(&self, point: Point3) -> Vec3 { Vec3::new(point.x(), point.y(), point.z()) }
+
+        fn bounds(&self) -> Bounds { Bounds::new(Point3::NEG_ONE, Point3::ONE) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::test_shape::TestShape;
+    use super::*;
+    use crate::prelude::{PI, point, vector};
+
+    #[test]
+    fn test_default_transformation() {
+        let s = TestShape::new();
+        assert_eq!(s.transform(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_assigning_a_transformation() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::translation(2.0, 3.0, 4.0));
+        assert_eq!(s.transform(), Mat4::translation(2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_default_material() {
+        let s = TestShape::new();
+        assert_eq!(*s.material(), Material::default());
+    }
+
+    #[test]
+    fn test_assigning_a_material() {
+        let mut s = TestShape::new();
+        let m = Material::new().with_ambient(1.0).unwrap();
+        s.set_material(m);
+        assert_eq!(*s.material(), m);
+    }
+
+    #[test]
+    fn test_intersecting_a_scaled_shape_with_a_ray() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        s.intersect(&r);
+        assert_eq!(
+            s.saved_ray(),
+            Some(Ray::new(point(0.0, 0.0, -2.5), vector(0.0, 0.0, 0.5)))
+        );
+    }
+
+    #[test]
+    fn test_intersecting_a_translated_shape_with_a_ray() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::translation(5.0, 0.0, 0.0));
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        s.intersect(&r);
+        assert_eq!(s.saved_ray(), Some(Ray::new(point(-5, 0, -5), vector(0, 0, 1))));
+    }
+
+    #[test]
+    fn test_computing_the_normal_on_a_translated_shape() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::translation(0.0, 1.0, 0.0));
+        let n = s.normal_at(point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_eq!(n, vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn test_computing_the_normal_on_a_transformed_shape() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::scaling(1.0, 0.5, 1.0) * Mat4::rotation_z(PI / 5.0));
+        let n = s.normal_at(point(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_eq!(n, vector(0.0, 0.97014, -0.24254));
+    }
+
+    #[test]
+    fn test_querying_a_shapes_bounding_box_in_its_parents_space() {
+        let mut s = TestShape::new();
+        s.set_transform(Mat4::translation(1.0, -3.0, 5.0) * Mat4::scaling(0.5, 2.0, 4.0));
+        assert_eq!(
+            s.parent_space_bounds(),
+            Bounds::new(point(0.5, -5.0, 1.0), point(1.5, -1.0, 9.0))
+        );
+    }
+
+    #[test]
+    fn test_shapes_are_usable_as_trait_objects() {
+        let s: Box<dyn Shape> = Box::new(TestShape::new());
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert!(s.intersect(&r).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::geometry::triangle::moller_trumbore;
+use crate::geometry::{Bounds, Intersection, Ray, Shape};
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// A triangle whose normal is interpolated from a normal at each vertex,
+/// giving curved-looking shading across flat geometry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmoothTriangle {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+    p1:        Point3,
+    p2:        Point3,
+    p3:        Point3,
+    n1:        Vec3,
+    n2:        Vec3,
+    n3:        Vec3,
+    e1:        Vec3,
+    e2:        Vec3,
+}
+
+impl SmoothTriangle {
+    /// Creates a new smooth triangle with vertices `p1`, `p2`, `p3` and
+    /// matching vertex normals `n1`, `n2`, `n3`.
+    #[must_use]
+    pub fn new(p1: Point3, p2: Point3, p3: Point3, n1: Vec3, n2: Vec3, n3: Vec3) -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+            material: Material::new(),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+        }
+    }
+
+    /// Returns the smooth triangle with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the smooth triangle with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Returns the vertices.
+    #[inline]
+    pub const fn vertices(&self) -> [Point3; 3] { [self.p1, self.p2, self.p3] }
+
+    /// Returns the vertex normals.
+    #[inline]
+    pub const fn normals(&self) -> [Vec3; 3] { [self.n1, self.n2, self.n3] }
+}
+
+impl Shape for SmoothTriangle {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        moller_trumbore(ray, self.p1, self.e1, self.e2).map_or_else(Vec::new, |(t, ..)| vec![t])
+    }
+
+    /// Returns the normal of the flat face, since no hit is known to
+    /// interpolate from.
+    fn local_normal_at(&self, _point: Point3) -> Vec3 { self.e2.cross(self.e1).normalize() }
+
+    /// Interpolates the vertex normals using the hit's `u` and `v`.
+    fn local_normal_at_hit(&self, point: Point3, hit: &Intersection) -> Vec3 {
+        match hit.uv() {
+            Some((u, v)) => self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v),
+            None => self.local_normal_at(point),
+        }
+    }
+
+    #[inline]
+    fn bounds(&self) -> Bounds {
+        Bounds::EMPTY
+            .merge_point(self.p1)
+            .merge_point(self.p2)
+            .merge_point(self.p3)
+    }
+
+    /// Intersects a world-space `ray`, recording the `u` and `v` of the hit
+    /// so the normal can be interpolated later.
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(&self.inverse);
+        moller_trumbore(&ray, self.p1, self.e1, self.e2)
+            .map(|(t, u, v)| Intersection::with_uv(t, self, u, v))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmp::float::is_equal;
+    use crate::prelude::{point, vector};
+    use crate::world::prepare_computations;
+
+    fn triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            point(0, 1, 0),
+            point(-1, 0, 0),
+            point(1, 0, 0),
+            vector(0, 1, 0),
+            vector(-1, 0, 0),
+            vector(1, 0, 0),
+        )
+    }
+
+    #[test]
+    fn test_constructing_a_smooth_triangle() {
+        let tri = triangle();
+        assert_eq!(tri.vertices(), [point(0, 1, 0), point(-1, 0, 0), point(1, 0, 0)]);
+        assert_eq!(tri.normals(), [
+            vector(0, 1, 0),
+            vector(-1, 0, 0),
+            vector(1, 0, 0)
+        ]);
+    }
+
+    #[test]
+    fn test_intersection_with_a_smooth_triangle_stores_u_and_v() {
+        let tri = triangle();
+        let r = Ray::new(point(-0.2, 0.3, -2.0), vector(0, 0, 1));
+        let xs = tri.intersect(&r);
+        let (u, v) = xs[0].uv().unwrap();
+        assert!(is_equal(u, 0.45));
+        assert!(is_equal(v, 0.25));
+    }
+
+    #[test]
+    fn test_smooth_triangle_uses_u_and_v_to_interpolate_the_normal() {
+        let tri = triangle();
+        let i = Intersection::with_uv(1.0, &tri, 0.45, 0.25);
+        let n = tri.normal_at_hit(point(0, 0, 0), &i);
+        assert_eq!(n, vector(-0.5547, 0.83205, 0.0));
+    }
+
+    #[test]
+    fn test_preparing_the_normal_on_a_smooth_triangle() {
+        let tri = triangle();
+        let i = Intersection::with_uv(1.0, &tri, 0.45, 0.25);
+        let r = Ray::new(point(-0.2, 0.3, -2.0), vector(0, 0, 1));
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert_eq!(comps.normalv, vector(-0.5547, 0.83205, 0.0));
+    }
+}
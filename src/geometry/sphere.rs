@@ -0,0 +1,268 @@
+use crate::geometry::{Bounds, Ray, Shape};
+use crate::math;
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// A unit sphere centered at the origin in object space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+}
+
+impl Sphere {
+    /// Creates a new sphere with an identity transform.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+            material:  Material::new(),
+        }
+    }
+
+    /// Creates a solid glass sphere: fully transparent with a refractive
+    /// index of `1.5`.
+    #[inline]
+    #[must_use]
+    pub const fn glass() -> Self {
+        let mut sphere = Self::new();
+        sphere.material.transparency = 1.0;
+        sphere.material.refractive_index = 1.5;
+        sphere
+    }
+
+    /// Returns the sphere with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the sphere with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Shape for Sphere {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    /// Returns zero or two `t` values in increasing order. A ray tangent to
+    /// the sphere yields the same `t` twice.
+    ///
+    /// The quadratic is solved around the point of closest approach, so the
+    /// half-chord stays accurate for grazing rays from far away.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        let sphere_to_ray = ray.origin() - Point3::ZERO;
+
+        let a = ray.direction().dot(ray.direction());
+        let t_mid = -ray.direction().dot(sphere_to_ray) / a;
+        let closest = sphere_to_ray + ray.direction() * t_mid;
+        let c = closest.dot(closest) - 1.0;
+
+        math::solve_quadratic(a, 0.0, c).map_or_else(Vec::new, |(s0, s1)| vec![t_mid + s0, t_mid + s1])
+    }
+
+    #[inline]
+    fn local_normal_at(&self, point: Point3) -> Vec3 { point - Point3::ZERO }
+
+    #[inline]
+    fn bounds(&self) -> Bounds { Bounds::new(Point3::NEG_ONE, Point3::ONE) }
+}
+
+impl Default for Sphere {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+    use crate::prelude::{FRAC_PI_4, Intersection, PI, point, vector};
+
+    fn ts(xs: &[Intersection]) -> Vec<f64> { xs.iter().map(Intersection::t).collect() }
+
+    #[test]
+    fn test_ray_intersects_sphere_at_two_points() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), [4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_ray_intersects_sphere_at_a_tangent() {
+        let r = Ray::new(point(0, 1, -5), vector(0, 0, 1));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), [5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_grazing_ray_hits_far_away_sphere_at_two_distinct_points() {
+        let s = Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 1e6));
+        let r = Ray::new(point(0, 0.9999, 0), vector(0, 0, 1));
+        let half_chord = (1.0_f64 - 0.9999 * 0.9999).sqrt();
+
+        let xs = ts(&s.intersect(&r));
+        assert_eq!(xs.len(), 2);
+        assert!(xs[0] < xs[1]);
+        assert!((xs[0] - (1e6 - half_chord)).abs() < 1e-6);
+        assert!((xs[1] - (1e6 + half_chord)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ray_misses_sphere() {
+        let r = Ray::new(point(0, 2, -5), vector(0, 0, 1));
+        let s = Sphere::new();
+        assert!(s.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_originates_inside_sphere() {
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), [-1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_sphere_is_behind_ray() {
+        let r = Ray::new(point(0, 0, 5), vector(0, 0, 1));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), [-6.0, -4.0]);
+    }
+
+    #[test]
+    fn test_intersect_sets_the_object() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let s = Sphere::new();
+        let xs = s.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert!(core::ptr::addr_eq(xs[0].object(), &s));
+        assert!(core::ptr::addr_eq(xs[1].object(), &s));
+    }
+
+    #[test]
+    fn test_sphere_default_transform() {
+        assert_eq!(Sphere::new().transform(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_changing_a_sphere_transformation() {
+        let t = Mat4::translation(2.0, 3.0, 4.0);
+        let mut s = Sphere::new();
+        s.set_transform(t);
+        assert_eq!(s.transform(), t);
+    }
+
+    #[test]
+    fn test_intersecting_a_scaled_sphere_with_a_ray() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let s = Sphere::new().with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        assert_eq!(ts(&s.intersect(&r)), [3.0, 7.0]);
+    }
+
+    #[test]
+    fn test_intersecting_a_translated_sphere_with_a_ray() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let s = Sphere::new().with_transform(Mat4::translation(5.0, 0.0, 0.0));
+        assert!(s.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_sphere_has_a_default_material() {
+        let s = Sphere::new();
+        assert_eq!(*s.material(), Material::default());
+    }
+
+    #[test]
+    fn test_sphere_may_be_assigned_a_material() {
+        let m = Material::new().with_ambient(1.0).unwrap();
+        let s = Sphere::new().with_material(m);
+        assert_eq!(*s.material(), m);
+    }
+
+    #[test]
+    fn test_normal_on_a_sphere_at_points_on_the_axes() {
+        let s = Sphere::new();
+        assert_eq!(s.normal_at(point(1, 0, 0)), vector(1, 0, 0));
+        assert_eq!(s.normal_at(point(0, 1, 0)), vector(0, 1, 0));
+        assert_eq!(s.normal_at(point(0, 0, 1)), vector(0, 0, 1));
+    }
+
+    #[test]
+    fn test_normal_on_a_sphere_at_a_nonaxial_point() {
+        let s = Sphere::new();
+        let k = 3_f64.sqrt() / 3.0;
+        let n = s.normal_at(point(k, k, k));
+        assert_eq!(n, vector(k, k, k));
+        assert_eq!(n, n.normalize());
+    }
+
+    #[test]
+    fn test_normal_on_a_translated_sphere() {
+        let s = Sphere::new().with_transform(Mat4::translation(0.0, 1.0, 0.0));
+        let n = s.normal_at(point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_eq!(n, vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn test_normal_on_a_transformed_sphere() {
+        let m = Mat4::scaling(1.0, 0.5, 1.0) * Mat4::rotation_z(PI / 5.0);
+        let s = Sphere::new().with_transform(m);
+        let n = s.normal_at(point(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_eq!(n, vector(0.0, 0.97014, -0.24254));
+        assert!(n.is_normalized());
+    }
+
+    #[test]
+    fn test_normal_is_unit_length_under_nonuniform_scaling() {
+        let m = Mat4::scaling(3.0, 0.25, 2.0) * Mat4::rotation_x(FRAC_PI_4);
+        let s = Sphere::new().with_transform(m);
+        let r = Ray::new(point(0, 0, -10), vector(0, 0, 1));
+        let p = r.position(s.intersect(&r)[0].t());
+        assert!(s.normal_at(p).is_normalized());
+    }
+
+    #[test]
+    fn test_sphere_has_a_bounding_box() {
+        assert_eq!(
+            Sphere::new().bounds(),
+            Bounds::new(point(-1, -1, -1), point(1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_glass_sphere_material() {
+        let s = Sphere::glass();
+        assert_eq!(s.transform(), Mat4::IDENTITY);
+        assert_eq!(s.material().transparency, 1.0);
+        assert_eq!(s.material().refractive_index, 1.5);
+    }
+}
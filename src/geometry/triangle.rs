@@ -0,0 +1,266 @@
+use crate::cmp::epsilon::EPSILON;
+use crate::geometry::{Bounds, Ray, Shape, SmoothTriangle};
+use crate::math;
+use crate::primitives::{Inverse, Mat4, Point3, Vec3};
+use crate::shading::Material;
+
+/// A flat triangle with vertices `p1`, `p2` and `p3` in object space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle {
+    transform: Mat4,
+    inverse:   Mat4,
+    material:  Material,
+    p1:        Point3,
+    p2:        Point3,
+    p3:        Point3,
+    e1:        Vec3,
+    e2:        Vec3,
+    normal:    Vec3,
+}
+
+impl Triangle {
+    /// Creates a new triangle with an identity transform, precomputing its
+    /// edge vectors and face normal.
+    #[must_use]
+    pub fn new(p1: Point3, p2: Point3, p3: Point3) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        Self {
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+            material: Material::new(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(e1).normalize(),
+        }
+    }
+
+    /// Creates a smooth triangle with vertices `p1`, `p2`, `p3` and matching
+    /// vertex normals `n1`, `n2`, `n3`; see [`SmoothTriangle::new`].
+    #[inline]
+    #[must_use]
+    pub fn smooth(p1: Point3, p2: Point3, p3: Point3, n1: Vec3, n2: Vec3, n3: Vec3) -> SmoothTriangle {
+        SmoothTriangle::new(p1, p2, p3, n1, n2, n3)
+    }
+
+    /// Creates the triangle for a mesh face with the given `vertices`,
+    /// smooth if the face has vertex `normals` and flat otherwise.
+    #[must_use]
+    pub fn from_face([p1, p2, p3]: [Point3; 3], normals: Option<[Vec3; 3]>) -> Box<dyn Shape> {
+        match normals {
+            Some([n1, n2, n3]) => Self::smooth(p1, p2, p3, n1, n2, n3).into(),
+            None => Self::new(p1, p2, p3).into(),
+        }
+    }
+
+    /// Returns the triangle with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the triangle with its material set to `material`.
+    #[inline]
+    #[must_use]
+    pub const fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Returns the first vertex.
+    #[inline]
+    pub const fn p1(&self) -> Point3 { self.p1 }
+
+    /// Returns the second vertex.
+    #[inline]
+    pub const fn p2(&self) -> Point3 { self.p2 }
+
+    /// Returns the third vertex.
+    #[inline]
+    pub const fn p3(&self) -> Point3 { self.p3 }
+
+    /// Returns the edge from `p1` to `p2`.
+    #[inline]
+    pub const fn e1(&self) -> Vec3 { self.e1 }
+
+    /// Returns the edge from `p1` to `p3`.
+    #[inline]
+    pub const fn e2(&self) -> Vec3 { self.e2 }
+
+    /// Returns the face normal.
+    #[inline]
+    pub const fn normal(&self) -> Vec3 { self.normal }
+}
+
+impl Shape for Triangle {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("shape transform must be invertible");
+        self.transform = transform;
+    }
+
+    #[inline]
+    fn material(&self) -> &Material { &self.material }
+
+    #[inline]
+    fn set_material(&mut self, material: Material) { self.material = material; }
+
+    /// Returns the `t` of the hit, if any, using the Möller–Trumbore
+    /// algorithm.
+    fn local_intersect(&self, ray: &Ray) -> Vec<f64> {
+        moller_trumbore(ray, self.p1, self.e1, self.e2).map_or_else(Vec::new, |(t, ..)| vec![t])
+    }
+
+    /// Returns the face normal, which is the same everywhere on the triangle.
+    #[inline]
+    fn local_normal_at(&self, _point: Point3) -> Vec3 { self.normal }
+
+    #[inline]
+    fn bounds(&self) -> Bounds {
+        Bounds::EMPTY
+            .merge_point(self.p1)
+            .merge_point(self.p2)
+            .merge_point(self.p3)
+    }
+}
+
+/// Intersects `ray` with the triangle at `p1` spanned by edges `e1` and `e2`,
+/// returning `t` along with the barycentric `u` and `v` of the hit.
+///
+/// Misses rays parallel to the triangle and rays passing outside any edge.
+pub(crate) fn moller_trumbore(ray: &Ray, p1: Point3, e1: Vec3, e2: Vec3) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.direction().cross(e2);
+    let det = e1.dot(dir_cross_e2);
+    if math::abs(det) < EPSILON {
+        return None;
+    }
+
+    let f = 1.0 / det;
+    let p1_to_origin = ray.origin() - p1;
+    let u = f * p1_to_origin.dot(dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin.cross(e1);
+    let v = f * ray.direction().dot(origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some((f * e2.dot(origin_cross_e1), u, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{point, vector};
+    use crate::world::prepare_computations;
+
+    fn triangle() -> Triangle { Triangle::new(point(0, 1, 0), point(-1, 0, 0), point(1, 0, 0)) }
+
+    #[test]
+    fn test_constructing_a_triangle() {
+        let t = triangle();
+        assert_eq!(t.p1(), point(0, 1, 0));
+        assert_eq!(t.p2(), point(-1, 0, 0));
+        assert_eq!(t.p3(), point(1, 0, 0));
+        assert_eq!(t.e1(), vector(-1, -1, 0));
+        assert_eq!(t.e2(), vector(1, -1, 0));
+        assert_eq!(t.normal(), vector(0, 0, -1));
+    }
+
+    #[test]
+    fn test_finding_the_normal_on_a_triangle() {
+        let t = triangle();
+        assert_eq!(t.local_normal_at(point(0.0, 0.5, 0.0)), t.normal());
+        assert_eq!(t.local_normal_at(point(-0.5, 0.75, 0.0)), t.normal());
+        assert_eq!(t.local_normal_at(point(0.5, 0.25, 0.0)), t.normal());
+    }
+
+    #[test]
+    fn test_intersecting_a_ray_parallel_to_the_triangle() {
+        let r = Ray::new(point(0, -1, -2), vector(0, 1, 0));
+        assert!(triangle().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_misses_the_p1_p3_edge() {
+        let r = Ray::new(point(1, 1, -2), vector(0, 0, 1));
+        assert!(triangle().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_misses_the_p1_p2_edge() {
+        let r = Ray::new(point(-1, 1, -2), vector(0, 0, 1));
+        assert!(triangle().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_misses_the_p2_p3_edge() {
+        let r = Ray::new(point(0, -1, -2), vector(0, 0, 1));
+        assert!(triangle().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_strikes_a_triangle() {
+        let r = Ray::new(point(0.0, 0.5, -2.0), vector(0, 0, 1));
+        assert_eq!(triangle().local_intersect(&r), [2.0]);
+    }
+
+    #[test]
+    fn test_triangle_has_a_bounding_box() {
+        let t = Triangle::new(point(-3, 7, 2), point(6, 2, -4), point(2, -1, -1));
+        assert_eq!(t.bounds(), Bounds::new(point(-3, -1, -4), point(6, 7, 2)));
+    }
+
+    #[test]
+    fn test_face_without_normals_is_flat() {
+        let [p1, p2, p3] = [point(0, 1, 0), point(-1, 0, 0), point(1, 0, 0)];
+        let face = Triangle::from_face([p1, p2, p3], None);
+        let expected = (p3 - p1).cross(p2 - p1).normalize();
+
+        let r = Ray::new(point(-0.2, 0.3, -2.0), vector(0, 0, 1));
+        let xs = face.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].uv(), None);
+        assert_eq!(prepare_computations(&xs[0], &r, &xs).normalv, expected);
+        assert_eq!(face.normal_at(point(0.5, 0.25, 0.0)), expected);
+    }
+
+    #[test]
+    fn test_face_with_normals_interpolates_them() {
+        let points = [point(0, 1, 0), point(-1, 0, 0), point(1, 0, 0)];
+        let normals = [vector(0, 1, 0), vector(-1, 0, 0), vector(1, 0, 0)];
+        let face = Triangle::from_face(points, Some(normals));
+
+        let r = Ray::new(point(-0.2, 0.3, -2.0), vector(0, 0, 1));
+        let xs = face.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(
+            prepare_computations(&xs[0], &r, &xs).normalv,
+            vector(-0.5547, 0.83205, 0.0)
+        );
+
+        let [p1, p2, p3] = points;
+        let [n1, n2, n3] = normals;
+        assert_eq!(
+            Triangle::smooth(p1, p2, p3, n1, n2, n3),
+            SmoothTriangle::new(p1, p2, p3, n1, n2, n3)
+        );
+    }
+}
@@ -0,0 +1,491 @@
+use core::f64::consts::TAU;
+
+use crate::geometry::{Ray, hit};
+use crate::graphics::canvas::Canvas;
+use crate::math;
+use crate::primitives::{Color3, Inverse, Mat4, Point3, Vec3};
+use crate::world::{ObjectId, World};
+
+/// A camera that maps the pixels of a canvas onto rays in the world.
+///
+/// The canvas sits one unit in front of the camera, which looks toward `-z`
+/// until its transform says otherwise. With the default zero aperture it is a
+/// pinhole camera and everything is in focus; a wider aperture blurs whatever
+/// lies off the plane `focus_distance` units away.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Camera {
+    hsize:          usize,
+    vsize:          usize,
+    field_of_view:  f64,
+    transform:      Mat4,
+    inverse:        Mat4,
+    half_width:     f64,
+    half_height:    f64,
+    pixel_size:     f64,
+    aperture:       f64,
+    focus_distance: f64,
+}
+
+impl Camera {
+    /// Creates a camera rendering `hsize`×`vsize` pixels with the given
+    /// horizontal or vertical `field_of_view`, whichever is larger.
+    #[must_use]
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Self {
+        let half_view = math::tan(field_of_view / 2.0);
+        let aspect = hsize as f64 / vsize as f64;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+
+        Self {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+            half_width,
+            half_height,
+            pixel_size: half_width * 2.0 / hsize as f64,
+            aperture: 0.0,
+            focus_distance: 1.0,
+        }
+    }
+
+    /// Sets the transform that orients the world relative to the camera.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `transform` is not invertible.
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Replaces the camera transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `transform` is not invertible.
+    pub fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("camera transform must be invertible");
+        self.transform = transform;
+    }
+
+    /// Returns the camera with its lens `aperture` set, in world units.
+    ///
+    /// Ray origins are spread across a disk of radius `aperture / 2`, so zero
+    /// gives a pinhole camera.
+    #[inline]
+    #[must_use]
+    pub const fn with_aperture(mut self, aperture: f64) -> Self {
+        self.aperture = aperture;
+        self
+    }
+
+    /// Returns the camera focused on the plane `focus_distance` units in
+    /// front of it.
+    #[inline]
+    #[must_use]
+    pub const fn with_focus_distance(mut self, focus_distance: f64) -> Self {
+        self.focus_distance = focus_distance;
+        self
+    }
+
+    /// Returns the horizontal size of the canvas in pixels.
+    #[inline]
+    pub const fn hsize(&self) -> usize { self.hsize }
+
+    /// Returns the vertical size of the canvas in pixels.
+    #[inline]
+    pub const fn vsize(&self) -> usize { self.vsize }
+
+    /// Returns the camera's field of view in radians.
+    #[inline]
+    pub const fn field_of_view(&self) -> f64 { self.field_of_view }
+
+    /// Returns the camera transform.
+    #[inline]
+    pub const fn transform(&self) -> Mat4 { self.transform }
+
+    /// Returns the size of one pixel on the canvas, in world units.
+    #[inline]
+    pub const fn pixel_size(&self) -> f64 { self.pixel_size }
+
+    /// Returns the diameter of the lens, in world units.
+    #[inline]
+    pub const fn aperture(&self) -> f64 { self.aperture }
+
+    /// Returns the distance to the plane that is in perfect focus.
+    #[inline]
+    pub const fn focus_distance(&self) -> f64 { self.focus_distance }
+}
+
+impl Camera {
+    /// Returns the ray from the camera through the center of pixel
+    /// (`px`, `py`).
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray { self.ray_for_subpixel(px, py, 0.5, 0.5) }
+
+    /// Returns the ray from the camera through pixel (`px`, `py`), offset by
+    /// (`dx`, `dy`) from its top-left corner.
+    ///
+    /// Offsets are fractions of a pixel, so `(0.5, 0.5)` is the pixel center.
+    /// With a nonzero aperture the origin is picked on the lens from a
+    /// deterministic hash of the pixel and offsets.
+    pub fn ray_for_subpixel(&self, px: usize, py: usize, dx: f64, dy: f64) -> Ray {
+        let seed = (py * self.hsize + px) as u64 ^ dx.to_bits() ^ dy.to_bits().rotate_left(32);
+        self.ray_through_lens(px, py, dx, dy, (jitter(seed, 2), jitter(seed, 3)))
+    }
+
+    /// Returns the ray from the point `lens` on the camera lens through pixel
+    /// (`px`, `py`), offset by (`dx`, `dy`) from its top-left corner.
+    ///
+    /// Both `lens` coordinates lie in `[0, 1]` and are mapped uniformly onto
+    /// the lens disk. Every lens point aims at the same spot on the focal
+    /// plane; with zero aperture the lens is ignored.
+    pub fn ray_through_lens(&self, px: usize, py: usize, dx: f64, dy: f64, lens: (f64, f64)) -> Ray {
+        let world_x = self.half_width - (px as f64 + dx) * self.pixel_size;
+        let world_y = self.half_height - (py as f64 + dy) * self.pixel_size;
+
+        let (pixel, origin) = if self.aperture == 0.0 {
+            (Point3::new(world_x, world_y, -1.0), Point3::new(0.0, 0.0, 0.0))
+        } else {
+            let radius = self.aperture / 2.0 * math::sqrt(lens.0);
+            let (sin, cos) = math::sin_cos(TAU * lens.1);
+            let focus = self.focus_distance;
+            (
+                Point3::new(world_x * focus, world_y * focus, -focus),
+                Point3::new(radius * cos, radius * sin, 0.0),
+            )
+        };
+
+        let pixel = self.inverse * pixel;
+        let origin = self.inverse * origin;
+        let direction: Vec3 = (pixel - origin).normalize();
+
+        Ray::new(origin, direction)
+    }
+
+    /// Returns where `point` lands on the canvas, in fractional pixels, or
+    /// `None` if it is not in front of the camera.
+    ///
+    /// This inverts [`ray_for_subpixel`](Self::ray_for_subpixel) for a
+    /// pinhole camera, so pixel (`px`, `py`) covers `[px, px + 1)` by
+    /// `[py, py + 1)`.
+    pub fn project(&self, point: Point3) -> Option<(f64, f64)> {
+        let view = self.transform * point;
+        if view.z() >= 0.0 {
+            return None;
+        }
+
+        let x = (self.half_width + view.x() / view.z()) / self.pixel_size;
+        let y = (self.half_height + view.y() / view.z()) / self.pixel_size;
+        Some((x, y))
+    }
+
+    /// Returns the id of the object seen at pixel (`px`, `py`), or `None` if
+    /// the pixel's ray hits nothing, e.g. to select objects by clicking.
+    pub fn pick(&self, world: &World, px: usize, py: usize) -> Option<ObjectId> {
+        let xs = world.intersect(&self.ray_for_pixel(px, py));
+        hit(&xs).and_then(|hit| world.object_id_of(hit.object()))
+    }
+
+    /// Renders `world` by casting one ray through the center of every pixel.
+    pub fn render(&self, world: &World) -> Canvas {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+                image.write_pixel(x, y, world.color_at_default(&ray));
+            }
+        }
+        image
+    }
+
+    /// Renders `world` like [`Camera::render`], after checking that it has
+    /// objects and lights to render.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`World::validate`] instead of an all-black
+    /// image.
+    pub fn try_render(&self, world: &World) -> crate::Result<Canvas> {
+        world.validate()?;
+        Ok(self.render(world))
+    }
+
+    /// Renders `world` with supersampling anti-aliasing.
+    ///
+    /// Each pixel is split into a `samples`×`samples` grid, one ray is cast
+    /// through a jittered point inside every cell, and the resulting colors
+    /// are averaged. The jitter is deterministic, so repeated renders produce
+    /// identical images. A single sample (or zero) casts one ray through the
+    /// pixel center, matching [`Camera::render`].
+    pub fn render_aa(&self, world: &World, samples: u32) -> Canvas {
+        let grid = samples.max(1) as usize;
+        let count = (grid * grid) as f64;
+
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let mut sum = Color3::BLACK;
+                for j in 0..grid {
+                    for i in 0..grid {
+                        let (jx, jy) = if grid == 1 {
+                            (0.5, 0.5)
+                        } else {
+                            let seed = ((y * self.hsize + x) * grid + j) * grid + i;
+                            (jitter(seed as u64, 0), jitter(seed as u64, 1))
+                        };
+                        let dx = (i as f64 + jx) / grid as f64;
+                        let dy = (j as f64 + jy) / grid as f64;
+
+                        let ray = self.ray_for_subpixel(x, y, dx, dy);
+                        sum += world.color_at_default(&ray);
+                    }
+                }
+                image.write_pixel(x, y, sum / count);
+            }
+        }
+        image
+    }
+}
+
+/// Returns a pseudo-random value in `[0, 1)` derived from `seed` and `stream`.
+///
+/// This is the SplitMix64 finalizer, which is plenty for spreading samples and
+/// keeps renders reproducible without pulling in an RNG.
+pub(crate) fn jitter(seed: u64, stream: u64) -> f64 {
+    let mut z = seed
+        .wrapping_mul(2)
+        .wrapping_add(stream)
+        .wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4};
+
+    use super::*;
+    use crate::cmp::float::is_equal;
+    use crate::error::{TracerError, WorldError};
+    use crate::prelude::{color, point, vector};
+
+    #[test]
+    fn test_constructing_a_camera() {
+        let c = Camera::new(160, 120, FRAC_PI_2);
+
+        assert_eq!(c.hsize(), 160);
+        assert_eq!(c.vsize(), 120);
+        assert_eq!(c.field_of_view(), FRAC_PI_2);
+        assert_eq!(c.transform(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_pixel_size_for_a_horizontal_canvas() {
+        let c = Camera::new(200, 125, FRAC_PI_2);
+        assert!((c.pixel_size() - 0.01).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_pixel_size_for_a_vertical_canvas() {
+        let c = Camera::new(125, 200, FRAC_PI_2);
+        assert!((c.pixel_size() - 0.01).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_constructing_a_ray_through_the_center_of_the_canvas() {
+        let c = Camera::new(201, 101, FRAC_PI_2);
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(r.origin(), point(0, 0, 0));
+        assert_eq!(r.direction(), vector(0, 0, -1));
+    }
+
+    #[test]
+    fn test_constructing_a_ray_through_a_corner_of_the_canvas() {
+        let c = Camera::new(201, 101, FRAC_PI_2);
+        let r = c.ray_for_pixel(0, 0);
+
+        assert_eq!(r.origin(), point(0, 0, 0));
+        assert_eq!(r.direction(), vector(0.66519, 0.33259, -0.66851));
+    }
+
+    #[test]
+    fn test_constructing_a_ray_when_the_camera_is_transformed() {
+        let c = Camera::new(201, 101, FRAC_PI_2)
+            .with_transform(Mat4::rotation_y(FRAC_PI_4) * Mat4::translation(0.0, -2.0, 5.0));
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(r.origin(), point(0, 2, -5));
+        assert_eq!(r.direction(), vector(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn test_rendering_a_world_with_a_camera() {
+        let w = World::default_world();
+        let from = point(0, 0, -5);
+        let to = point(0, 0, 0);
+        let up = vector(0, 1, 0);
+        let c = Camera::new(11, 11, FRAC_PI_2).with_transform(Mat4::view_transform(from, to, up));
+
+        let image = c.render(&w);
+
+        assert_eq!(image.pixel_at(5, 5), color(0.38066, 0.47583, 0.2855));
+    }
+
+    #[test]
+    fn test_projecting_a_point_onto_the_canvas() {
+        let from = point(1, 2, -5);
+        let c = Camera::new(201, 101, FRAC_PI_4).with_transform(Mat4::view_transform(
+            from,
+            point(0, 1, 0),
+            vector(0, 1, 0),
+        ));
+
+        let r = c.ray_for_subpixel(100, 50, 0.25, 0.75);
+        let (x, y) = c.project(r.position(7.0)).unwrap();
+        assert!(is_equal(x, 100.25) && is_equal(y, 50.75), "({x}, {y})");
+        assert_eq!(c.project(from), None);
+        assert_eq!(c.project(point(2, 3, -10)), None);
+    }
+
+    #[test]
+    fn test_picking_the_object_at_a_pixel() {
+        let w = World::default_world();
+        let from = point(0, 0, -5);
+        let to = point(0, 0, 0);
+        let up = vector(0, 1, 0);
+        let c = Camera::new(11, 11, FRAC_PI_2).with_transform(Mat4::view_transform(from, to, up));
+
+        let id = c.pick(&w, 5, 5).unwrap();
+        let outer = w.objects()[0].as_ref();
+        assert!(core::ptr::addr_eq(w.get_object(id).unwrap(), outer));
+        assert_eq!(c.pick(&w, 0, 0), None);
+    }
+
+    #[test]
+    fn test_try_render_rejects_an_empty_world() {
+        let c = Camera::new(11, 11, FRAC_PI_2);
+        let err = c.try_render(&World::new()).unwrap_err();
+        assert!(
+            matches!(err, TracerError::World(WorldError::EmptyScene)),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_try_render_matches_render_for_a_valid_world() {
+        let w = World::default_world();
+        let c = Camera::new(11, 11, FRAC_PI_2).with_transform(Mat4::view_transform(
+            point(0, 0, -5),
+            point(0, 0, 0),
+            vector(0, 1, 0),
+        ));
+        assert_eq!(c.try_render(&w).unwrap(), c.render(&w));
+    }
+
+    #[test]
+    fn test_single_sample_antialiasing_matches_plain_render() {
+        let w = World::default_world();
+        let c = Camera::new(11, 11, FRAC_PI_2).with_transform(Mat4::view_transform(
+            point(0, 0, -5),
+            point(0, 0, 0),
+            vector(0, 1, 0),
+        ));
+
+        assert_eq!(c.render_aa(&w, 1), c.render(&w));
+    }
+
+    #[test]
+    fn test_antialiasing_blends_colors_along_a_silhouette() {
+        let w = World::default_world();
+        let c = Camera::new(21, 21, FRAC_PI_2).with_transform(Mat4::view_transform(
+            point(0, 0, -5),
+            point(0, 0, 0),
+            vector(0, 1, 0),
+        ));
+
+        let plain = c.render(&w);
+        let smooth = c.render_aa(&w, 4);
+
+        // Without anti-aliasing every pixel is either background or lit sphere;
+        // along the silhouette, averaging must produce something in between.
+        let row = 10;
+        let edge = (0..20)
+            .find(|&x| {
+                plain.pixel_at(x, row) == Color3::BLACK && plain.pixel_at(x + 1, row) != Color3::BLACK
+            })
+            .expect("row crosses the sphere silhouette");
+        let (background, object) = (plain.pixel_at(edge, row), plain.pixel_at(edge + 1, row));
+        let blended = [smooth.pixel_at(edge, row), smooth.pixel_at(edge + 1, row)];
+
+        assert!(
+            blended
+                .iter()
+                .any(|&pixel| pixel != background && pixel != object),
+            "expected an intermediate color at the edge, got {blended:?}"
+        );
+    }
+
+    #[test]
+    fn test_zero_aperture_matches_the_pinhole_ray() {
+        let pinhole = Camera::new(201, 101, FRAC_PI_2)
+            .with_transform(Mat4::rotation_y(FRAC_PI_4) * Mat4::translation(0.0, -2.0, 5.0));
+        let lens = pinhole.clone().with_aperture(0.0).with_focus_distance(7.5);
+
+        for (px, py) in [(0, 0), (100, 50), (37, 81)] {
+            let expected = pinhole.ray_for_pixel(px, py);
+            for sample in [(0.0, 0.0), (0.3, 0.9), (1.0, 0.5)] {
+                let r = lens.ray_through_lens(px, py, 0.5, 0.5, sample);
+                assert_eq!(r.origin().x().to_bits(), expected.origin().x().to_bits());
+                assert_eq!(r.origin().z().to_bits(), expected.origin().z().to_bits());
+                assert_eq!(r.direction().x().to_bits(), expected.direction().x().to_bits());
+                assert_eq!(r.direction().y().to_bits(), expected.direction().y().to_bits());
+            }
+            assert_eq!(lens.ray_for_pixel(px, py), expected);
+        }
+    }
+
+    #[test]
+    fn test_nonzero_aperture_spreads_origins_across_the_lens() {
+        let c = Camera::new(11, 11, FRAC_PI_2)
+            .with_aperture(0.5)
+            .with_focus_distance(4.0);
+
+        let origins: Vec<Point3> = (0..4)
+            .map(|i| {
+                c.ray_for_subpixel(5, 5, 0.25 + 0.125 * f64::from(i), 0.5)
+                    .origin()
+            })
+            .collect();
+        for (i, a) in origins.iter().enumerate() {
+            assert!(
+                a.x().hypot(a.y()) <= 0.25 && a.z() == 0.0,
+                "{a:?} is off the lens"
+            );
+            assert!(
+                origins[i + 1..].iter().all(|b| a != b),
+                "origins repeat: {origins:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lens_rays_converge_on_the_focal_plane() {
+        let c = Camera::new(11, 11, FRAC_PI_2)
+            .with_aperture(1.0)
+            .with_focus_distance(3.0);
+
+        for lens in [(0.0, 0.0), (1.0, 0.25), (0.5, 0.75)] {
+            let r = c.ray_through_lens(5, 5, 0.5, 0.5, lens);
+            assert_eq!(r.position(-3.0 / r.direction().z()), point(0, 0, -3));
+        }
+    }
+}
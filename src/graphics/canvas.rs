@@ -1,7 +1,11 @@
 use core::ops::{Index, IndexMut};
 
+use crate::error::{GraphicsError, IoError};
 use crate::primitives::Color3;
 
+/// Maximum line length for PPM pixel data, as required by the spec.
+const PPM_MAX_LINE: usize = 70;
+
 /// A 2D canvas storing colors for ray tracing.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Canvas {
@@ -36,18 +40,51 @@ impl Canvas {
 impl Canvas {
     pub fn write_pixel(&mut self, x: usize, y: usize, color: Color3) { self[y][x] = color; }
 
-    pub fn to_ppm(&self) -> String {
+    /// Returns the color of the pixel at `(x, y)`.
+    pub fn pixel_at(&self, x: usize, y: usize) -> Color3 { self[y][x] }
+
+    /// Sets every pixel of the canvas to `color`.
+    pub fn fill(&mut self, color: Color3) { self.pixels.fill(color); }
+
+    /// Resets every pixel of the canvas to black.
+    pub fn clear(&mut self) { self.fill(Color3::BLACK); }
+
+    pub fn to_ppm(&self) -> String { self.to_ppm_wrapped(Some(PPM_MAX_LINE)) }
+
+    /// Generates the PPM output, wrapping pixel data at `max_line` characters.
+    ///
+    /// `None` disables wrapping so that each canvas row is written on a single
+    /// line.
+    pub fn to_ppm_wrapped(&self, max_line: Option<usize>) -> String {
         let mut ppm = self.ppm_header();
-        ppm.push_str(&self.ppm_content());
+        ppm.push_str(&self.ppm_content(max_line));
         ppm
     }
 
-    // fn scale_color(value: f64) -> u8 { (value * 256.0).clamp(0.0, 255.0) as u8 }
+    /// Generates the PPM output after [gamma encoding] every pixel.
+    ///
+    /// A `gamma` of `1.0` produces the same output as [`Canvas::to_ppm`];
+    /// `2.2` approximates sRGB.
+    ///
+    /// [gamma encoding]: Color3::gamma_encode
+    pub fn to_ppm_gamma(&self, gamma: f64) -> String {
+        let pixels = self
+            .pixels
+            .iter()
+            .map(|pixel| pixel.gamma_encode(gamma))
+            .collect();
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+        .to_ppm()
+    }
 
     /// Generates the PPM header.
     fn ppm_header(&self) -> String { format!("P3\n{} {}\n255\n", self.width, self.height) }
 
-    fn ppm_content(&self) -> String {
+    fn ppm_content(&self, max_line: Option<usize>) -> String {
         use ::std::fmt::Write as _;
 
         let mut output = String::with_capacity(self.width * self.height * 12);
@@ -57,12 +94,14 @@ impl Canvas {
 
             for rgb_values in pixel_row.iter().map(|&color| <[u8; 3]>::from(color)) {
                 for component_str in rgb_values.into_iter().map(|component| component.to_string()) {
-                    let separator = if current_line_len == 0 { "" } else { " " };
+                    let mut separator = if current_line_len == 0 { "" } else { " " };
 
-                    // Enforce 70-char line limit (PPM spec requirement)
-                    if current_line_len + separator.len() + component_str.len() > 70 {
+                    // Wrap long lines (the PPM spec caps them at 70 chars)
+                    let next_len = current_line_len + separator.len() + component_str.len();
+                    if max_line.is_some_and(|max| next_len > max) {
                         output.push('\n');
                         current_line_len = 0;
+                        separator = "";
                     }
 
                     write!(output, "{}{}", separator, component_str).unwrap();
@@ -84,6 +123,371 @@ impl Canvas {
         let mut file = ::std::fs::File::create(path)?;
         file.write_all(content.as_bytes())
     }
+
+    /// Writes the canvas to `path` as an OpenEXR file with 32-bit float RGB
+    /// channels.
+    ///
+    /// Unlike [`Canvas::export`], colors are written as-is with no clamping or
+    /// tone mapping, so values above `1.0` survive for HDR workflows.
+    #[cfg(feature = "exr")]
+    pub fn export_exr(&self, path: impl AsRef<::std::path::Path>) -> ::std::io::Result<()> {
+        ::exr::prelude::write_rgb_file(path, self.width, self.height, |x, y| {
+            let pixel = self[y][x];
+            (pixel.r() as f32, pixel.g() as f32, pixel.b() as f32)
+        })
+        .map_err(|error| match error {
+            ::exr::error::Error::Io(error) => error,
+            error => ::std::io::Error::other(error),
+        })
+    }
+
+    /// Writes the canvas to `path` as an 8-bit RGB PNG file.
+    ///
+    /// Each component is converted with [`scale_component`], exactly as in
+    /// the PPM output.
+    ///
+    /// [`scale_component`]: crate::primitives::scale_component
+    #[cfg(feature = "png")]
+    pub fn export_png(&self, path: impl AsRef<::std::path::Path>) -> ::std::io::Result<()> {
+        let file = ::std::io::BufWriter::new(::std::fs::File::create(path)?);
+        let (width, height) = (self.width as u32, self.height as u32);
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|&pixel| <[u8; 3]>::from(pixel))
+            .collect();
+
+        let mut encoder = ::png::Encoder::new(file, width, height);
+        encoder.set_color(::png::ColorType::Rgb);
+        encoder.set_depth(::png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(png_to_io)?;
+        writer.write_image_data(&data).map_err(png_to_io)?;
+        writer.finish().map_err(png_to_io)
+    }
+}
+
+impl Canvas {
+    /// Parses a plain (`P3`) PPM image into a canvas.
+    ///
+    /// Samples are scaled by `1 / maxval`, so images with a maximum color value
+    /// other than `255` are read correctly. Tokens may be split across lines
+    /// arbitrarily, and everything from a `#` to the end of its line is treated
+    /// as a comment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoError::ParseError`] with the offending line number if the
+    /// magic number is not `P3`, a header field or sample is missing or not a
+    /// valid number, the image size overflows, a sample exceeds `maxval`, or
+    /// data follows the last pixel.
+    pub fn from_ppm(ppm: &str) -> crate::Result<Self> {
+        let mut tokens = ppm.lines().enumerate().flat_map(|(index, line)| {
+            let content = line.split_once('#').map_or(line, |(content, _)| content);
+            content.split_whitespace().map(move |token| (index + 1, token))
+        });
+        let last_line = ppm.lines().count().max(1);
+
+        let mut next = |what: &str| {
+            tokens
+                .next()
+                .ok_or_else(|| ppm_error(Some(last_line), format!("missing {what}")))
+        };
+
+        let (line, magic) = next("magic number")?;
+        if magic != "P3" {
+            return Err(ppm_error(
+                Some(line),
+                format!("expected magic number 'P3', found '{magic}'"),
+            ));
+        }
+
+        let mut number = |what: &str| {
+            let (line, token) = next(what)?;
+            token
+                .parse::<usize>()
+                .map(|value| (line, value))
+                .map_err(|_| ppm_error(Some(line), format!("invalid {what} '{token}'")))
+        };
+
+        let (_, width) = number("width")?;
+        let (size_line, height) = number("height")?;
+        let (line, maxval) = number("maximum color value")?;
+        if maxval == 0 {
+            return Err(ppm_error(
+                Some(line),
+                "maximum color value must be positive".to_string(),
+            ));
+        }
+
+        let Some(count) = width
+            .checked_mul(height)
+            .filter(|count| count.checked_mul(3).is_some())
+        else {
+            return Err(ppm_error(
+                Some(size_line),
+                format!("image size {width}x{height} is too large"),
+            ));
+        };
+
+        // Pixels are collected as they are read rather than allocated up
+        // front, so a header claiming a huge image fails on the missing data
+        // instead of on the allocation.
+        let scale = 1.0 / maxval as f64;
+        let mut pixels = Vec::new();
+        for _ in 0..count {
+            let mut sample = || {
+                let (line, value) = number("color sample")?;
+                if value > maxval {
+                    return Err(ppm_error(
+                        Some(line),
+                        format!("color sample {value} exceeds maximum {maxval}"),
+                    ));
+                }
+                Ok(value as f64 * scale)
+            };
+            pixels.push(Color3::new(sample()?, sample()?, sample()?));
+        }
+
+        if let Some((line, token)) = tokens.next() {
+            return Err(ppm_error(
+                Some(line),
+                format!("unexpected data '{token}' after pixel data"),
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+#[cfg(feature = "png")]
+fn png_to_io(error: ::png::EncodingError) -> ::std::io::Error {
+    match error {
+        ::png::EncodingError::IoError(error) => error,
+        error => ::std::io::Error::other(error),
+    }
+}
+
+fn ppm_error(line_number: Option<usize>, reason: String) -> crate::error::TracerError {
+    IoError::ParseError {
+        filename: "<ppm>".to_string(),
+        line_number,
+        reason,
+    }
+    .into()
+}
+
+impl Canvas {
+    /// Draws a straight line from `(x0, y0)` to `(x1, y1)` using Bresenham's
+    /// algorithm.
+    ///
+    /// Both endpoints are included. Coordinates may lie outside the canvas,
+    /// however far; the line is clipped to the canvas first, so only its
+    /// visible part is walked.
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, color: Color3) {
+        let Some([x0, y0, x1, y1]) = self.clip_line(x0, y0, x1, y1) else {
+            return;
+        };
+
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+
+        loop {
+            self.write_pixel_clipped(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Copies `src` into the canvas with its top-left corner at
+    /// `(dest_x, dest_y)`.
+    ///
+    /// Any part of `src` that would fall outside the canvas is clipped, and
+    /// pixels not covered by `src` are left untouched.
+    pub fn blit(&mut self, src: &Canvas, dest_x: usize, dest_y: usize) {
+        let width = src.width.min(self.width.saturating_sub(dest_x));
+        let height = src.height.min(self.height.saturating_sub(dest_y));
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        for y in 0..height {
+            self[dest_y + y][dest_x..dest_x + width].copy_from_slice(&src[y][..width]);
+        }
+    }
+
+    /// Clips the line from `(x0, y0)` to `(x1, y1)` to the canvas with the
+    /// Cohen–Sutherland algorithm, returning the endpoints of the visible
+    /// part, or `None` if the line misses the canvas entirely.
+    ///
+    /// Lines that lie entirely on the canvas are returned unchanged. The
+    /// clip edges run half a pixel outside the canvas, so rounding error
+    /// cannot reject a line that touches an edge pixel.
+    fn clip_line(&self, x0: isize, y0: isize, x1: isize, y1: isize) -> Option<[isize; 4]> {
+        const LEFT: u8 = 1;
+        const RIGHT: u8 = 2;
+        const TOP: u8 = 4;
+        const BOTTOM: u8 = 8;
+
+        let (min, max_x, max_y) = (-0.5, self.width as f64 - 0.5, self.height as f64 - 0.5);
+        let outcode = |(x, y): (f64, f64)| {
+            let mut code = 0;
+            if x < min {
+                code |= LEFT;
+            } else if x > max_x {
+                code |= RIGHT;
+            }
+            if y < min {
+                code |= TOP;
+            } else if y > max_y {
+                code |= BOTTOM;
+            }
+            code
+        };
+
+        let (mut a, mut b) = ((x0 as f64, y0 as f64), (x1 as f64, y1 as f64));
+        let (mut code_a, mut code_b) = (outcode(a), outcode(b));
+        while code_a | code_b != 0 {
+            if code_a & code_b != 0 {
+                return None;
+            }
+
+            // Move an endpoint outside the canvas onto the edge it lies
+            // beyond. The other endpoint is not beyond that edge, so the
+            // divisor is never zero.
+            let code = if code_a == 0 { code_b } else { code_a };
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let point = if code & LEFT != 0 {
+                (min, a.1 + dy * (min - a.0) / dx)
+            } else if code & RIGHT != 0 {
+                (max_x, a.1 + dy * (max_x - a.0) / dx)
+            } else if code & TOP != 0 {
+                (a.0 + dx * (min - a.1) / dy, min)
+            } else {
+                (a.0 + dx * (max_y - a.1) / dy, max_y)
+            };
+
+            if code_a == 0 {
+                (b, code_b) = (point, outcode(point));
+            } else {
+                (a, code_a) = (point, outcode(point));
+            }
+        }
+
+        let (x0, y0) = (a.0.round() as isize, a.1.round() as isize);
+        let (x1, y1) = (b.0.round() as isize, b.1.round() as isize);
+        Some([x0, y0, x1, y1])
+    }
+
+    fn write_pixel_clipped(&mut self, x: isize, y: isize, color: Color3) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y))
+            && x < self.width
+            && y < self.height
+        {
+            self.write_pixel(x, y, color);
+        }
+    }
+}
+
+impl Canvas {
+    /// Returns a copy of the canvas with Floyd–Steinberg dithering applied.
+    ///
+    /// Every pixel of the result is quantized to an exact 8-bit value, with
+    /// the rounding error diffused into neighboring pixels. This hides the
+    /// banding that plain rounding produces on smooth gradients.
+    pub fn dither(&self) -> Self {
+        let (width, height) = (self.width, self.height);
+        let mut pixels = self.pixels.clone();
+
+        for y in 0..height {
+            for x in 0..width {
+                let old = pixels[y * width + x];
+                let new = Color3::from(<[u8; 3]>::from(old));
+                let error = old - new;
+                pixels[y * width + x] = new;
+
+                let mut diffuse = |dx: isize, dy: usize, weight: f64| {
+                    let nx = x.wrapping_add_signed(dx);
+                    let ny = y + dy;
+                    if nx < width && ny < height {
+                        pixels[ny * width + nx] += error * weight;
+                    }
+                };
+
+                diffuse(1, 0, 7.0 / 16.0);
+                diffuse(-1, 1, 3.0 / 16.0);
+                diffuse(0, 1, 5.0 / 16.0);
+                diffuse(1, 1, 1.0 / 16.0);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Writes the canvas to `path` as a PPM file after [dithering] it.
+    ///
+    /// [dithering]: Canvas::dither
+    pub fn export_dithered(&self, path: impl AsRef<::std::path::Path>) -> ::std::io::Result<()> {
+        self.dither().export(path)
+    }
+}
+
+impl Canvas {
+    /// Shrinks the canvas by `factor`, averaging each `factor`×`factor` block
+    /// of pixels into one.
+    ///
+    /// Rendering at a multiple of the target resolution and downscaling gives
+    /// a simple, deterministic form of anti-aliasing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphicsError::InvalidScaleFactor`] if `factor` is zero or
+    /// does not evenly divide both the width and the height.
+    pub fn downscale(&self, factor: usize) -> crate::Result<Self> {
+        if factor == 0 || !self.width.is_multiple_of(factor) || !self.height.is_multiple_of(factor) {
+            return Err(GraphicsError::InvalidScaleFactor {
+                factor,
+                width: self.width,
+                height: self.height,
+            }
+            .into());
+        }
+
+        let (width, height) = (self.width / factor, self.height / factor);
+        let samples = (factor * factor) as f64;
+
+        let mut canvas = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum: Color3 = (0..factor)
+                    .flat_map(|dy| &self[y * factor + dy][x * factor..(x + 1) * factor])
+                    .sum();
+                canvas.write_pixel(x, y, sum / samples);
+            }
+        }
+
+        Ok(canvas)
+    }
 }
 
 impl Index<usize> for Canvas {
@@ -106,6 +510,7 @@ impl IndexMut<usize> for Canvas {
 mod tests {
 
     use super::*;
+    use crate::error::TracerError;
     use crate::prelude::color;
 
     #[test]
@@ -119,9 +524,9 @@ mod tests {
     fn test_canvas_constructor_sets_all_pixels_to_black() {
         let canvas1 = Canvas::new(4, 6);
 
-        for x in 0..3 {
-            for y in 0..5 {
-                assert_eq!(canvas1[y][x], color(0.0, 0.0, 0.0));
+        for y in 0..5 {
+            for pixel in canvas1[y].iter().take(3) {
+                assert_eq!(*pixel, color(0.0, 0.0, 0.0));
             }
         }
     }
@@ -136,4 +541,393 @@ mod tests {
         let actual = canvas1[3][2];
         assert_eq!(actual, color(1.0, 0.0, 0.0),);
     }
+
+    #[test]
+    fn test_fill_sets_every_pixel() {
+        let mut canvas = Canvas::new(5, 3);
+        let red = color(1.0, 0.0, 0.0);
+
+        canvas.fill(red);
+
+        assert_eq!(canvas.pixel_at(0, 0), red);
+        assert_eq!(canvas.pixel_at(4, 0), red);
+        assert_eq!(canvas.pixel_at(0, 2), red);
+        assert_eq!(canvas.pixel_at(4, 2), red);
+        assert_eq!(canvas.pixel_at(2, 1), red);
+        assert!(canvas.pixels().iter().all(|&pixel| pixel == red));
+    }
+
+    #[test]
+    fn test_clear_resets_every_pixel_to_black() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.fill(color(0.2, 0.4, 0.6));
+
+        canvas.clear();
+
+        assert_eq!(canvas, Canvas::new(4, 4));
+    }
+
+    #[test]
+    fn test_ppm_with_unit_gamma_matches_plain_output() {
+        let mut canvas = Canvas::new(5, 3);
+        canvas.write_pixel(0, 0, color(1.5, 0.0, 0.0));
+        canvas.write_pixel(2, 1, color(0.0, 0.5, 0.0));
+        canvas.write_pixel(4, 2, color(-0.5, 0.0, 1.0));
+
+        assert_eq!(canvas.to_ppm_gamma(1.0), canvas.to_ppm());
+    }
+
+    #[test]
+    fn test_ppm_with_gamma_brightens_midtones() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.fill(color(0.5, 0.5, 0.5));
+
+        let ppm = canvas.to_ppm_gamma(2.2);
+
+        assert_eq!(ppm.lines().nth(3), Some("186 186 186"));
+    }
+
+    #[test]
+    fn test_ppm_without_wrapping_puts_each_row_on_one_line() {
+        let mut canvas = Canvas::new(10, 2);
+        canvas.fill(color(1.0, 0.8, 0.6));
+
+        let ppm = canvas.to_ppm_wrapped(None);
+        let rows: Vec<&str> = ppm.lines().skip(3).collect();
+
+        assert_eq!(rows.len(), 2);
+        for row in rows {
+            assert_eq!(row, ["255 204 153"; 10].join(" "));
+        }
+    }
+
+    #[test]
+    fn test_ppm_splits_long_lines_at_70_characters() {
+        let mut canvas = Canvas::new(10, 2);
+        canvas.fill(color(1.0, 0.8, 0.6));
+
+        let expected = "P3\n10 2\n255\n255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 \
+                        204\n153 255 204 153 255 204 153 255 204 153 255 204 153\n255 204 153 255 204 153 \
+                        255 204 153 255 204 153 255 204 153 255 204\n153 255 204 153 255 204 153 255 204 \
+                        153 255 204 153\n";
+        assert_eq!(canvas.to_ppm(), expected);
+        assert_eq!(canvas.to_ppm_wrapped(Some(70)), expected);
+    }
+
+    #[test]
+    fn test_reading_a_file_with_the_wrong_magic_number_is_an_error() {
+        let ppm = "P32\n1 1\n255\n0 0 0\n";
+
+        let err = Canvas::from_ppm(ppm).unwrap_err();
+        let TracerError::Io(IoError::ParseError { line_number, .. }) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(line_number, Some(1));
+    }
+
+    #[test]
+    fn test_reading_pixel_data_from_a_ppm_file() {
+        let ppm = "P3\n3 2\n255\n255 127 0  0 127 255  127 255 0\n0 0 0  255 255 255  0 0 255\n";
+
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!((canvas.width(), canvas.height()), (3, 2));
+        assert_eq!(canvas[0][0], color(1.0, 0.49804, 0.0));
+        assert_eq!(canvas[0][1], color(0.0, 0.49804, 1.0));
+        assert_eq!(canvas[0][2], color(0.49804, 1.0, 0.0));
+        assert_eq!(canvas[1][0], color(0.0, 0.0, 0.0));
+        assert_eq!(canvas[1][1], color(1.0, 1.0, 1.0));
+        assert_eq!(canvas[1][2], color(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_ppm_parsing_ignores_comment_lines_and_split_tokens() {
+        let ppm =
+            "P3\n# this is a comment\n2 1\n# another comment\n255\n51\n153\n\n204\n255 # trailing\n 0 0\n";
+
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(canvas[0][0], color(0.2, 0.6, 0.8));
+        assert_eq!(canvas[0][1], color(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_ppm_parsing_scales_samples_by_the_maximum_color_value() {
+        let ppm = "P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(canvas[1][0], color(0.75, 0.5, 0.25));
+    }
+
+    #[test]
+    fn test_ppm_round_trips_through_from_ppm() {
+        let mut canvas = Canvas::new(10, 2);
+        canvas.fill(color(1.0, 0.8, 0.6));
+
+        assert_eq!(Canvas::from_ppm(&canvas.to_ppm()).unwrap(), canvas);
+    }
+
+    #[test]
+    fn test_ppm_parsing_reports_the_line_of_a_malformed_sample() {
+        let ppm = "P3\n1 1\n255\n0 0\nabc\n";
+
+        let err = Canvas::from_ppm(ppm).unwrap_err();
+        let TracerError::Io(IoError::ParseError { line_number, .. }) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(line_number, Some(5));
+    }
+
+    #[test]
+    fn test_ppm_parsing_rejects_an_oversized_header() {
+        let err = Canvas::from_ppm("P3\n4294967296 4294967296\n255\n").unwrap_err();
+        let TracerError::Io(IoError::ParseError { line_number, .. }) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(line_number, Some(2));
+
+        let err = Canvas::from_ppm("P3\n100000 100000\n255\n0 0 0\n").unwrap_err();
+        let TracerError::Io(IoError::ParseError { line_number, .. }) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(line_number, Some(4));
+    }
+
+    #[cfg(feature = "exr")]
+    #[test]
+    fn test_exr_export_preserves_hdr_values() {
+        use ::exr::prelude::read_first_rgba_layer_from_file;
+
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(1, 1, color(4.0, 0.5, 0.0));
+
+        let path = ::std::env::temp_dir().join("raytracer_canvas_hdr.exr");
+        canvas.export_exr(&path).unwrap();
+
+        let image = read_first_rgba_layer_from_file(
+            &path,
+            |resolution, _| vec![[0.0_f32; 3]; resolution.width() * resolution.height()],
+            |pixels, position, (r, g, b, _): (f32, f32, f32, f32)| {
+                pixels[position.y() * 3 + position.x()] = [r, g, b];
+            },
+        )
+        .unwrap();
+        ::std::fs::remove_file(&path).ok();
+
+        let pixels = image.layer_data.channel_data.pixels;
+        assert_eq!(pixels[3 + 1], [4.0, 0.5, 0.0]);
+        assert_eq!(pixels[0], [0.0, 0.0, 0.0]);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png_export_writes_an_image_with_the_canvas_dimensions() {
+        let mut canvas = Canvas::new(10, 10);
+        for y in 0..10 {
+            for x in 0..10 {
+                canvas.write_pixel(x, y, color(x as f64 / 9.0, y as f64 / 9.0, 0.5));
+            }
+        }
+
+        let path = ::std::env::temp_dir().join("raytracer_canvas_gradient.png");
+        canvas.export_png(&path).unwrap();
+
+        let file = ::std::io::BufReader::new(::std::fs::File::open(&path).unwrap());
+        let mut reader = ::png::Decoder::new(file).read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut data).unwrap();
+        ::std::fs::remove_file(&path).ok();
+
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (10, 10));
+        assert_eq!(data[..3], [0, 0, 128]);
+        assert_eq!(data[data.len() - 3..], [255, 255, 128]);
+    }
+
+    #[test]
+    fn test_draw_line_sets_pixels_along_a_diagonal() {
+        let mut canvas = Canvas::new(5, 5);
+        let red = color(1.0, 0.0, 0.0);
+
+        canvas.draw_line(0, 0, 4, 4, red);
+
+        for y in 0..5 {
+            for (x, &pixel) in canvas[y].iter().enumerate() {
+                let expected = if x == y { red } else { Color3::BLACK };
+                assert_eq!(pixel, expected, "pixel ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_line_sets_pixels_along_a_horizontal_line() {
+        let mut canvas = Canvas::new(5, 3);
+        let red = color(1.0, 0.0, 0.0);
+
+        canvas.draw_line(4, 1, 1, 1, red);
+
+        assert_eq!(canvas[1][1..5], [red; 4]);
+        assert_eq!(canvas[1][0], Color3::BLACK);
+        assert!(
+            canvas[0]
+                .iter()
+                .chain(&canvas[2])
+                .all(|&pixel| pixel == Color3::BLACK)
+        );
+    }
+
+    #[test]
+    fn test_draw_line_handles_steep_slopes_in_every_direction() {
+        let red = color(1.0, 0.0, 0.0);
+        for (x0, y0, x1, y1) in [(1, 0, 2, 4), (2, 4, 1, 0), (2, 0, 1, 4), (1, 4, 2, 0)] {
+            let mut canvas = Canvas::new(3, 5);
+            canvas.draw_line(x0, y0, x1, y1, red);
+
+            for y in 0..5 {
+                let set = canvas[y].iter().filter(|&&pixel| pixel == red).count();
+                assert_eq!(set, 1, "row {y} of line ({x0}, {y0}) -> ({x1}, {y1})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_line_clips_pixels_outside_the_canvas() {
+        let mut canvas = Canvas::new(3, 3);
+        let red = color(1.0, 0.0, 0.0);
+
+        canvas.draw_line(-2, 1, 5, 1, red);
+
+        assert_eq!(canvas[1], [red; 3]);
+    }
+
+    #[test]
+    fn test_draw_line_with_far_out_of_bounds_endpoints() {
+        let mut canvas = Canvas::new(4, 4);
+        let red = color(1.0, 0.0, 0.0);
+
+        canvas.draw_line(-1_000_000_000, 2, 1_000_000_000, 2, red);
+        canvas.draw_line(-1_000_000_000, -1_000_000_000, 1_000_000_000, 1_000_000_000, red);
+        canvas.draw_line(isize::MIN, 3, isize::MAX, 3, red);
+        canvas.draw_line(isize::MAX, 0, isize::MAX, isize::MIN, red);
+
+        for y in 0..4 {
+            for (x, &pixel) in canvas[y].iter().enumerate() {
+                let expected = if x == y || y >= 2 { red } else { Color3::BLACK };
+                assert_eq!(pixel, expected, "pixel ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_blit_copies_a_canvas_at_an_offset() {
+        let mut canvas = Canvas::new(5, 5);
+        let red = color(1.0, 0.0, 0.0);
+        let mut tile = Canvas::new(2, 2);
+        tile.fill(red);
+
+        canvas.blit(&tile, 1, 1);
+
+        for y in 0..5 {
+            for x in 0..5 {
+                let inside = (1..3).contains(&x) && (1..3).contains(&y);
+                let expected = if inside { red } else { Color3::BLACK };
+                assert_eq!(canvas.pixel_at(x, y), expected, "pixel ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_blit_clips_pixels_outside_the_canvas() {
+        let mut canvas = Canvas::new(3, 3);
+        let red = color(1.0, 0.0, 0.0);
+        let mut tile = Canvas::new(4, 4);
+        tile.fill(red);
+
+        canvas.blit(&tile, 2, 1);
+        canvas.blit(&tile, usize::MAX, usize::MAX);
+
+        assert_eq!(canvas[0], [Color3::BLACK; 3]);
+        assert_eq!(canvas[1], [Color3::BLACK, Color3::BLACK, red]);
+        assert_eq!(canvas[2], [Color3::BLACK, Color3::BLACK, red]);
+    }
+
+    #[test]
+    fn test_blit_entirely_past_one_edge_is_a_no_op() {
+        let mut canvas = Canvas::new(3, 3);
+        let mut tile = Canvas::new(2, 2);
+        tile.fill(color(1.0, 0.0, 0.0));
+
+        canvas.blit(&tile, 10, 0);
+        canvas.blit(&tile, 0, 3);
+
+        assert!(canvas.pixels().iter().all(|&c| c == Color3::BLACK));
+    }
+
+    #[test]
+    fn test_downscale_averages_blocks() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.write_pixel(0, 0, color(1.0, 0.0, 0.0));
+        canvas.write_pixel(1, 1, color(0.0, 1.0, 0.0));
+        canvas.write_pixel(3, 2, color(0.0, 0.0, 1.0));
+        canvas.write_pixel(2, 3, color(0.0, 0.0, 1.0));
+
+        let small = canvas.downscale(2).unwrap();
+
+        assert_eq!(small.width(), 2);
+        assert_eq!(small.height(), 2);
+        assert_eq!(small[0][0], color(0.25, 0.25, 0.0));
+        assert_eq!(small[0][1], Color3::BLACK);
+        assert_eq!(small[1][0], Color3::BLACK);
+        assert_eq!(small[1][1], color(0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_downscale_rejects_non_divisible_dimensions() {
+        let canvas = Canvas::new(5, 4);
+
+        let err = canvas.downscale(2).unwrap_err();
+        let TracerError::Graphics(err) = err else {
+            panic!("expected a graphics error, got {err:?}");
+        };
+        assert_eq!(err, GraphicsError::InvalidScaleFactor {
+            factor: 2,
+            width:  5,
+            height: 4,
+        });
+        assert_eq!(
+            err.to_string(),
+            "Invalid scale factor 2 for 5x4 canvas (must divide both dimensions)"
+        );
+        assert!(canvas.downscale(0).is_err());
+    }
+
+    #[test]
+    fn test_dithering_breaks_up_gradient_banding() {
+        // A gradient spanning a single 8-bit step rounds to one hard band
+        let mut canvas = Canvas::new(64, 4);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let value = 0.5 + (x as f64 / 63.0) / 255.0;
+                canvas.write_pixel(x, y, color(value, value, value));
+            }
+        }
+
+        let transitions = |canvas: &Canvas| {
+            (0..canvas.height())
+                .flat_map(|y| {
+                    canvas[y]
+                        .windows(2)
+                        .map(|w| (<[u8; 3]>::from(w[0]), <[u8; 3]>::from(w[1])))
+                })
+                .filter(|(a, b)| a != b)
+                .count()
+        };
+
+        let dithered = canvas.dither();
+        assert!(transitions(&dithered) > transitions(&canvas));
+        for pixel in dithered.pixels() {
+            assert_eq!(*pixel, Color3::from(<[u8; 3]>::from(*pixel)));
+        }
+    }
 }
@@ -12,7 +12,6 @@
 // #![warn(missing_docs)]
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
-#![feature(stmt_expr_attributes)]
 #![feature(const_trait_impl)]
 mod math;
 
@@ -22,8 +21,34 @@ pub mod geometry;
 pub mod graphics;
 pub mod prelude;
 pub mod primitives;
+pub mod scenes;
 pub mod shading;
 pub mod world;
 // Re-export at crate root for convenience
 pub use error::TracerError;
+use graphics::camera::Camera;
+use graphics::canvas::Canvas;
+pub use math::Interval;
 pub use prelude::Result;
+use world::World;
+
+/// Renders `world` through `camera` into a new canvas.
+///
+/// ```rust
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use raytracer::prelude::*;
+///
+/// let camera = Camera::new(11, 7, FRAC_PI_2);
+/// let canvas = raytracer::render(&World::default_world(), &camera).unwrap();
+/// assert_eq!((canvas.width(), canvas.height()), (11, 7));
+/// ```
+///
+/// # Errors
+///
+/// Returns the error from [`World::validate`] if the world has no objects or
+/// no lights.
+pub fn render(world: &World, camera: &Camera) -> Result<Canvas> {
+    world.validate()?;
+    Ok(camera.render(world))
+}
@@ -24,6 +24,9 @@ mod std_math {
     #[inline(always)]
     pub(crate) const fn max(f: f64, other: f64) -> f64 { f64::max(f, other) }
 
+    #[inline(always)]
+    pub(crate) const fn min(f: f64, other: f64) -> f64 { f64::min(f, other) }
+
     #[inline(always)]
     pub(crate) fn acos_approx(f: f64) -> f64 { f64::acos(f64::clamp(f, -1.0, 1.0)) }
 
@@ -59,3 +62,266 @@ mod std_math {
 }
 
 pub(crate) use std_math::*;
+
+use crate::cmp::epsilon::EPSILON;
+
+/// Returns the real roots of `a·t² + b·t + c = 0` in ascending order, or
+/// `None` if there are none.
+///
+/// A double root is returned twice. When `a` is within epsilon of zero the
+/// equation is solved as the linear `b·t + c = 0`, whose single root is also
+/// returned twice.
+///
+/// The roots are computed as `q / a` and `c / q` with
+/// `q = -½(b + sign(b)·√disc)`, which never subtracts two nearly equal
+/// values and so keeps the smaller root accurate when `b² ≫ 4ac`.
+pub(crate) fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    if abs(a) < EPSILON {
+        if abs(b) < EPSILON {
+            return None;
+        }
+        let t = -c / b;
+        return Some((t, t));
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let q = -0.5 * (b + copysign(sqrt(discriminant), b));
+    if q == 0.0 {
+        // Only reachable when `b` and `c` are both zero
+        return Some((0.0, 0.0));
+    }
+
+    let (t0, t1) = (q / a, c / q);
+    Some((min(t0, t1), max(t0, t1)))
+}
+
+/// Linearly interpolates from `a` to `b` by `t`, extrapolating outside
+/// `[0, 1]`.
+#[inline]
+pub(crate) const fn lerp(a: f64, b: f64, t: f64) -> f64 { a + (b - a) * t }
+
+/// Restricts `x` to `[lo, hi]`.
+#[inline]
+pub(crate) const fn clamp(x: f64, lo: f64, hi: f64) -> f64 { f64::clamp(x, lo, hi) }
+
+/// Smoothly interpolates from `0` to `1` as `x` moves from `edge0` to
+/// `edge1`, using the Hermite polynomial `3t² - 2t³`.
+///
+/// The result is `0` at or before `edge0` and `1` at or after `edge1`.
+#[inline]
+pub(crate) const fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = clamp((x - edge0) / (edge1 - edge0), 0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Maps `x` from the range `[in_lo, in_hi]` onto `[out_lo, out_hi]`.
+///
+/// Values outside the input range are extrapolated, not clamped.
+#[inline]
+pub(crate) const fn remap(x: f64, in_lo: f64, in_hi: f64, out_lo: f64, out_hi: f64) -> f64 {
+    lerp(out_lo, out_hi, (x - in_lo) / (in_hi - in_lo))
+}
+
+/// A closed range of ray parameters `[min, max]`.
+///
+/// Used to check intersection `t` values, e.g. the span between a point and
+/// a light for shadow rays, or the `minimum..maximum` extent of a truncated
+/// cylinder.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Interval {
+    /// An interval containing nothing.
+    pub const EMPTY: Self = Self::new(f64::INFINITY, f64::NEG_INFINITY);
+    /// An interval containing every value.
+    pub const UNIVERSE: Self = Self::new(f64::NEG_INFINITY, f64::INFINITY);
+
+    /// Creates a new interval.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(min: f64, max: f64) -> Self { Self { min, max } }
+
+    /// Returns the length of the interval.
+    #[inline]
+    #[must_use]
+    pub const fn size(&self) -> f64 { self.max - self.min }
+
+    /// Returns whether the interval contains no values.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.min > self.max }
+
+    /// Returns whether `t` lies in the interval, bounds included.
+    #[inline]
+    #[must_use]
+    pub const fn contains(&self, t: f64) -> bool { self.min <= t && t <= self.max }
+
+    /// Returns whether `t` lies strictly inside the interval, bounds
+    /// excluded.
+    #[inline]
+    #[must_use]
+    pub const fn surrounds(&self, t: f64) -> bool { self.min < t && t < self.max }
+
+    /// Restricts `t` to the interval.
+    #[inline]
+    #[must_use]
+    pub const fn clamp(&self, t: f64) -> f64 {
+        if t < self.min {
+            self.min
+        } else if t > self.max {
+            self.max
+        } else {
+            t
+        }
+    }
+
+    /// Returns whether `self` and `rhs` share at least one value.
+    #[inline]
+    #[must_use]
+    pub const fn overlaps(&self, rhs: &Self) -> bool { !self.intersection(rhs).is_empty() }
+
+    /// Returns the interval of values contained in both `self` and `rhs`.
+    ///
+    /// The result [`is_empty`](Self::is_empty) when they do not overlap.
+    #[inline]
+    #[must_use]
+    pub const fn intersection(&self, rhs: &Self) -> Self {
+        Self::new(max(self.min, rhs.min), min(self.max, rhs.max))
+    }
+}
+
+impl Default for Interval {
+    #[inline(always)]
+    fn default() -> Self { Self::EMPTY }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_quadratic_with_two_distinct_roots() {
+        assert_eq!(solve_quadratic(1.0, -3.0, 2.0), Some((1.0, 2.0)));
+        // A negative leading coefficient still yields ascending roots
+        assert_eq!(solve_quadratic(-1.0, 3.0, -2.0), Some((1.0, 2.0)));
+    }
+
+    #[test]
+    fn test_solve_quadratic_with_a_double_root() {
+        assert_eq!(solve_quadratic(1.0, -4.0, 4.0), Some((2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_solve_quadratic_without_real_roots() {
+        assert_eq!(solve_quadratic(1.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_solve_quadratic_keeps_the_small_root_accurate() {
+        // The naive `(-b + √disc) / 2a` cancels to zero here
+        let (t0, t1) = solve_quadratic(1.0, -1e8, 1.0).unwrap();
+        assert!((t0 - 1e-8).abs() < 1e-20);
+        assert_eq!(t1, 1e8);
+    }
+
+    #[test]
+    fn test_solve_quadratic_with_zero_linear_and_constant_terms() {
+        assert_eq!(solve_quadratic(2.0, 0.0, 0.0), Some((0.0, 0.0)));
+        assert_eq!(solve_quadratic(1.0, 0.0, -4.0), Some((-2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_solve_quadratic_falls_back_to_linear() {
+        assert_eq!(solve_quadratic(0.0, 2.0, -4.0), Some((2.0, 2.0)));
+        assert_eq!(solve_quadratic(0.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_lerp_interpolates_and_extrapolates() {
+        assert_eq!(lerp(2.0, 4.0, 0.0), 2.0);
+        assert_eq!(lerp(2.0, 4.0, 1.0), 4.0);
+        assert_eq!(lerp(2.0, 4.0, 0.5), 3.0);
+        assert_eq!(lerp(2.0, 4.0, 2.0), 6.0);
+    }
+
+    #[test]
+    fn test_clamp_restricts_to_bounds() {
+        assert_eq!(clamp(-1.0, 0.0, 1.0), 0.0);
+        assert_eq!(clamp(0.25, 0.0, 1.0), 0.25);
+        assert_eq!(clamp(3.0, 0.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_smoothstep_at_edges_and_midpoint() {
+        assert_eq!(smoothstep(1.0, 3.0, 1.0), 0.0);
+        assert_eq!(smoothstep(1.0, 3.0, 3.0), 1.0);
+        assert_eq!(smoothstep(1.0, 3.0, 2.0), 0.5);
+        assert_eq!(smoothstep(1.0, 3.0, -5.0), 0.0);
+        assert_eq!(smoothstep(1.0, 3.0, 5.0), 1.0);
+        assert!(smoothstep(1.0, 3.0, 1.5) < 0.25);
+    }
+
+    #[test]
+    fn test_remap_between_ranges() {
+        assert_eq!(remap(5.0, 0.0, 10.0, 100.0, 200.0), 150.0);
+        assert_eq!(remap(-1.0, -1.0, 1.0, 0.0, 1.0), 0.0);
+        assert_eq!(remap(2.0, -1.0, 1.0, 0.0, 1.0), 1.5);
+    }
+
+    #[test]
+    fn test_interval_contains_its_bounds() {
+        let interval = Interval::new(1.0, 2.0);
+        assert!(interval.contains(1.0));
+        assert!(interval.contains(1.5));
+        assert!(interval.contains(2.0));
+        assert!(!interval.contains(0.999));
+        assert!(!interval.contains(2.001));
+    }
+
+    #[test]
+    fn test_interval_surrounds_excludes_its_bounds() {
+        let interval = Interval::new(1.0, 2.0);
+        assert!(interval.surrounds(1.5));
+        assert!(!interval.surrounds(1.0));
+        assert!(!interval.surrounds(2.0));
+    }
+
+    #[test]
+    fn test_interval_clamp() {
+        let interval = Interval::new(-1.0, 1.0);
+        assert_eq!(interval.clamp(-3.0), -1.0);
+        assert_eq!(interval.clamp(0.5), 0.5);
+        assert_eq!(interval.clamp(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_interval_intersection_of_overlapping_intervals() {
+        let a = Interval::new(0.0, 2.0);
+        let b = Interval::new(1.0, 3.0);
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Interval::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn test_interval_intersection_of_disjoint_intervals_is_empty() {
+        let a = Interval::new(0.0, 1.0);
+        let b = Interval::new(2.0, 3.0);
+        assert!(!a.overlaps(&b));
+        assert!(a.intersection(&b).is_empty());
+    }
+
+    #[test]
+    fn test_touching_intervals_overlap_at_a_single_point() {
+        let a = Interval::new(0.0, 1.0);
+        let b = Interval::new(1.0, 2.0);
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b).size(), 0.0);
+    }
+}
@@ -35,20 +35,58 @@ pub use crate::primitives::{ColorRGB, Tuple,};
 // Re-export constructor functions
 pub use crate::primitives::{color, point, vector, tuple};
 
+#[rustfmt::skip]
 // Re-export matrix and transformation types
-pub use crate::primitives::{Mat2, Mat3, Mat4, Matrix};
+pub use crate::primitives::{Mat2, Mat3, Mat4, Matrix, Transform};
+
+#[rustfmt::skip]
+// Re-export matrix operation traits
+pub use crate::primitives::{Cofactor, Determinant, Inverse, Minor, Submatrix};
+
+// ================================
+// Geometry
+// ================================
+#[rustfmt::skip]
+// Re-export shapes
+pub use crate::geometry::{Csg, CsgOperation, Cube, Cylinder, Group, Plane, SmoothTriangle, Sphere, Triangle};
+
+#[rustfmt::skip]
+// Re-export ray casting types and helpers
+pub use crate::geometry::{Bounds, Intersection, Ray, Shape, hit, intersections};
+
+// ================================
+// Shading
+// ================================
+#[rustfmt::skip]
+pub use crate::shading::{BlendedPattern, CubeMap, Light, LightComponents, Material, NestedPattern, Pattern, PerturbedPattern, PointLight, SpotLight, StripePattern, TextureMap, UvCheckers, UvImage, UvMapping, UvPattern, lighting, lighting_components, pattern_at_shape};
+
+// ================================
+// World
+// ================================
+#[rustfmt::skip]
+pub use crate::world::{Computations, LightId, ObjectId, World, prepare_computations, schlick};
+
+#[rustfmt::skip]
+#[cfg(feature = "yaml")]
+pub use crate::scenes::parse_yaml;
+pub use crate::scenes::Scene;
 
 // ================================
 // Graphics & Rendering
 // ================================
 #[rustfmt::skip]
-pub use crate::graphics::canvas::Canvas;
+pub use crate::graphics::{camera::Camera, canvas::Canvas};
+
+#[rustfmt::skip]
+// Re-export the top-level render entry point
+pub use crate::render;
 
 // ================================
 // Constants & Utilities
 // ================================
 pub use crate::cmp::epsilon::EPSILON;
-pub use crate::cmp::float::is_equal;
+pub use crate::cmp::float::{ApproxEq, is_equal};
+pub use crate::math::Interval;
 
 // Common numerical constants
 #[rustfmt::skip]
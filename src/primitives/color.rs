@@ -1,6 +1,9 @@
-use core::ops::{Add, Mul, Sub};
+use core::iter::Sum;
+use core::ops::{Add, Div, Mul, Sub};
 
-use crate::cmp::float::is_equal;
+use crate::cmp::float::{ApproxEq, is_equal, is_equal_epsilon};
+use crate::error::GraphicsError;
+use crate::{impl_op_assign, math};
 
 /// Creates a color
 #[inline]
@@ -27,6 +30,14 @@ impl Color3 {
     #[must_use]
     #[inline(always)]
     pub const fn splat(value: f64) -> Self { Self(value, value, value) }
+
+    /// Creates a new color from integer components.
+    ///
+    /// Components are taken as-is, like [`color`], so `from_ints(1, 0, 0)` is
+    /// [`Color3::RED`]. Unlike [`color`], this can be used in `const` contexts.
+    #[must_use]
+    #[inline(always)]
+    pub const fn from_ints(r: i32, g: i32, b: i32) -> Self { Self(r as f64, g as f64, b as f64) }
 }
 
 impl Color3 {
@@ -78,6 +89,15 @@ impl PartialEq for Color3 {
     }
 }
 
+impl ApproxEq for Color3 {
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        is_equal_epsilon(self.0, other.0, epsilon)
+            && is_equal_epsilon(self.1, other.1, epsilon)
+            && is_equal_epsilon(self.2, other.2, epsilon)
+    }
+}
+
 macro_rules! impl_ops {
     ($Struct:ident, $t:ty, $Trait:ident, $func:ident, $op:tt) => {
         impl $Trait for $Struct {
@@ -150,9 +170,47 @@ macro_rules! impl_ops {
 impl_ops!(Color3, f64, Mul, mul, *);
 impl_ops!(Color3, f64, Add, add, +);
 impl_ops!(Color3, f64, Sub, sub, -);
+impl_ops!(Color3, f64, Div, div, /);
+
+impl_op_assign!(Color3, Color3, MulAssign, mul_assign, mul);
+impl_op_assign!(Color3, f64, MulAssign, mul_assign, mul);
+impl_op_assign!(Color3, Color3, AddAssign, add_assign, add);
+impl_op_assign!(Color3, f64, AddAssign, add_assign, add);
+impl_op_assign!(Color3, Color3, SubAssign, sub_assign, sub);
+impl_op_assign!(Color3, f64, SubAssign, sub_assign, sub);
+impl_op_assign!(Color3, Color3, DivAssign, div_assign, div);
+impl_op_assign!(Color3, f64, DivAssign, div_assign, div);
+
+impl Sum for Color3 {
+    #[inline]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        iter.fold(Self::BLACK, Self::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for Color3 {
+    #[inline]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.fold(Self::BLACK, Self::add)
+    }
+}
 
 const INV_255: f64 = 1.0 / 255.0;
 
+/// Converts a color component to an 8-bit channel value.
+///
+/// The component is clamped to `[0.0, 1.0]` before scaling, so out-of-range
+/// values saturate at `0` or `255` instead of wrapping around. The scaled
+/// value is rounded to the nearest integer, which makes the conversion the
+/// exact inverse of dividing a channel by `255`.
+pub fn scale_component(value: f64) -> u8 { (value.clamp(0.0, 1.0) * 255.0).round() as u8 }
+
 impl From<(u8, u8, u8)> for Color3 {
     fn from((r, g, b): (u8, u8, u8)) -> Self {
         Self::new(r as f64 * INV_255, g as f64 * INV_255, b as f64 * INV_255)
@@ -170,13 +228,192 @@ impl From<[u8; 3]> for Color3 {
 impl From<Color3> for [u8; 3] {
     fn from(color: Color3) -> Self {
         [
-            (color.r().clamp(0.0, 1.0) * 255.0).round() as u8,
-            (color.g().clamp(0.0, 1.0) * 255.0).round() as u8,
-            (color.b().clamp(0.0, 1.0) * 255.0).round() as u8,
+            scale_component(color.r()),
+            scale_component(color.g()),
+            scale_component(color.b()),
         ]
     }
 }
 
+impl Color3 {
+    /// Returns `true` if, and only if, all elements are finite.
+    ///
+    /// If any element is either `NaN`, positive or negative infinity, this
+    /// will return `false`.
+    #[inline]
+    #[must_use]
+    pub const fn is_finite(&self) -> bool {
+        self.r().is_finite() && self.g().is_finite() && self.b().is_finite()
+    }
+
+    /// Returns `true` if any element is `NaN`.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(&self) -> bool { self.r().is_nan() || self.g().is_nan() || self.b().is_nan() }
+
+    /// Returns the color with every component restricted to `[min, max]`.
+    #[inline]
+    #[must_use]
+    pub const fn clamp(&self, min: f64, max: f64) -> Self {
+        Self::new(
+            self.r().clamp(min, max),
+            self.g().clamp(min, max),
+            self.b().clamp(min, max),
+        )
+    }
+
+    /// Returns the color with every component restricted to `[0, 1]`.
+    #[inline]
+    #[must_use]
+    pub const fn clamped(&self) -> Self { self.clamp(0.0, 1.0) }
+}
+
+impl Color3 {
+    /// Performs a linear interpolation between `self` and `rhs` based on the
+    /// value `t`.
+    ///
+    /// When `t` is `0.0`, the result will be equal to `self`. When `t` is
+    /// `1.0`, the result will be equal to `rhs`. When `t` is outside of
+    /// range `[0, 1]`, the result is linearly extrapolated.
+    #[doc(alias = "mix")]
+    #[inline]
+    #[must_use]
+    pub fn lerp(self, rhs: Self, t: f64) -> Self { self * (1.0 - t) + rhs * t }
+}
+
+impl Color3 {
+    /// Returns the relative luminance of the color using the Rec. 709
+    /// weights.
+    #[inline]
+    pub const fn luminance(&self) -> f64 { 0.2126 * self.r() + 0.7152 * self.g() + 0.0722 * self.b() }
+
+    /// Returns a gray with the same [luminance](Color3::luminance) as this
+    /// color.
+    #[inline]
+    #[must_use]
+    pub const fn grayscale(&self) -> Self { Self::splat(self.luminance()) }
+}
+
+impl Color3 {
+    /// Encodes a linear color for display by raising each component to
+    /// `1 / gamma`.
+    ///
+    /// Negative components are treated as zero.
+    #[must_use]
+    pub fn gamma_encode(&self, gamma: f64) -> Self {
+        let encode = |c: f64| math::powf(math::max(c, 0.0), gamma.recip());
+        Self::new(encode(self.r()), encode(self.g()), encode(self.b()))
+    }
+
+    /// Decodes a gamma-encoded color back to linear by raising each component
+    /// to `gamma`.
+    ///
+    /// Negative components are treated as zero.
+    #[must_use]
+    pub fn gamma_decode(&self, gamma: f64) -> Self {
+        let decode = |c: f64| math::powf(math::max(c, 0.0), gamma);
+        Self::new(decode(self.r()), decode(self.g()), decode(self.b()))
+    }
+}
+
+impl Color3 {
+    /// Parses a color from a hex string in `#rrggbb` or `#rgb` form.
+    ///
+    /// The leading `#` is optional and digits are case-insensitive. In the
+    /// short form each digit is repeated, so `#0f0` is [`Color3::GREEN`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphicsError::InvalidColorValue`] if the string has the
+    /// wrong length or contains anything other than hex digits.
+    pub fn from_hex(hex: &str) -> crate::Result<Self> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let invalid = |component: &str| GraphicsError::InvalidColorValue {
+            component:   format!("{component} in hex '{hex}'"),
+            value:       f64::NAN,
+            valid_range: (0.0, 255.0),
+        };
+
+        let width = match digits.len() {
+            6 => 2,
+            3 => 1,
+            _ => return Err(invalid("length").into()),
+        };
+        let channel = |index: usize, component: &str| {
+            digits
+                .get(index * width..(index + 1) * width)
+                .filter(|part| part.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|part| u8::from_str_radix(part, 16).ok())
+                .map(|value| if width == 1 { value * 0x11 } else { value })
+                .ok_or_else(|| invalid(component))
+        };
+
+        Ok(Self::from([
+            channel(0, "red")?,
+            channel(1, "green")?,
+            channel(2, "blue")?,
+        ]))
+    }
+
+    /// Formats the color as a `#rrggbb` hex string.
+    ///
+    /// Components are converted with [`scale_component`], so out-of-range
+    /// values are clamped first.
+    pub fn to_hex(&self) -> String {
+        let [r, g, b] = <[u8; 3]>::from(*self);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+impl Color3 {
+    /// Creates a color from hue (in degrees), saturation and lightness.
+    ///
+    /// Hues outside `[0, 360)` wrap around, and saturation and lightness are
+    /// expected in `[0, 1]`.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let chroma = (1.0 - math::abs(2.0 * lightness - 1.0)) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - math::abs(sector.rem_euclid(2.0) - 1.0));
+
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        Self::new(r + m, g + m, b + m)
+    }
+
+    /// Returns the color as `(hue, saturation, lightness)`.
+    ///
+    /// Hue is in degrees within `[0, 360)`. Grays have no defined hue, so
+    /// theirs is reported as `0`.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (self.r(), self.g(), self.b());
+        let max = math::max(r, math::max(g, b));
+        let min = math::min(r, math::min(g, b));
+        let lightness = (max + min) / 2.0;
+
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - math::abs(2.0 * lightness - 1.0));
+        let hue = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        (hue * 60.0, saturation, lightness)
+    }
+}
+
 impl core::fmt::Display for Color3 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let p = f.precision().unwrap_or(3);
@@ -197,6 +434,17 @@ mod tests {
         assert_eq!(c1.b(), 1.7);
     }
 
+    #[test]
+    fn test_from_ints_in_const_context() {
+        const PALETTE: [Color3; 3] = [
+            Color3::from_ints(1, 0, 0),
+            Color3::from_ints(0, 1, 0),
+            Color3::from_ints(0, 0, 1),
+        ];
+
+        assert_eq!(PALETTE, [Color3::RED, Color3::GREEN, Color3::BLUE]);
+    }
+
     #[test]
     fn test_can_be_muliplied_by_a_scalar() {
         let c1 = color(0.2, 0.3, 0.4);
@@ -216,6 +464,205 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_scale_component_rounds_to_nearest_channel_value() {
+        assert_eq!(scale_component(0.0), 0);
+        assert_eq!(scale_component(1.0), 255);
+        assert_eq!(scale_component(0.5), 128);
+    }
+
+    #[test]
+    fn test_scale_component_saturates_out_of_range_values() {
+        assert_eq!(scale_component(1.5), 255);
+        assert_eq!(scale_component(-0.5), 0);
+    }
+
+    #[test]
+    fn test_can_be_divided_by_a_scalar() {
+        let c1 = color(0.4, 0.6, 0.8);
+
+        assert_eq!(c1 / 2.0, color(0.2, 0.3, 0.4));
+    }
+
+    #[test]
+    fn test_dividing_by_the_sample_count_averages_colors() {
+        let samples = [color(1.0, 0.0, 0.5), color(0.0, 1.0, 0.5), color(0.5, 0.5, 0.5)];
+
+        let total = samples[0] + samples[1] + samples[2];
+
+        assert_eq!(total / samples.len() as f64, color(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_colors_can_be_summed() {
+        let colors = [color(0.1, 0.2, 0.3), color(0.4, 0.5, 0.6), color(0.7, 0.8, 0.9)];
+        let expected = colors[0] + colors[1] + colors[2];
+
+        assert_eq!(colors.iter().sum::<Color3>(), expected);
+        assert_eq!(colors.into_iter().sum::<Color3>(), expected);
+        assert_eq!(core::iter::empty::<Color3>().sum::<Color3>(), Color3::BLACK);
+    }
+
+    #[test]
+    fn test_parsing_hex_colors() {
+        assert_eq!(Color3::from_hex("#ff0000").unwrap(), Color3::RED);
+        assert_eq!(Color3::from_hex("#0f0").unwrap(), Color3::GREEN);
+        assert_eq!(Color3::from_hex("00F").unwrap(), Color3::BLUE);
+        assert_eq!(Color3::from_hex("336699").unwrap(), color(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_parsing_malformed_hex_colors_is_an_error() {
+        for hex in [
+            "", "#", "#ff00", "#ff00000", "#gg0000", "#+f0000", "#ff 000", "#éé",
+        ] {
+            let err = Color3::from_hex(hex).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    crate::TracerError::Graphics(GraphicsError::InvalidColorValue { .. })
+                ),
+                "unexpected result for {hex:?}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_formatting_hex_colors() {
+        assert_eq!(Color3::RED.to_hex(), "#ff0000");
+        assert_eq!(color(0.2, 0.4, 0.6).to_hex(), "#336699");
+        assert_eq!(color(1.5, -0.5, 0.5).to_hex(), "#ff0080");
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        for hex in ["#ff0000", "#00ff00", "#0000ff", "#123456", "#abcdef"] {
+            assert_eq!(Color3::from_hex(hex).unwrap().to_hex(), hex);
+        }
+        assert_eq!(
+            Color3::from_hex(&Color3::YELLOW.to_hex()).unwrap(),
+            Color3::YELLOW
+        );
+    }
+
+    #[test]
+    fn test_pure_red_in_hsl() {
+        assert_eq!(Color3::RED.to_hsl(), (0.0, 1.0, 0.5));
+        assert_eq!(Color3::from_hsl(0.0, 1.0, 0.5), Color3::RED);
+        assert_eq!(Color3::from_hsl(360.0, 1.0, 0.5), Color3::RED);
+    }
+
+    #[test]
+    fn test_hsl_round_trip_across_hues() {
+        for hue in [0.0, 30.0, 60.0, 120.0, 180.0, 210.0, 240.0, 300.0, 345.0] {
+            let c = Color3::from_hsl(hue, 0.75, 0.4);
+            let (h, s, l) = c.to_hsl();
+
+            assert!(is_equal(h, hue), "hue {hue} came back as {h}");
+            assert!(is_equal(s, 0.75), "saturation for hue {hue} came back as {s}");
+            assert!(is_equal(l, 0.4), "lightness for hue {hue} came back as {l}");
+            assert_eq!(Color3::from_hsl(h, s, l), c);
+        }
+        assert_eq!(Color3::from_hsl(120.0, 1.0, 0.5), Color3::GREEN);
+        assert_eq!(Color3::from_hsl(240.0, 1.0, 0.5), Color3::BLUE);
+    }
+
+    #[test]
+    fn test_grays_have_zero_saturation_in_hsl() {
+        let gray = color(0.3, 0.3, 0.3);
+
+        let (h, s, l) = gray.to_hsl();
+        assert_eq!((h, s), (0.0, 0.0));
+        assert!(is_equal(l, 0.3));
+        assert_eq!(Color3::from_hsl(h, s, l), gray);
+        assert_eq!(Color3::from_hsl(200.0, 0.0, 0.3), gray);
+    }
+
+    #[test]
+    fn test_luminance_uses_rec_709_weights() {
+        assert!(is_equal(Color3::WHITE.luminance(), 1.0));
+        assert_eq!(Color3::BLACK.luminance(), 0.0);
+        assert!(is_equal(Color3::GREEN.luminance(), 0.7152));
+        assert!(is_equal(Color3::RED.luminance(), 0.2126));
+    }
+
+    #[test]
+    fn test_grayscale_produces_equal_channels() {
+        let gray = color(0.9, 0.2, 0.1).grayscale();
+
+        assert_eq!(gray.r(), gray.g());
+        assert_eq!(gray.g(), gray.b());
+        assert!(is_equal(gray.r(), 0.2126 * 0.9 + 0.7152 * 0.2 + 0.0722 * 0.1));
+    }
+
+    #[test]
+    fn test_gamma_encoding_brightens_midtones() {
+        let encoded = Color3::splat(0.5).gamma_encode(2.2);
+
+        assert!(encoded.r() > 0.5);
+        assert_eq!(encoded, Color3::splat(0.72974));
+        assert_eq!(Color3::WHITE.gamma_encode(2.2), Color3::WHITE);
+        assert_eq!(Color3::BLACK.gamma_encode(2.2), Color3::BLACK);
+    }
+
+    #[test]
+    fn test_gamma_decoding_undoes_encoding() {
+        let c = color(0.1, 0.5, 0.9);
+
+        assert_eq!(c.gamma_encode(2.2).gamma_decode(2.2), c);
+        assert_eq!(c.gamma_encode(1.0), c);
+    }
+
+    #[test]
+    fn test_clamped_pins_components_to_the_unit_range() {
+        assert_eq!(color(1.5, -0.2, 0.5).clamped(), color(1.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_clamp_respects_custom_bounds() {
+        let c = color(0.1, 0.5, 0.9);
+
+        assert_eq!(c.clamp(0.2, 0.8), color(0.2, 0.5, 0.8));
+        assert_eq!(color(3.0, -3.0, 1.5).clamp(-1.0, 2.0), color(2.0, -1.0, 1.5));
+    }
+
+    #[test]
+    fn test_lerp_blends_between_colors() {
+        assert_eq!(Color3::BLACK.lerp(Color3::WHITE, 0.0), Color3::BLACK);
+        assert_eq!(Color3::BLACK.lerp(Color3::WHITE, 1.0), Color3::WHITE);
+        assert_eq!(Color3::BLACK.lerp(Color3::WHITE, 0.5), color(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_lerp_extrapolates_outside_the_unit_range() {
+        assert_eq!(Color3::BLACK.lerp(Color3::RED, 2.0), color(2.0, 0.0, 0.0));
+        assert_eq!(Color3::BLACK.lerp(Color3::RED, -1.0), color(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut c = Color3::WHITE;
+
+        c *= 0.5;
+        assert_eq!(c, color(0.5, 0.5, 0.5));
+        c += color(0.1, 0.2, 0.3);
+        assert_eq!(c, color(0.6, 0.7, 0.8));
+        c -= &color(0.6, 0.6, 0.6);
+        assert_eq!(c, color(0.0, 0.1, 0.2));
+        c /= 2.0;
+        assert_eq!(c, color(0.0, 0.05, 0.1));
+        c *= color(1.0, 2.0, 3.0);
+        assert_eq!(c, color(0.0, 0.1, 0.3));
+    }
+
+    #[test]
+    fn test_finite_and_nan_predicates() {
+        assert!(color(0.5, 1.5, -0.5).is_finite());
+        assert!(!color(0.5, 1.5, -0.5).is_nan());
+        assert!(!color(f64::INFINITY, 0.0, 0.0).is_finite());
+        assert!(color(0.0, f64::NAN, 0.0).is_nan());
+    }
+
     #[test]
     fn test_round_trip_rgb_conversion() {
         for r in 0..=255u8 {
@@ -229,4 +676,13 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_approx_eq_with_custom_epsilon() {
+        let a = Color3::new(0.5, 0.25, 1.0);
+        let b = Color3::new(0.5, 0.2501, 1.0);
+
+        assert!(a.approx_eq_epsilon(&b, 1e-3));
+        assert!(!a.approx_eq_epsilon(&b, 1e-6));
+    }
 }
@@ -41,3 +41,17 @@ macro_rules! impl_op {
         }
     };
 }
+
+#[macro_export]
+macro_rules! impl_op_assign {
+    ($t:ty, $rhs:ty, $trt:ident, $func:ident, $op:ident) => {
+        impl core::ops::$trt<$rhs> for $t {
+            #[inline]
+            fn $func(&mut self, rhs: $rhs) { *self = (*self).$op(rhs); }
+        }
+        impl core::ops::$trt<&$rhs> for $t {
+            #[inline]
+            fn $func(&mut self, rhs: &$rhs) { *self = (*self).$op(*rhs); }
+        }
+    };
+}
@@ -4,9 +4,10 @@ use core::iter::{Product, Sum};
 use core::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
 
 use crate::cmp::epsilon::EPSILON;
-use crate::cmp::float::is_equal;
+use crate::cmp::float::{ApproxEq, is_equal, is_equal_epsilon};
+use crate::error::MathError;
 use crate::math;
-use crate::prelude::Tuple4;
+use crate::prelude::{Point3, Tuple4, Vec3};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
@@ -97,6 +98,241 @@ where
     }
 }
 
+impl<const N: usize> Matrix<N>
+where
+    [(); N * N]: Sized,
+{
+    /// Returns the element at (`row`, `col`), or `None` if either index is
+    /// out of bounds.
+    #[inline]
+    pub const fn get(&self, row: usize, col: usize) -> Option<f64> {
+        if row < N && col < N {
+            Some(self.buffer[row * N + col])
+        } else {
+            None
+        }
+    }
+
+    /// Returns row `i` as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    pub fn row(&self, i: usize) -> &[f64] { &self[i] }
+
+    /// Returns a copy of column `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` is out of bounds.
+    #[inline]
+    pub fn col(&self, j: usize) -> [f64; N] {
+        assert!(j < N, "column index {j} out of bounds for a {N}x{N} matrix");
+        core::array::from_fn(|i| self.buffer[i * N + j])
+    }
+
+    /// Returns an iterator over the rows, from top to bottom.
+    #[inline]
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[f64]> {
+        self.buffer.as_chunks::<N>().0.iter().map(<[f64; N]>::as_slice)
+    }
+
+    /// Returns an iterator over copies of the columns, from left to right.
+    #[inline]
+    pub fn cols(&self) -> impl ExactSizeIterator<Item = [f64; N]> + '_ { (0..N).map(|j| self.col(j)) }
+
+    /// Returns a mutable reference to the element at (`row`, `col`), or `None`
+    /// if either index is out of bounds.
+    #[inline]
+    pub const fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut f64> {
+        if row < N && col < N {
+            Some(&mut self.buffer[row * N + col])
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> Matrix<N>
+where
+    [(); N * N]: Sized,
+{
+    /// Computes the inverse by Gauss-Jordan elimination with partial pivoting,
+    /// or `None` if a pivot is within epsilon of zero.
+    ///
+    /// Unlike [`Inverse::inverse`], which expands cofactors, this runs in
+    /// `O(N³)` and so stays practical for larger matrices.
+    #[must_use]
+    pub fn inverse_gauss(&self) -> Option<Self> {
+        let mut matrix = self.buffer;
+        let mut inverse = Self::IDENTITY.buffer;
+
+        for col in 0..N {
+            // Swap in the row with the largest pivot to limit rounding error
+            let pivot_row = (col..N).max_by(|&a, &b| {
+                math::abs(matrix[a * N + col]).total_cmp(&math::abs(matrix[b * N + col]))
+            })?;
+            let pivot = matrix[pivot_row * N + col];
+            if math::abs(pivot) < EPSILON {
+                return None;
+            }
+            if pivot_row != col {
+                for k in 0..N {
+                    matrix.swap(col * N + k, pivot_row * N + k);
+                    inverse.swap(col * N + k, pivot_row * N + k);
+                }
+            }
+
+            for k in 0..N {
+                matrix[col * N + k] /= pivot;
+                inverse[col * N + k] /= pivot;
+            }
+
+            for row in (0..N).filter(|&row| row != col) {
+                let factor = matrix[row * N + col];
+                if factor == 0.0 {
+                    continue;
+                }
+                for k in 0..N {
+                    matrix[row * N + k] -= factor * matrix[col * N + k];
+                    inverse[row * N + k] -= factor * inverse[col * N + k];
+                }
+            }
+        }
+
+        Some(Self { buffer: inverse })
+    }
+}
+
+impl Matrix<4> {
+    /// Creates a transform that moves points by (`x`, `y`, `z`).
+    ///
+    /// Vectors are unaffected, since their `w` component is zero.
+    #[must_use]
+    pub const fn translation(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.buffer[3] = x;
+        matrix.buffer[7] = y;
+        matrix.buffer[11] = z;
+        matrix
+    }
+
+    /// Creates a transform that scales along each axis by (`x`, `y`, `z`).
+    #[must_use]
+    pub const fn scaling(x: f64, y: f64, z: f64) -> Self { Self::from_diagonal([x, y, z, 1.0]) }
+
+    /// Creates a transform that rotates by `radians` around the x axis.
+    #[must_use]
+    pub fn rotation_x(radians: f64) -> Self {
+        let (sin, cos) = math::sin_cos(radians);
+        Self::from([
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, cos, -sin, 0.0, //
+            0.0, sin, cos, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ])
+    }
+
+    /// Creates a transform that rotates by `radians` around the y axis.
+    #[must_use]
+    pub fn rotation_y(radians: f64) -> Self {
+        let (sin, cos) = math::sin_cos(radians);
+        Self::from([
+            cos, 0.0, sin, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            -sin, 0.0, cos, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ])
+    }
+
+    /// Creates a transform that rotates by `radians` around the z axis.
+    #[must_use]
+    pub fn rotation_z(radians: f64) -> Self {
+        let (sin, cos) = math::sin_cos(radians);
+        Self::from([
+            cos, -sin, 0.0, 0.0, //
+            sin, cos, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ])
+    }
+
+    /// Creates the transform that orients the world relative to an eye at
+    /// `from` looking toward `to`, with `up` roughly pointing upward.
+    ///
+    /// `up` need not be exactly perpendicular to the line of sight; only its
+    /// general direction matters.
+    #[must_use]
+    pub fn view_transform(from: Point3, to: Point3, up: Vec3) -> Self {
+        let forward = (to - from).normalize();
+        let left = forward.cross(up.normalize());
+        let true_up = left.cross(forward);
+
+        let orientation = Self::from([
+            left.x(),
+            left.y(),
+            left.z(),
+            0.0, //
+            true_up.x(),
+            true_up.y(),
+            true_up.z(),
+            0.0, //
+            -forward.x(),
+            -forward.y(),
+            -forward.z(),
+            0.0, //
+            0.0,
+            0.0,
+            0.0,
+            1.0, //
+        ]);
+        orientation * Self::translation(-from.x(), -from.y(), -from.z())
+    }
+
+    /// Creates a transform that moves each component in proportion to the
+    /// other two, e.g. `xy` moves x in proportion to y.
+    #[must_use]
+    pub const fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        Self {
+            buffer: [
+                1.0, xy, xz, 0.0, //
+                yx, 1.0, yz, 0.0, //
+                zx, zy, 1.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0, //
+            ],
+        }
+    }
+
+    /// Raises the matrix to the integer power `n` by repeated squaring.
+    ///
+    /// A negative `n` raises the inverse to `-n`, and zero gives
+    /// [`IDENTITY`](Self::IDENTITY).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is negative and the matrix is not invertible.
+    #[must_use]
+    pub fn powi(self, n: i32) -> Self {
+        let mut base = if n < 0 {
+            self.inverse().expect("negative powers need an invertible matrix")
+        } else {
+            self
+        };
+
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::IDENTITY;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
 impl<const N: usize> Default for Matrix<N>
 where
     [(); N * N]: Sized,
@@ -117,6 +353,19 @@ where
     }
 }
 
+impl<const N: usize> ApproxEq for Matrix<N>
+where
+    [(); N * N]: Sized,
+{
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        self.buffer
+            .iter()
+            .zip(&other.buffer)
+            .all(|(&a, &b)| is_equal_epsilon(a, b, epsilon))
+    }
+}
+
 impl<const N: usize> Add for Matrix<N>
 where
     [(); N * N]: Sized,
@@ -298,6 +547,40 @@ where
     fn mul(self, rhs: Tuple4) -> Self::Output { (*self).mul(rhs) }
 }
 
+/// Transforms a point, which is affected by translation (`w = 1`).
+impl Mul<Point3> for Matrix<4> {
+    type Output = Point3;
+
+    fn mul(self, rhs: Point3) -> Self::Output {
+        let p = self * Tuple4::from(rhs);
+        Point3::new(p.x(), p.y(), p.z())
+    }
+}
+
+impl Mul<Point3> for &Matrix<4> {
+    type Output = Point3;
+
+    #[inline]
+    fn mul(self, rhs: Point3) -> Self::Output { (*self).mul(rhs) }
+}
+
+/// Transforms a vector, which ignores translation (`w = 0`).
+impl Mul<Vec3> for Matrix<4> {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        let v = self * Tuple4::from(rhs);
+        Vec3::new(v.x(), v.y(), v.z())
+    }
+}
+
+impl Mul<Vec3> for &Matrix<4> {
+    type Output = Vec3;
+
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Self::Output { (*self).mul(rhs) }
+}
+
 impl<const N: usize> Index<(usize, usize)> for Matrix<N>
 where
     [(); N * N]: Sized,
@@ -339,6 +622,31 @@ where
     }
 }
 
+impl<const N: usize> core::fmt::Display for Matrix<N>
+where
+    [(); N * N]: Sized,
+{
+    /// Writes one bracketed row per line, right-aligning the columns.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let p = f.precision().unwrap_or(3);
+        let cells = self.buffer.map(|value| format!("{value:.p$}"));
+        let width = cells.iter().map(String::len).max().unwrap_or(0);
+
+        for (row, values) in cells.chunks(N.max(1)).enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (col, value) in values.iter().enumerate() {
+                let separator = if col == 0 { "" } else { ", " };
+                write!(f, "{separator}{value:>width$}")?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
 impl<const N: usize> From<[f64; N * N]> for Matrix<N>
 where
     [(); N * N]: Sized,
@@ -377,7 +685,7 @@ mod matrices {
         fn minor(&self, row: usize, col: usize) -> f64;
     }
 
-    trait Cofactor {
+    pub const trait Cofactor {
         fn cofactor(&self, row: usize, col: usize) -> f64;
     }
 
@@ -387,6 +695,10 @@ mod matrices {
         fn invertible(&self) -> bool;
         fn inverse(&self) -> Option<Self::Output>;
         // fn inverse(&self) -> Option<Self>;
+
+        /// Like [`Inverse::inverse`], but reports a non-invertible matrix as
+        /// [`MathError::MatrixNotInvertible`].
+        fn try_inverse(&self) -> crate::Result<Self::Output>;
     }
 
     // pub trait DefinedMatrix<const N: usize>
@@ -406,12 +718,128 @@ mod matrices {
 
     pub type Mat3 = Matrix<3>;
     pub type Mat4 = Matrix<4>;
+
+    impl<const N: usize> Submatrix for Matrix<N>
+    where
+        [(); N * N]: Sized,
+        [(); (N - 1) * (N - 1)]: Sized,
+    {
+        type Output = Matrix<{ N - 1 }>;
+
+        /// Returns a copy of the matrix with `row` and `col` removed.
+        #[inline]
+        fn submatrix(&self, row: usize, col: usize) -> Self::Output {
+            Matrix::from_fn(|r, c| self[(r + usize::from(r >= row), c + usize::from(c >= col))])
+        }
+    }
+
+    impl<const N: usize> Minor for Matrix<N>
+    where
+        [(); N * N]: Sized,
+        [(); (N - 1) * (N - 1)]: Sized,
+        Matrix<{ N - 1 }>: Determinant,
+    {
+        /// Returns the determinant of the submatrix at (`row`, `col`).
+        #[inline]
+        fn minor(&self, row: usize, col: usize) -> f64 { self.submatrix(row, col).determinant() }
+    }
+
+    impl<const N: usize> Cofactor for Matrix<N>
+    where
+        [(); N * N]: Sized,
+        [(); (N - 1) * (N - 1)]: Sized,
+        Matrix<{ N - 1 }>: Determinant,
+    {
+        /// Returns the minor at (`row`, `col`), negated when `row + col` is
+        /// odd.
+        #[inline]
+        fn cofactor(&self, row: usize, col: usize) -> f64 {
+            let minor = self.minor(row, col);
+            if (row + col).is_multiple_of(2) {
+                minor
+            } else {
+                -minor
+            }
+        }
+    }
+
+    impl<const N: usize> Inverse for Matrix<N>
+    where
+        [(); N * N]: Sized,
+        [(); (N - 1) * (N - 1)]: Sized,
+        Self: Determinant,
+        Matrix<{ N - 1 }>: Determinant,
+    {
+        type Output = Self;
+
+        /// Returns whether the determinant is finite and non-zero (within
+        /// epsilon), so a matrix holding NaNs is not invertible.
+        #[inline]
+        fn invertible(&self) -> bool {
+            let determinant = self.determinant();
+            determinant.is_finite() && !is_equal(determinant, 0.0)
+        }
+
+        /// Computes the inverse as the transposed matrix of cofactors divided
+        /// by the determinant, or `None` if the matrix is not invertible.
+        fn inverse(&self) -> Option<Self> {
+            let determinant = self.determinant();
+            if !determinant.is_finite() || is_equal(determinant, 0.0) {
+                return None;
+            }
+
+            Some(Self::from_fn(|row, col| self.cofactor(col, row) / determinant))
+        }
+
+        fn try_inverse(&self) -> crate::Result<Self> {
+            self.inverse().ok_or_else(|| {
+                MathError::MatrixNotInvertible {
+                    matrix: format!("{N}x{N}"),
+                }
+                .into()
+            })
+        }
+    }
+
+    impl Determinant for Mat2 {
+        /// Returns `ad - bc`.
+        #[inline]
+        fn determinant(&self) -> f64 { self[(0, 0)] * self[(1, 1)] - self[(0, 1)] * self[(1, 0)] }
+    }
+
+    /// Expands the determinant along the first row using cofactors, which
+    /// recurses through the determinant of each `N - 1` submatrix.
+    #[inline]
+    fn cofactor_expansion<const N: usize>(matrix: &Matrix<N>) -> f64
+    where
+        [(); N * N]: Sized,
+        [(); (N - 1) * (N - 1)]: Sized,
+        Matrix<{ N - 1 }>: Determinant,
+    {
+        (0..N).map(|col| matrix[(0, col)] * matrix.cofactor(0, col)).sum()
+    }
+
+    /// Implements [`Determinant`] by cofactor expansion for each listed size.
+    ///
+    /// Every size needs its own impl because a blanket impl over
+    /// `Matrix<N>` cannot bound its own recursion down to the `Mat2` base
+    /// case.
+    macro_rules! impl_determinant {
+        ($($n:literal),+) => {$(
+            impl Determinant for Matrix<$n> {
+                #[inline]
+                fn determinant(&self) -> f64 { cofactor_expansion(self) }
+            }
+        )+};
+    }
+    impl_determinant!(3, 4, 5, 6);
 }
 pub use matrices::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prelude::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_6, point, vector};
 
     #[test]
     fn test_matrix_creation() {
@@ -441,6 +869,385 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn test_matrix_get_in_range() {
+        let mut m = Mat2::from([-3.0, 5.0, 1.0, -2.0]);
+        assert_eq!(m.get(0, 1), Some(5.0));
+        assert_eq!(m.get(1, 1), Some(-2.0));
+
+        *m.get_mut(1, 0).unwrap() = 7.0;
+        assert_eq!(m[(1, 0)], 7.0);
+    }
+
+    #[test]
+    fn test_matrix_get_out_of_range() {
+        let mut m = Mat3::IDENTITY;
+        assert_eq!(m.get(3, 0), None);
+        assert_eq!(m.get(0, 3), None);
+        assert_eq!(m.get_mut(usize::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_determinant_of_2x2_matrix() {
+        let a = Mat2::from([1.0, 5.0, -3.0, 2.0]);
+        assert_eq!(a.determinant(), 17.0);
+    }
+
+    #[test]
+    fn test_determinant_of_3x3_matrix() {
+        let a = Mat3::from([1.0, 2.0, 6.0, -5.0, 8.0, -4.0, 2.0, 6.0, 4.0]);
+        assert_eq!(a.determinant(), -196.0);
+    }
+
+    #[test]
+    fn test_determinant_of_4x4_matrix() {
+        let a = Mat4::from([
+            -2.0, -8.0, 3.0, 5.0, -3.0, 1.0, 7.0, 3.0, 1.0, 2.0, -9.0, 6.0, -6.0, 7.0, 7.0, -9.0,
+        ]);
+        assert_eq!(a.determinant(), -4071.0);
+    }
+
+    #[test]
+    fn test_determinant_of_5x5_matrix() {
+        // Swapping the first two rows of an upper-triangular matrix negates
+        // the product of its diagonal
+        let a = Matrix::<5>::from([
+            0.0, 3.0, 1.0, 0.0, 2.0, //
+            2.0, 1.0, 4.0, 1.0, 3.0, //
+            0.0, 0.0, 1.0, 5.0, 1.0, //
+            0.0, 0.0, 0.0, 4.0, 2.0, //
+            0.0, 0.0, 0.0, 0.0, 5.0, //
+        ]);
+        assert_eq!(a.determinant(), -120.0);
+    }
+
+    #[test]
+    fn test_submatrix_of_3x3_matrix_is_2x2_matrix() {
+        let a = Mat3::from([1.0, 5.0, 0.0, -3.0, 2.0, 7.0, 0.0, 6.0, -3.0]);
+        assert_eq!(a.submatrix(0, 2), Mat2::from([-3.0, 2.0, 0.0, 6.0]));
+    }
+
+    #[test]
+    fn test_submatrix_of_4x4_matrix_is_3x3_matrix() {
+        let a = Mat4::from([
+            -6.0, 1.0, 1.0, 6.0, -8.0, 5.0, 8.0, 6.0, -1.0, 0.0, 8.0, 2.0, -7.0, 1.0, -1.0, 1.0,
+        ]);
+        let expected = Mat3::from([-6.0, 1.0, 6.0, -8.0, 8.0, 6.0, -7.0, -1.0, 1.0]);
+        assert_eq!(a.submatrix(2, 1), expected);
+    }
+
+    #[test]
+    fn test_minor_of_3x3_matrix() {
+        let a = Mat3::from([3.0, 5.0, 0.0, 2.0, -1.0, -7.0, 6.0, -1.0, 5.0]);
+        let b = a.submatrix(1, 0);
+        assert_eq!(b.determinant(), 25.0);
+        assert_eq!(a.minor(1, 0), 25.0);
+    }
+
+    #[test]
+    fn test_cofactor_of_3x3_matrix() {
+        let a = Mat3::from([3.0, 5.0, 0.0, 2.0, -1.0, -7.0, 6.0, -1.0, 5.0]);
+        assert_eq!(a.minor(0, 0), -12.0);
+        assert_eq!(a.cofactor(0, 0), -12.0);
+        assert_eq!(a.minor(1, 0), 25.0);
+        assert_eq!(a.cofactor(1, 0), -25.0);
+    }
+
+    #[test]
+    fn test_invertible_matrix() {
+        let a = Mat4::from([
+            6.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 6.0, 4.0, -9.0, 3.0, -7.0, 9.0, 1.0, 7.0, -6.0,
+        ]);
+        assert_eq!(a.determinant(), -2120.0);
+        assert!(a.invertible());
+    }
+
+    #[test]
+    fn test_noninvertible_matrix() {
+        let a = Mat4::from([
+            -4.0, 2.0, -2.0, -3.0, 9.0, 6.0, 2.0, 6.0, 0.0, -5.0, 1.0, -5.0, 0.0, 0.0, 0.0, 0.0,
+        ]);
+        assert_eq!(a.determinant(), 0.0);
+        assert!(!a.invertible());
+        assert_eq!(a.inverse(), None);
+        assert!(matches!(
+            a.try_inverse(),
+            Err(crate::TracerError::Math(MathError::MatrixNotInvertible { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_matrix_times_its_inverse_is_identity() {
+        let a = Mat4::from([
+            -5.0, 2.0, 6.0, -8.0, 1.0, -5.0, 1.0, 8.0, 7.0, 7.0, -6.0, -7.0, 1.0, -3.0, 7.0, 4.0,
+        ]);
+        let b = a.inverse().unwrap();
+
+        assert_eq!(b[(3, 2)], -160.0 / 532.0);
+        assert_eq!(b[(2, 3)], 105.0 / 532.0);
+        assert_eq!(a * b, Mat4::IDENTITY);
+        assert_eq!(b * a, Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_inverse_undoes_a_transformation_of_a_tuple() {
+        let a = Mat4::from([
+            3.0, -9.0, 7.0, 3.0, 3.0, -8.0, 2.0, -9.0, -4.0, 4.0, 4.0, 1.0, -6.0, 5.0, -1.0, 1.0,
+        ]);
+        let t = Tuple4::new(1.0, -2.0, 3.0, 1.0);
+
+        let transformed = a * t;
+        assert_eq!(a.inverse().unwrap() * transformed, t);
+    }
+
+    #[test]
+    fn test_raising_a_matrix_to_an_integer_power() {
+        let m =
+            Mat4::rotation_y(FRAC_PI_6) * Mat4::translation(1.0, 2.0, 3.0) * Mat4::scaling(1.5, 1.0, 0.5);
+
+        assert_eq!(m.powi(0), Mat4::IDENTITY);
+        assert_eq!(m.powi(1), m);
+        assert_eq!(m.powi(2), m * m);
+        assert_eq!(m.powi(5), m * m * m * m * m);
+        assert_eq!(m.powi(-1), m.inverse().unwrap());
+        assert_eq!(m.powi(-3) * m.powi(3), Mat4::IDENTITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "invertible")]
+    fn test_negative_power_of_a_singular_matrix_panics() { let _ = Mat4::scaling(0.0, 1.0, 1.0).powi(-1); }
+
+    #[test]
+    fn test_translation_moves_a_point() {
+        let transform = Mat4::translation(5.0, -3.0, 2.0);
+        let p = Tuple4::from(point(-3, 4, 5));
+        assert_eq!(transform * p, Tuple4::from(point(2, 1, 7)));
+    }
+
+    #[test]
+    fn test_inverse_of_translation_moves_a_point_in_reverse() {
+        let transform = Mat4::translation(5.0, -3.0, 2.0);
+        let inv = transform.inverse().unwrap();
+        let p = Tuple4::from(point(-3, 4, 5));
+        assert_eq!(inv * p, Tuple4::from(point(-8, 7, 3)));
+    }
+
+    #[test]
+    fn test_translation_does_not_affect_vectors() {
+        let transform = Mat4::translation(5.0, -3.0, 2.0);
+        let v = Tuple4::from(vector(-3, 4, 5));
+        assert_eq!(transform * v, v);
+    }
+
+    #[test]
+    fn test_translation_moves_a_point3_but_not_a_vec3() {
+        let transform = Mat4::translation(5.0, -3.0, 2.0);
+        assert_eq!(transform * point(-3, 4, 5), point(2, 1, 7));
+        assert_eq!(transform * vector(-3, 4, 5), vector(-3, 4, 5));
+    }
+
+    #[test]
+    fn test_transforming_a_point3_and_a_vec3_matches_tuple4() {
+        let transform = Mat4::rotation_x(FRAC_PI_6) * Mat4::scaling(2.0, 3.0, 4.0);
+        let (p, v) = (point(1, -2, 3), vector(-4, 5, 6));
+        assert_eq!(Tuple4::from(transform * p), transform * Tuple4::from(p));
+        assert_eq!(Tuple4::from(transform * v), transform * Tuple4::from(v));
+    }
+
+    #[test]
+    fn test_scaling_a_point_and_a_vector() {
+        let transform = Mat4::scaling(2.0, 3.0, 4.0);
+        assert_eq!(
+            transform * Tuple4::from(point(-4, 6, 8)),
+            Tuple4::from(point(-8, 18, 32))
+        );
+        assert_eq!(
+            transform * Tuple4::from(vector(-4, 6, 8)),
+            Tuple4::from(vector(-8, 18, 32))
+        );
+    }
+
+    #[test]
+    fn test_reflection_is_scaling_by_a_negative_value() {
+        let transform = Mat4::scaling(-1.0, 1.0, 1.0);
+        assert_eq!(
+            transform * Tuple4::from(point(2, 3, 4)),
+            Tuple4::from(point(-2, 3, 4))
+        );
+    }
+
+    #[test]
+    fn test_rotating_a_point_around_the_x_axis() {
+        let p = Tuple4::from(point(0, 1, 0));
+        let half_quarter = Mat4::rotation_x(FRAC_PI_4);
+        let full_quarter = Mat4::rotation_x(FRAC_PI_2);
+        let h = 2_f64.sqrt() / 2.0;
+        assert_eq!(half_quarter * p, Tuple4::from(point(0.0, h, h)));
+        assert_eq!(full_quarter * p, Tuple4::from(point(0, 0, 1)));
+        assert_eq!(
+            half_quarter.inverse().unwrap() * p,
+            Tuple4::from(point(0.0, h, -h))
+        );
+    }
+
+    #[test]
+    fn test_rotating_a_point_around_the_y_axis() {
+        let p = Tuple4::from(point(0, 0, 1));
+        let h = 2_f64.sqrt() / 2.0;
+        assert_eq!(Mat4::rotation_y(FRAC_PI_4) * p, Tuple4::from(point(h, 0.0, h)));
+        assert_eq!(Mat4::rotation_y(FRAC_PI_2) * p, Tuple4::from(point(1, 0, 0)));
+    }
+
+    #[test]
+    fn test_rotating_a_point_around_the_z_axis() {
+        let p = Tuple4::from(point(0, 1, 0));
+        let h = 2_f64.sqrt() / 2.0;
+        assert_eq!(Mat4::rotation_z(FRAC_PI_4) * p, Tuple4::from(point(-h, h, 0.0)));
+        assert_eq!(Mat4::rotation_z(FRAC_PI_2) * p, Tuple4::from(point(-1, 0, 0)));
+    }
+
+    #[test]
+    fn test_shearing_moves_each_component_in_proportion_to_the_others() {
+        let p = Tuple4::from(point(2, 3, 4));
+        let cases = [
+            (Mat4::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0), point(5, 3, 4)),
+            (Mat4::shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), point(6, 3, 4)),
+            (Mat4::shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0), point(2, 5, 4)),
+            (Mat4::shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0), point(2, 7, 4)),
+            (Mat4::shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0), point(2, 3, 6)),
+            (Mat4::shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0), point(2, 3, 7)),
+        ];
+        for (transform, expected) in cases {
+            assert_eq!(transform * p, Tuple4::from(expected));
+        }
+    }
+
+    #[test]
+    fn test_view_transform_for_the_default_orientation() {
+        let t = Mat4::view_transform(point(0, 0, 0), point(0, 0, -1), vector(0, 1, 0));
+        assert_eq!(t, Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_view_transform_looking_in_positive_z_direction() {
+        let t = Mat4::view_transform(point(0, 0, 0), point(0, 0, 1), vector(0, 1, 0));
+        assert_eq!(t, Mat4::scaling(-1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_view_transform_moves_the_world() {
+        let t = Mat4::view_transform(point(0, 0, 8), point(0, 0, 0), vector(0, 1, 0));
+        assert_eq!(t, Mat4::translation(0.0, 0.0, -8.0));
+    }
+
+    #[test]
+    fn test_arbitrary_view_transformation() {
+        let t = Mat4::view_transform(point(1, 3, 2), point(4, -2, 8), vector(1, 1, 0));
+        let expected = Mat4::from([
+            -0.50709, 0.50709, 0.67612, -2.36643, //
+            0.76772, 0.60609, 0.12122, -2.82843, //
+            -0.35857, 0.59761, -0.71714, 0.00000, //
+            0.00000, 0.00000, 0.00000, 1.00000, //
+        ]);
+        assert_eq!(t, expected);
+    }
+
+    #[test]
+    fn test_gauss_jordan_inverse_agrees_with_cofactor_inverse() {
+        let matrices = [
+            Mat4::from([
+                -5.0, 2.0, 6.0, -8.0, 1.0, -5.0, 1.0, 8.0, 7.0, 7.0, -6.0, -7.0, 1.0, -3.0, 7.0, 4.0,
+            ]),
+            Mat4::from([
+                8.0, -5.0, 9.0, 2.0, 7.0, 5.0, 6.0, 1.0, -6.0, 0.0, 9.0, 6.0, -3.0, 0.0, -9.0, -4.0,
+            ]),
+            // Needs a row swap: the first pivot is zero
+            Mat4::from([
+                0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+            ]),
+        ];
+
+        for m in matrices {
+            assert_eq!(m.inverse_gauss(), m.inverse());
+        }
+    }
+
+    #[test]
+    fn test_gauss_jordan_inverse_of_a_singular_matrix_is_none() {
+        let m = Mat4::from([
+            -4.0, 2.0, -2.0, -3.0, 9.0, 6.0, 2.0, 6.0, 0.0, -5.0, 1.0, -5.0, 0.0, 0.0, 0.0, 0.0,
+        ]);
+
+        assert_eq!(m.inverse_gauss(), None);
+    }
+
+    #[test]
+    fn test_gauss_jordan_inverse_for_other_sizes() {
+        let m = Mat2::from([4.0, 7.0, 2.0, 6.0]);
+        assert_eq!(m.inverse_gauss(), Some(Mat2::from([0.6, -0.7, -0.2, 0.4])));
+
+        let m = Matrix::<5>::diagonal(2.0);
+        assert_eq!(m.inverse_gauss(), Some(Matrix::<5>::diagonal(0.5)));
+    }
+
+    #[test]
+    fn test_display_prints_one_aligned_row_per_line() {
+        let m = Mat2::from([1.0, -2.5, 30.0, 4.0]);
+
+        assert_eq!(m.to_string(), "[ 1.000, -2.500]\n[30.000,  4.000]");
+        assert_eq!(format!("{m:.1}"), "[ 1.0, -2.5]\n[30.0,  4.0]");
+    }
+
+    #[test]
+    fn test_approx_eq_epsilon_loosens_the_comparison() {
+        let m = Mat4::rotation_x(FRAC_PI_6) * Mat4::rotation_z(FRAC_PI_6);
+        // Expected values rounded to four decimal places
+        let expected = Mat4::from([
+            0.8660, -0.5000, 0.0000, 0.0000, //
+            0.4330, 0.7500, -0.5000, 0.0000, //
+            0.2500, 0.4330, 0.8660, 0.0000, //
+            0.0000, 0.0000, 0.0000, 1.0000, //
+        ]);
+
+        assert_ne!(m, expected);
+        assert!(m.approx_eq_epsilon(&expected, 1e-4));
+        assert!(!m.approx_eq_epsilon(&expected, 1e-5));
+    }
+
+    #[test]
+    fn test_rows_and_columns_of_a_matrix() {
+        let m = Mat3::from([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+        assert_eq!(m.row(1), [4.0, 5.0, 6.0]);
+        assert_eq!(m.col(1), [2.0, 5.0, 8.0]);
+        assert_eq!(m.rows().collect::<Vec<_>>(), [
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0]
+        ]);
+        assert_eq!(m.cols().collect::<Vec<_>>(), [
+            [1.0, 4.0, 7.0],
+            [2.0, 5.0, 8.0],
+            [3.0, 6.0, 9.0]
+        ]);
+    }
+
+    #[test]
+    fn test_column_iterator_gathers_strided_elements() {
+        assert_eq!(Mat4::IDENTITY.cols().next(), Some([1.0, 0.0, 0.0, 0.0]));
+
+        let m = Mat4::from_fn(|row, col| (row * 4 + col) as f64);
+        for (j, col) in m.cols().enumerate() {
+            let gathered: Vec<f64> = (0..4).map(|i| m[(i, j)]).collect();
+            assert_eq!(col.as_slice(), gathered);
+        }
+        assert_eq!(m.cols().len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "column index 4 out of bounds")]
+    fn test_column_out_of_bounds_panics() { let _ = Mat4::IDENTITY.col(4); }
+
     #[test]
     fn test_matrix_addition() {
         let a = Mat2::from([7.0, 3.0, -4.0, 2.0]);
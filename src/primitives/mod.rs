@@ -3,12 +3,14 @@ mod components;
 mod macros;
 mod matrix;
 mod point;
+mod transform;
 mod tuple;
 mod vector;
 
-pub use color::{Color3, color};
+pub use color::{Color3, color, scale_component};
 pub use components::*;
-pub use matrix::{Mat2, Mat3, Mat4, Matrix};
+pub use matrix::{Cofactor, Determinant, Inverse, Mat2, Mat3, Mat4, Matrix, Minor, Submatrix};
 pub use point::{Point3, point};
+pub use transform::Transform;
 pub use tuple::{Tuple4, tuple};
 pub use vector::{Vec3, vector};
@@ -1,6 +1,8 @@
 use core::ops::{Add, Sub};
 
-use crate::cmp::float::is_equal;
+use crate::cmp::epsilon::EPSILON;
+use crate::cmp::float::{ApproxEq, is_equal, is_equal_epsilon};
+use crate::math;
 use crate::primitives::tuple::Tuple4;
 use crate::primitives::vector::Vec3;
 
@@ -29,6 +31,13 @@ impl Point3 {
     #[inline]
     #[must_use]
     pub const fn splat(value: f64) -> Self { Self(value, value, value) }
+
+    /// Creates a new point from integer components.
+    ///
+    /// Unlike [`point`], this can be used in `const` contexts.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_ints(x: i32, y: i32, z: i32) -> Self { Self(x as f64, y as f64, z as f64) }
 }
 
 impl Point3 {
@@ -74,6 +83,66 @@ impl Point3 {
     pub const fn w(&self) -> f64 { 1.0 }
 }
 
+impl Point3 {
+    /// Returns `true` if, and only if, all elements are finite.
+    ///
+    /// If any element is either `NaN`, positive or negative infinity, this
+    /// will return `false`.
+    #[inline]
+    #[must_use]
+    pub const fn is_finite(&self) -> bool {
+        self.x().is_finite() && self.y().is_finite() && self.z().is_finite()
+    }
+
+    /// Returns `true` if any element is `NaN`.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(&self) -> bool { self.x().is_nan() || self.y().is_nan() || self.z().is_nan() }
+
+    /// Returns whether the four points lie on a common plane.
+    ///
+    /// The points are coplanar when the edges from `p1` to the others span no
+    /// volume, i.e. their scalar triple product is within epsilon of zero.
+    #[inline]
+    #[must_use]
+    pub fn coplanar(p1: Self, p2: Self, p3: Self, p4: Self) -> bool {
+        math::abs(Vec3::scalar_triple(p2 - p1, p3 - p1, p4 - p1)) < EPSILON
+    }
+}
+
+impl Point3 {
+    /// Computes the Euclidean distance between `self` and `rhs`.
+    #[inline]
+    #[must_use]
+    pub fn distance(self, rhs: Self) -> f64 { (self - rhs).length() }
+
+    /// Computes the squared Euclidean distance between `self` and `rhs`.
+    ///
+    /// This is faster than `distance()` as it avoids a square root operation.
+    #[inline]
+    #[must_use]
+    pub const fn distance_squared(self, rhs: Self) -> f64 {
+        let (dx, dy, dz) = (self.x() - rhs.x(), self.y() - rhs.y(), self.z() - rhs.z());
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Performs a linear interpolation between `self` and `rhs` based on the
+    /// value `t`.
+    ///
+    /// When `t` is `0.0`, the result will be equal to `self`. When `t` is
+    /// `1.0`, the result will be equal to `rhs`. When `t` is outside of
+    /// range `[0, 1]`, the result is linearly extrapolated.
+    #[doc(alias = "mix")]
+    #[inline]
+    #[must_use]
+    pub fn lerp(self, rhs: Self, t: f64) -> Self { self + (rhs - self) * t }
+
+    /// Returns the point halfway between `self` and `rhs`.
+    #[inline]
+    #[must_use]
+    pub fn midpoint(self, rhs: Self) -> Self { self.lerp(rhs, 0.5) }
+}
+
 impl Default for Point3 {
     #[inline(always)]
     fn default() -> Self { Self::ZERO }
@@ -89,6 +158,16 @@ impl PartialEq for Point3 {
     }
 }
 
+impl ApproxEq for Point3 {
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        is_equal_epsilon(self.x(), other.x(), epsilon)
+            && is_equal_epsilon(self.y(), other.y(), epsilon)
+            && is_equal_epsilon(self.z(), other.z(), epsilon)
+            && is_equal_epsilon(self.w(), other.w(), epsilon)
+    }
+}
+
 // Point + Vector = Point
 impl Add<Vec3> for Point3 {
     type Output = Self;
@@ -177,3 +256,72 @@ impl core::fmt::Display for Point3 {
         write!(f, "[{:.*}, {:.*}, {:.*}]", p, self.x(), p, self.y(), p, self.z())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_on_a_plane_are_coplanar() {
+        let p1 = point(0, 2, 0);
+        let p2 = point(1, 2, 5);
+        let p3 = point(-3, 2, 1);
+        let p4 = point(7, 2, -4);
+        assert!(Point3::coplanar(p1, p2, p3, p4));
+    }
+
+    #[test]
+    fn test_corners_of_a_tetrahedron_are_not_coplanar() {
+        let p1 = Point3::ZERO;
+        assert!(!Point3::coplanar(p1, Point3::X, Point3::Y, Point3::Z));
+    }
+
+    #[test]
+    fn test_distance_between_points() {
+        let a = Point3::ZERO;
+        let b = Point3::new(3.0, 4.0, 0.0);
+
+        assert_eq!(a.distance(b), 5.0);
+        assert_eq!(b.distance(a), 5.0);
+        assert_eq!(a.distance_squared(b), 25.0);
+    }
+
+    #[test]
+    fn test_midpoint_between_points() {
+        let a = Point3::ZERO;
+        let b = Point3::new(3.0, 4.0, 0.0);
+
+        assert_eq!(a.midpoint(b), Point3::new(1.5, 2.0, 0.0));
+        assert_eq!(b.midpoint(a), Point3::new(1.5, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_lerp_between_points() {
+        let a = point(0, 0, 0);
+        let b = point(10, 0, 0);
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), point(5, 0, 0));
+        assert_eq!(a.lerp(b, 1.5), point(15, 0, 0));
+        assert_eq!(a.lerp(b, -0.5), point(-5, 0, 0));
+    }
+
+    #[test]
+    fn test_finite_and_nan_predicates() {
+        assert!(Point3::NAN.is_nan());
+        assert!(!Point3::INFINITY.is_finite());
+        assert!(point(1, 2, 3).is_finite());
+        assert!(!point(1, 2, 3).is_nan());
+        assert!(point(f64::NAN, 2.0, 3.0).is_nan());
+    }
+
+    #[test]
+    fn test_approx_eq_with_custom_epsilon() {
+        let a = point(1.0, 2.0, 3.0);
+        let b = point(1.00001, 2.0, 3.0);
+
+        assert!(a.approx_eq_epsilon(&b, 1e-4));
+        assert!(!a.approx_eq_epsilon(&b, 1e-9));
+    }
+}
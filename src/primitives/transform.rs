@@ -0,0 +1,160 @@
+use crate::primitives::matrix::Mat4;
+
+/// A builder for composing transformation matrices in the order they are
+/// applied.
+///
+/// Matrix products apply right-to-left, so a rotation followed by a scale and
+/// a translation is written `T * S * R`. The builder takes care of that
+/// reversal:
+///
+/// ```rust
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use raytracer::prelude::*;
+///
+/// let transform = Transform::identity()
+///     .rotate_x(FRAC_PI_2)
+///     .scale(5, 5, 5)
+///     .translate(10, 5, 7)
+///     .build();
+///
+/// let p = Tuple4::from(point(1, 0, 1));
+/// assert_eq!(transform * p, Tuple4::from(point(15, 0, 7)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    matrix: Mat4,
+}
+
+impl Transform {
+    /// Starts a transform that leaves everything unchanged.
+    #[inline]
+    #[must_use]
+    pub const fn identity() -> Self {
+        Self {
+            matrix: Mat4::IDENTITY,
+        }
+    }
+
+    /// Applies `matrix` after the transforms added so far.
+    #[inline]
+    #[must_use]
+    pub fn then(self, matrix: Mat4) -> Self {
+        Self {
+            matrix: matrix * self.matrix,
+        }
+    }
+
+    /// Applies a translation by (`x`, `y`, `z`).
+    #[inline]
+    #[must_use]
+    pub fn translate<X, Y, Z>(self, x: X, y: Y, z: Z) -> Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        Z: Into<f64>,
+    {
+        self.then(Mat4::translation(x.into(), y.into(), z.into()))
+    }
+
+    /// Applies a scale by (`x`, `y`, `z`).
+    #[inline]
+    #[must_use]
+    pub fn scale<X, Y, Z>(self, x: X, y: Y, z: Z) -> Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        Z: Into<f64>,
+    {
+        self.then(Mat4::scaling(x.into(), y.into(), z.into()))
+    }
+
+    /// Applies a rotation by `radians` around the x axis.
+    #[inline]
+    #[must_use]
+    pub fn rotate_x(self, radians: f64) -> Self { self.then(Mat4::rotation_x(radians)) }
+
+    /// Applies a rotation by `radians` around the y axis.
+    #[inline]
+    #[must_use]
+    pub fn rotate_y(self, radians: f64) -> Self { self.then(Mat4::rotation_y(radians)) }
+
+    /// Applies a rotation by `radians` around the z axis.
+    #[inline]
+    #[must_use]
+    pub fn rotate_z(self, radians: f64) -> Self { self.then(Mat4::rotation_z(radians)) }
+
+    /// Applies a shear. See [`Mat4::shearing`].
+    #[inline]
+    #[must_use]
+    pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        self.then(Mat4::shearing(xy, xz, yx, yz, zx, zy))
+    }
+
+    /// Returns the composed matrix.
+    #[inline]
+    #[must_use]
+    pub const fn build(self) -> Mat4 { self.matrix }
+}
+
+impl Default for Transform {
+    #[inline(always)]
+    fn default() -> Self { Self::identity() }
+}
+
+impl From<Transform> for Mat4 {
+    #[inline]
+    fn from(transform: Transform) -> Self { transform.build() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{FRAC_PI_2, Tuple4, point};
+
+    #[test]
+    fn test_individual_transformations_are_applied_in_sequence() {
+        let p = Tuple4::from(point(1, 0, 1));
+        let a = Mat4::rotation_x(FRAC_PI_2);
+        let b = Mat4::scaling(5.0, 5.0, 5.0);
+        let c = Mat4::translation(10.0, 5.0, 7.0);
+
+        let p2 = a * p;
+        assert_eq!(p2, Tuple4::from(point(1, -1, 0)));
+        let p3 = b * p2;
+        assert_eq!(p3, Tuple4::from(point(5, -5, 0)));
+        let p4 = c * p3;
+        assert_eq!(p4, Tuple4::from(point(15, 0, 7)));
+    }
+
+    #[test]
+    fn test_chained_transformations_must_be_applied_in_reverse_order() {
+        let a = Mat4::rotation_x(FRAC_PI_2);
+        let b = Mat4::scaling(5.0, 5.0, 5.0);
+        let c = Mat4::translation(10.0, 5.0, 7.0);
+
+        let transform = Transform::identity()
+            .rotate_x(FRAC_PI_2)
+            .scale(5, 5, 5)
+            .translate(10, 5, 7)
+            .build();
+
+        assert_eq!(transform, c * b * a);
+        assert_eq!(
+            transform * Tuple4::from(point(1, 0, 1)),
+            Tuple4::from(point(15, 0, 7))
+        );
+    }
+
+    #[test]
+    fn test_then_applies_an_arbitrary_matrix_last() {
+        let shear = Mat4::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let transform = Transform::identity().scale(2, 2, 2).then(shear).build();
+        assert_eq!(transform, shear * Mat4::scaling(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn test_empty_transform_is_identity() {
+        assert_eq!(Transform::default().build(), Mat4::IDENTITY);
+    }
+}
@@ -1,10 +1,10 @@
 use core::iter::{Product, Sum};
 use core::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
 
-use crate::cmp::float::is_equal;
-use crate::impl_op;
+use crate::cmp::float::{ApproxEq, is_equal, is_equal_epsilon};
 use crate::primitives::point::Point3;
 use crate::primitives::vector::Vec3;
+use crate::{impl_op, math};
 
 /// Creates a 4-dimensional tuple
 #[inline]
@@ -32,6 +32,15 @@ impl Tuple4 {
     #[inline]
     #[must_use]
     pub const fn splat(value: f64) -> Self { Self(value, value, value, value) }
+
+    /// Creates a new tuple from integer components.
+    ///
+    /// Unlike [`tuple`], this can be used in `const` contexts.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_ints(x: i32, y: i32, z: i32, w: i32) -> Self {
+        Self(x as f64, y as f64, z as f64, w as f64)
+    }
 }
 
 impl Tuple4 {
@@ -75,6 +84,64 @@ impl Tuple4 {
         Self::new(f(self.x()), f(self.y()), f(self.z()), f(self.w()))
     }
 
+    /// Returns `true` if, and only if, all elements are finite.
+    ///
+    /// If any element is either `NaN`, positive or negative infinity, this
+    /// will return `false`.
+    #[inline]
+    #[must_use]
+    pub const fn is_finite(&self) -> bool {
+        self.x().is_finite() && self.y().is_finite() && self.z().is_finite() && self.w().is_finite()
+    }
+
+    /// Returns `true` if any element is `NaN`.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(&self) -> bool {
+        self.x().is_nan() || self.y().is_nan() || self.z().is_nan() || self.w().is_nan()
+    }
+
+    /// Computes the dot product of `self` and `rhs` over all four components.
+    #[inline]
+    #[must_use]
+    pub const fn dot(self, rhs: Self) -> f64 {
+        (self.x() * rhs.x()) + (self.y() * rhs.y()) + (self.z() * rhs.z()) + (self.w() * rhs.w())
+    }
+
+    /// Computes the cross product of the `x`, `y` and `z` components of `self`
+    /// and `rhs`.
+    ///
+    /// The `w` components are ignored and the result is always a vector.
+    #[inline]
+    #[must_use]
+    pub const fn cross(self, rhs: Self) -> Self {
+        Self(
+            self.y() * rhs.z() - self.z() * rhs.y(),
+            self.z() * rhs.x() - self.x() * rhs.z(),
+            self.x() * rhs.y() - self.y() * rhs.x(),
+            0.0,
+        )
+    }
+
+    /// Computes the length of `self` over all four components.
+    #[doc(alias = "length")]
+    #[inline]
+    #[must_use]
+    pub fn magnitude(self) -> f64 { math::sqrt(self.magnitude_squared()) }
+
+    /// Computes the squared length of `self` over all four components.
+    #[doc(alias = "length_squared")]
+    #[inline]
+    #[must_use]
+    pub const fn magnitude_squared(self) -> f64 { self.dot(self) }
+
+    /// Returns `self` scaled to magnitude 1.0.
+    ///
+    /// For valid results, `self` must be finite and _not_ of magnitude zero.
+    #[inline]
+    #[must_use]
+    pub fn normalize(self) -> Self { self / self.magnitude() }
+
     pub fn is_point(&self) -> bool { is_equal(self.3, 1.0) }
 
     pub fn is_vector(&self) -> bool { is_equal(self.3, 0.0) }
@@ -95,6 +162,16 @@ impl PartialEq for Tuple4 {
     }
 }
 
+impl ApproxEq for Tuple4 {
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        is_equal_epsilon(self.x(), other.x(), epsilon)
+            && is_equal_epsilon(self.y(), other.y(), epsilon)
+            && is_equal_epsilon(self.z(), other.z(), epsilon)
+            && is_equal_epsilon(self.w(), other.w(), epsilon)
+    }
+}
+
 impl Mul for Tuple4 {
     type Output = Self;
 
@@ -343,3 +420,68 @@ impl From<Tuple4> for (f64, f64, f64, f64) {
     #[inline]
     fn from(v: Tuple4) -> Self { (v.0, v.1, v.2, v.3) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{point, vector};
+
+    #[test]
+    fn test_magnitude_of_vectors() {
+        assert_eq!(Tuple4::from(vector(1, 0, 0)).magnitude(), 1.0);
+        assert_eq!(Tuple4::from(vector(0, 0, 1)).magnitude(), 1.0);
+        assert_eq!(Tuple4::from(vector(1, 2, 3)).magnitude(), 14_f64.sqrt());
+        assert_eq!(Tuple4::from(vector(-1, -2, -3)).magnitude(), 14_f64.sqrt());
+        assert_eq!(Tuple4::from(vector(1, 2, 3)).magnitude_squared(), 14.0);
+    }
+
+    #[test]
+    fn test_normalizing_vectors() {
+        assert_eq!(
+            Tuple4::from(vector(4, 0, 0)).normalize(),
+            Tuple4::from(vector(1, 0, 0))
+        );
+
+        let normalized = Tuple4::from(vector(1, 2, 3)).normalize();
+        assert_eq!(normalized, Tuple4::from(vector(1, 2, 3).normalize()));
+        assert!(is_equal(normalized.magnitude(), 1.0));
+    }
+
+    #[test]
+    fn test_dot_and_cross_match_vec3() {
+        let (a, b) = (vector(1, 2, 3), vector(2, 3, 4));
+        let (ta, tb) = (Tuple4::from(a), Tuple4::from(b));
+
+        assert_eq!(ta.dot(tb), 20.0);
+        assert_eq!(ta.dot(tb), a.dot(b));
+        assert_eq!(ta.cross(tb), Tuple4::from(vector(-1, 2, -1)));
+        assert_eq!(tb.cross(ta), Tuple4::from(b.cross(a)));
+    }
+
+    #[test]
+    fn test_dot_includes_the_w_component() {
+        let p = Tuple4::from(point(1, 2, 3));
+
+        assert_eq!(p.dot(p), 15.0);
+        assert!(p.cross(p).is_vector());
+    }
+
+    #[test]
+    fn test_finite_and_nan_predicates() {
+        assert!(Tuple4::NAN.is_nan());
+        assert!(!Tuple4::INFINITY.is_finite());
+        assert!(Tuple4::from(point(1, 2, 3)).is_finite());
+        assert!(!Tuple4::new(1.0, 2.0, 3.0, f64::INFINITY).is_finite());
+        assert!(Tuple4::new(1.0, 2.0, 3.0, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_approx_eq_compares_w() {
+        let a = Tuple4::new(1.0, 2.0, 3.0, 1.0);
+        let b = Tuple4::new(1.0, 2.0, 3.0, 1.0005);
+
+        assert!(a.approx_eq_epsilon(&b, 1e-3));
+        assert!(!a.approx_eq_epsilon(&b, 1e-5));
+        assert!(!a.approx_eq_epsilon(&Tuple4::from(vector(1, 2, 3)), 1e-3));
+    }
+}
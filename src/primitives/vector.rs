@@ -2,9 +2,9 @@ use core::iter::{Product, Sum};
 use core::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
 
 use crate::cmp::epsilon::EPSILON;
-use crate::cmp::float::is_equal;
+use crate::cmp::float::{ApproxEq, is_equal, is_equal_epsilon};
 use crate::prelude::Tuple4;
-use crate::{impl_op, math};
+use crate::{impl_op, impl_op_assign, math};
 
 /// Creates a 3-dimensional vector.
 #[inline(always)]
@@ -32,6 +32,13 @@ impl Vec3 {
     #[must_use]
     #[inline(always)]
     pub const fn splat(value: f64) -> Self { Self(value, value, value) }
+
+    /// Creates a new vector from integer components.
+    ///
+    /// Unlike [`vector`], this can be used in `const` contexts.
+    #[must_use]
+    #[inline(always)]
+    pub const fn from_ints(x: i32, y: i32, z: i32) -> Self { Self(x as f64, y as f64, z as f64) }
 }
 
 impl Vec3 {
@@ -89,6 +96,94 @@ impl Vec3 {
         Self::new(f(self.x()), f(self.y()), f(self.z()))
     }
 
+    /// Returns `true` if, and only if, all elements are finite.
+    ///
+    /// If any element is either `NaN`, positive or negative infinity, this
+    /// will return `false`.
+    #[inline]
+    #[must_use]
+    pub const fn is_finite(&self) -> bool {
+        self.x().is_finite() && self.y().is_finite() && self.z().is_finite()
+    }
+
+    /// Returns `true` if any element is `NaN`.
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(&self) -> bool { self.x().is_nan() || self.y().is_nan() || self.z().is_nan() }
+
+    /// Returns a vector containing the minimum values for each element of
+    /// `self` and `rhs`.
+    ///
+    /// In other words this computes `[min(x, rhs.x), min(y, rhs.y), ..]`.
+    #[inline]
+    #[must_use]
+    pub const fn min(self, rhs: Self) -> Self {
+        Self(
+            math::min(self.x(), rhs.x()),
+            math::min(self.y(), rhs.y()),
+            math::min(self.z(), rhs.z()),
+        )
+    }
+
+    /// Returns a vector containing the maximum values for each element of
+    /// `self` and `rhs`.
+    ///
+    /// In other words this computes `[max(x, rhs.x), max(y, rhs.y), ..]`.
+    #[inline]
+    #[must_use]
+    pub const fn max(self, rhs: Self) -> Self {
+        Self(
+            math::max(self.x(), rhs.x()),
+            math::max(self.y(), rhs.y()),
+            math::max(self.z(), rhs.z()),
+        )
+    }
+
+    /// Returns a vector containing the absolute value of each element of
+    /// `self`.
+    #[inline]
+    #[must_use]
+    pub const fn abs(self) -> Self { Self(math::abs(self.x()), math::abs(self.y()), math::abs(self.z())) }
+
+    /// Returns the smallest element of `self`.
+    #[inline]
+    #[must_use]
+    pub const fn min_element(self) -> f64 { math::min(self.x(), math::min(self.y(), self.z())) }
+
+    /// Returns the largest element of `self`.
+    #[inline]
+    #[must_use]
+    pub const fn max_element(self) -> f64 { math::max(self.x(), math::max(self.y(), self.z())) }
+
+    /// Returns a vector containing the largest integer less than or equal to
+    /// each element of `self`.
+    #[inline]
+    #[must_use]
+    pub fn floor(self) -> Self { self.map(math::floor) }
+
+    /// Returns a vector containing the smallest integer greater than or equal
+    /// to each element of `self`.
+    #[inline]
+    #[must_use]
+    pub fn ceil(self) -> Self { self.map(math::ceil) }
+
+    /// Returns a vector containing the nearest integer to each element of
+    /// `self`.
+    ///
+    /// Half-way cases round away from zero, like [`f64::round`].
+    #[inline]
+    #[must_use]
+    pub fn round(self) -> Self { self.map(math::round) }
+
+    /// Returns a vector containing the fractional part of each element of
+    /// `self`, as `x - trunc(x)`.
+    ///
+    /// Like [`f64::fract`], the result keeps the sign of the element, so the
+    /// fractional part of `-1.25` is `-0.25`.
+    #[inline]
+    #[must_use]
+    pub fn fract(self) -> Self { self.map(|x| x - math::trunc(x)) }
+
     /// Computes the dot product of `self` and `rhs`.
     #[inline]
     #[must_use]
@@ -113,6 +208,15 @@ impl Vec3 {
         )
     }
 
+    /// Computes the scalar triple product `a · (b × c)`.
+    ///
+    /// This is the signed volume of the parallelepiped spanned by the three
+    /// vectors, and is zero when they are coplanar.
+    #[doc(alias = "triple_product")]
+    #[inline]
+    #[must_use]
+    pub const fn scalar_triple(a: Self, b: Self, c: Self) -> f64 { a.dot(b.cross(c)) }
+
     /// Computes the length of `self`.
     #[doc(alias = "magnitude")]
     #[inline]
@@ -249,6 +353,116 @@ impl Vec3 {
         self - normal * (2.0 * self.dot(normal))
     }
 
+    /// Returns the direction of the incident vector `self` after it passes
+    /// through a surface with normal `normal`, or `None` on total internal
+    /// reflection.
+    ///
+    /// `n_ratio` is the refractive index on the incoming side divided by the
+    /// one on the outgoing side. `self` points toward the surface and `normal`
+    /// away from it, as for [`Self::reflect()`]; both must be normalized.
+    ///
+    /// *Snell's law: n₁ sin θᵢ = n₂ sin θₜ*
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `normal` is not normalized when `assert` is enabled.
+    #[inline]
+    #[must_use]
+    pub fn refract(self, normal: Self, n_ratio: f64) -> Option<Self> {
+        debug_assert!(normal.is_normalized());
+        let cos_i = -self.dot(normal);
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return None;
+        }
+
+        let cos_t = math::sqrt(1.0 - sin2_t);
+        Some(self * n_ratio + normal * (n_ratio * cos_i - cos_t))
+    }
+
+    /// Returns the angle between `self` and `rhs` in radians, in `[0, π]`.
+    ///
+    /// If either vector has zero length the angle is undefined, and `0.0` is
+    /// returned instead of `NaN`.
+    #[inline]
+    #[must_use]
+    pub fn angle_between(self, rhs: Self) -> f64 {
+        let lengths = math::sqrt(self.length_squared() * rhs.length_squared());
+        if lengths == 0.0 {
+            return 0.0;
+        }
+        math::acos_approx(self.dot(rhs) / lengths)
+    }
+
+    /// Returns the component of `self` parallel to `other`.
+    ///
+    /// Returns [`Vec3::ZERO`] if `other` has zero length.
+    ///
+    /// See also [`Self::reject_from()`].
+    #[inline]
+    #[must_use]
+    pub fn project_onto(self, other: Self) -> Self {
+        let length_squared = other.length_squared();
+        if length_squared == 0.0 {
+            return Self::ZERO;
+        }
+        other * (self.dot(other) / length_squared)
+    }
+
+    /// Returns the component of `self` perpendicular to `other`.
+    ///
+    /// Together with [`Self::project_onto()`] this splits `self` in two, so
+    /// `v.project_onto(o) + v.reject_from(o) == v`.
+    #[inline]
+    #[must_use]
+    pub fn reject_from(self, other: Self) -> Self { self - self.project_onto(other) }
+
+    /// Returns `self` rotated by `angle` radians around `axis`, following the
+    /// right-hand rule, using Rodrigues' rotation formula.
+    ///
+    /// `axis` is normalized internally. If it has (near) zero length there is
+    /// no axis to turn about, and `self` is returned unchanged.
+    #[inline]
+    #[must_use]
+    pub fn rotate_around(self, axis: Self, angle: f64) -> Self {
+        let Some(k) = axis.try_normalize() else {
+            return self;
+        };
+        let (sin, cos) = math::sin_cos(angle);
+        self * cos + k.cross(self) * sin + k * (k.dot(self) * (1.0 - cos))
+    }
+
+    /// Returns `self` scaled down, if needed, so its length is no more than
+    /// `max`. The direction is preserved and a zero vector is returned
+    /// unchanged.
+    #[inline]
+    #[must_use]
+    pub fn clamp_length_max(self, max: f64) -> Self { self.clamp_length(0.0, max) }
+
+    /// Returns `self` rescaled, if needed, so its length lies within
+    /// `[min, max]`. The direction is preserved and a zero vector is returned
+    /// unchanged, since it has no direction to scale along.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `min` is greater than `max` when `debug_assertions` are
+    /// enabled.
+    #[inline]
+    #[must_use]
+    pub fn clamp_length(self, min: f64, max: f64) -> Self {
+        debug_assert!(min <= max, "clamp_length: min must not exceed max");
+        let length_squared = self.length_squared();
+        if length_squared == 0.0 {
+            self
+        } else if length_squared < min * min {
+            self * (min / math::sqrt(length_squared))
+        } else if length_squared > max * max {
+            self * (max / math::sqrt(length_squared))
+        } else {
+            self
+        }
+    }
+
     /// Performs a linear interpolation between `self` and `rhs` based on the
     /// value `s`.
     ///
@@ -276,6 +490,16 @@ impl PartialEq for Vec3 {
     }
 }
 
+impl ApproxEq for Vec3 {
+    #[inline]
+    fn approx_eq_epsilon(&self, other: &Self, epsilon: f64) -> bool {
+        is_equal_epsilon(self.x(), other.x(), epsilon)
+            && is_equal_epsilon(self.y(), other.y(), epsilon)
+            && is_equal_epsilon(self.z(), other.z(), epsilon)
+            && is_equal_epsilon(self.w(), other.w(), epsilon)
+    }
+}
+
 impl Mul for Vec3 {
     type Output = Self;
 
@@ -293,6 +517,8 @@ impl Mul<f64> for Vec3 {
     fn mul(self, rhs: f64) -> Self::Output { Self(self.x() * rhs, self.y() * rhs, self.z() * rhs) }
 }
 impl_op!(f64, Vec3, Mul, mul);
+impl_op_assign!(Vec3, Vec3, MulAssign, mul_assign, mul);
+impl_op_assign!(Vec3, f64, MulAssign, mul_assign, mul);
 
 impl Div for Vec3 {
     type Output = Self;
@@ -311,6 +537,8 @@ impl Div<f64> for Vec3 {
     fn div(self, rhs: f64) -> Self::Output { Self(self.x() / rhs, self.y() / rhs, self.z() / rhs) }
 }
 impl_op!(f64, Vec3, Div, div);
+impl_op_assign!(Vec3, Vec3, DivAssign, div_assign, div);
+impl_op_assign!(Vec3, f64, DivAssign, div_assign, div);
 
 impl Add for Vec3 {
     type Output = Self;
@@ -329,6 +557,8 @@ impl Add<f64> for Vec3 {
     fn add(self, rhs: f64) -> Self::Output { Self(self.x() + rhs, self.y() + rhs, self.z() + rhs) }
 }
 impl_op!(f64, Vec3, Add, add);
+impl_op_assign!(Vec3, Vec3, AddAssign, add_assign, add);
+impl_op_assign!(Vec3, f64, AddAssign, add_assign, add);
 
 impl Sub for Vec3 {
     type Output = Self;
@@ -347,6 +577,8 @@ impl Sub<f64> for Vec3 {
     fn sub(self, rhs: f64) -> Self::Output { Self(self.x() - rhs, self.y() - rhs, self.z() - rhs) }
 }
 impl_op!(f64, Vec3, Sub, sub);
+impl_op_assign!(Vec3, Vec3, SubAssign, sub_assign, sub);
+impl_op_assign!(Vec3, f64, SubAssign, sub_assign, sub);
 
 impl Neg for Vec3 {
     type Output = Self;
@@ -503,8 +735,24 @@ impl TryFrom<&Tuple4> for Vec3 {
 
 #[cfg(test)]
 mod tests {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
     use super::*;
 
+    #[test]
+    fn test_from_ints_in_const_context() {
+        static DIAGONALS: [Vec3; 4] = [
+            Vec3::from_ints(1, 1, 1),
+            Vec3::from_ints(-1, 1, 1),
+            Vec3::from_ints(1, -1, 1),
+            Vec3::from_ints(1, 1, -1),
+        ];
+
+        assert_eq!(DIAGONALS[0], Vec3::ONE);
+        assert_eq!(DIAGONALS[1], vector(-1, 1, 1));
+        assert_eq!(DIAGONALS[3].z(), -1.0);
+    }
+
     #[test]
     fn test_equality_is_exact() {
         let t1 = Vec3::new(1.0, 2.0, 3.0);
@@ -640,6 +888,12 @@ mod tests {
         assert_eq!(t2.cross(t1), Vec3::new(1.0, -2.0, 1.0));
     }
 
+    #[test]
+    fn test_scalar_triple_of_orthonormal_axes() {
+        assert_eq!(Vec3::scalar_triple(Vec3::X, Vec3::Y, Vec3::Z), 1.0);
+        assert_eq!(Vec3::scalar_triple(Vec3::Y, Vec3::X, Vec3::Z), -1.0);
+    }
+
     #[test]
     fn test_reflecting_at_45_degrees() {
         let v = vector(1.0, -1.0, 0.0);
@@ -657,4 +911,212 @@ mod tests {
         let reflected = v.reflect(normal);
         assert_eq!(reflected, vector(1.0, 0.0, 0.0));
     }
+
+    #[test]
+    fn test_angle_between_orthogonal_vectors() {
+        let angle = vector(1, 0, 0).angle_between(vector(0, 3, 0));
+        assert!(is_equal(angle, FRAC_PI_2));
+    }
+
+    #[test]
+    fn test_angle_between_identical_and_opposite_vectors() {
+        let v = vector(1, 2, 3);
+
+        assert_eq!(v.angle_between(v), 0.0);
+        assert!(is_equal(v.angle_between(-v), PI));
+    }
+
+    #[test]
+    fn test_angle_between_zero_length_vector_is_zero() {
+        assert_eq!(Vec3::ZERO.angle_between(vector(1, 0, 0)), 0.0);
+        assert_eq!(vector(1, 0, 0).angle_between(Vec3::ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_projecting_onto_and_rejecting_from_an_axis() {
+        let v = vector(2, 2, 0);
+        let x_axis = vector(1, 0, 0);
+
+        assert_eq!(v.project_onto(x_axis), vector(2, 0, 0));
+        assert_eq!(v.reject_from(x_axis), vector(0, 2, 0));
+        assert_eq!(v.project_onto(vector(5, 0, 0)), vector(2, 0, 0));
+    }
+
+    #[test]
+    fn test_projection_and_rejection_sum_to_the_original_vector() {
+        let v = vector(1, -2, 3);
+        let other = vector(4, 1, -2);
+
+        assert_eq!(v.project_onto(other) + v.reject_from(other), v);
+        assert!(is_equal(v.reject_from(other).dot(other), 0.0));
+    }
+
+    #[test]
+    fn test_projecting_onto_a_zero_vector_is_zero() {
+        let v = vector(1, 2, 3);
+
+        assert_eq!(v.project_onto(Vec3::ZERO), Vec3::ZERO);
+        assert_eq!(v.reject_from(Vec3::ZERO), v);
+    }
+
+    #[test]
+    fn test_refracting_with_equal_indices_passes_straight_through() {
+        let v = vector(1, -1, 0).normalize();
+        let normal = vector(0, 1, 0);
+
+        assert_eq!(v.refract(normal, 1.0), Some(v));
+    }
+
+    #[test]
+    fn test_refracting_into_a_denser_medium_bends_toward_the_normal() {
+        let v = vector(1, -1, 0).normalize();
+        let normal = vector(0, 1, 0);
+
+        let refracted = v.refract(normal, 1.0 / 1.5).unwrap();
+
+        assert!(refracted.is_normalized());
+        assert!(refracted.angle_between(-normal) < v.angle_between(-normal));
+    }
+
+    #[test]
+    fn test_refracting_beyond_the_critical_angle_is_total_internal_reflection() {
+        let v = vector(1.0, -0.1, 0.0).normalize();
+        let normal = vector(0, 1, 0);
+
+        assert_eq!(v.refract(normal, 1.5), None);
+    }
+
+    #[test]
+    fn test_abs_of_each_element() {
+        assert_eq!(vector(1, -2, 3).abs(), vector(1, 2, 3));
+    }
+
+    #[test]
+    fn test_min_and_max_pick_per_axis_extremes() {
+        let a = vector(1, -2, 5);
+        let b = vector(-1, 4, 3);
+
+        assert_eq!(a.min(b), vector(-1, -2, 3));
+        assert_eq!(a.max(b), vector(1, 4, 5));
+    }
+
+    #[test]
+    fn test_min_and_max_element() {
+        let v = vector(1, 5, 3);
+
+        assert_eq!(v.max_element(), 5.0);
+        assert_eq!(v.min_element(), 1.0);
+    }
+
+    #[test]
+    fn test_floor_and_ceil_of_each_element() {
+        let v = vector(1.7, -1.2, 2.5);
+
+        assert_eq!(v.floor(), vector(1, -2, 2));
+        assert_eq!(v.ceil(), vector(2, -1, 3));
+    }
+
+    #[test]
+    fn test_round_matches_f64_round() {
+        let v = vector(2.5, -2.5, 0.4);
+
+        assert_eq!(
+            v.round(),
+            vector(2.5_f64.round(), (-2.5_f64).round(), 0.4_f64.round())
+        );
+        assert_eq!(v.round(), vector(3, -3, 0));
+    }
+
+    #[test]
+    fn test_fract_keeps_the_sign_of_each_element() {
+        assert_eq!(vector(1.75, -1.25, 3.0).fract(), vector(0.75, -0.25, 0.0));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut v = vector(1, 2, 3);
+
+        v *= 0.5;
+        assert_eq!(v, vector(0.5, 1, 1.5));
+        v += vector(0.5, 0, -0.5);
+        assert_eq!(v, vector(1, 1, 1));
+        v -= &vector(1, 2, 3);
+        assert_eq!(v, vector(0, -1, -2));
+        v /= 2.0;
+        assert_eq!(v, vector(0, -0.5, -1));
+    }
+
+    #[test]
+    fn test_finite_and_nan_predicates() {
+        assert!(Vec3::NAN.is_nan());
+        assert!(!Vec3::INFINITY.is_finite());
+        assert!(!Vec3::INFINITY.is_nan());
+        assert!(vector(1, -2, 3).is_finite());
+        assert!(!vector(1, -2, 3).is_nan());
+        assert!(vector(1.0, f64::NAN, 3.0).is_nan());
+        assert!(!vector(1.0, 2.0, f64::NEG_INFINITY).is_finite());
+    }
+
+    #[test]
+    fn test_approx_eq_with_custom_epsilon() {
+        let a = vector(1.0, -2.0, 3.0);
+        let b = vector(1.0, -2.001, 3.0);
+
+        assert!(a.approx_eq_epsilon(&b, 1e-2));
+        assert!(!a.approx_eq_epsilon(&b, 1e-4));
+    }
+
+    #[test]
+    fn test_clamp_length_max_shortens_long_vectors() {
+        let v = vector(6, 8, 0);
+        let clamped = v.clamp_length_max(5.0);
+
+        assert!(is_equal(clamped.length(), 5.0));
+        assert_eq!(clamped.normalize(), v.normalize());
+        assert_eq!(vector(1, 2, 2).clamp_length_max(5.0), vector(1, 2, 2));
+    }
+
+    #[test]
+    fn test_clamp_length_grows_short_vectors() {
+        let v = vector(0, 2, 0);
+        let clamped = v.clamp_length(3.0, 5.0);
+
+        assert!(is_equal(clamped.length(), 3.0));
+        assert_eq!(clamped, vector(0, 3, 0));
+        assert_eq!(vector(0, 0, 4).clamp_length(3.0, 5.0), vector(0, 0, 4));
+    }
+
+    #[test]
+    fn test_clamp_length_leaves_zero_vector_unchanged() {
+        assert_eq!(Vec3::ZERO.clamp_length(3.0, 5.0), Vec3::ZERO);
+        assert_eq!(Vec3::ZERO.clamp_length_max(5.0), Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_rotating_around_an_arbitrary_axis() {
+        assert_eq!(
+            vector(1, 0, 0).rotate_around(vector(0, 0, 1), FRAC_PI_2),
+            vector(0, 1, 0)
+        );
+        assert_eq!(
+            vector(1, 0, 0).rotate_around(vector(0, 0, 5), FRAC_PI_2),
+            vector(0, 1, 0)
+        );
+        assert_eq!(
+            vector(1, 0, 0).rotate_around(vector(1, 1, 1), 2.0 * PI / 3.0),
+            vector(0, 1, 0)
+        );
+        assert_eq!(
+            vector(0, 0, 3).rotate_around(vector(0, 0, 1), 1.0),
+            vector(0, 0, 3)
+        );
+    }
+
+    #[test]
+    fn test_rotating_around_a_zero_axis_is_a_no_op() {
+        assert_eq!(
+            vector(1, 2, 3).rotate_around(Vec3::ZERO, FRAC_PI_2),
+            vector(1, 2, 3)
+        );
+    }
 }
@@ -0,0 +1,22 @@
+//! Scene descriptions that build a [`World`] and the [`Camera`] viewing it.
+#[cfg(feature = "yaml")] mod yaml;
+
+#[cfg(feature = "yaml")] pub use yaml::parse_yaml;
+
+use crate::graphics::camera::Camera;
+use crate::graphics::canvas::Canvas;
+use crate::world::World;
+
+/// A world together with the camera that renders it.
+#[derive(Debug)]
+pub struct Scene {
+    pub world:  World,
+    pub camera: Camera,
+}
+
+impl Scene {
+    /// Renders the world through the scene's camera.
+    #[inline]
+    #[must_use]
+    pub fn render(&self) -> Canvas { self.camera.render(&self.world) }
+}
@@ -0,0 +1,658 @@
+use std::collections::HashMap;
+
+use yaml_rust2::yaml::Hash;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::error::{ConfigError, IoError, TracerError};
+use crate::geometry::{Cube, Cylinder, Plane, Sphere};
+use crate::graphics::camera::Camera;
+use crate::primitives::{Color3, Inverse, Mat4, Point3, Vec3};
+use crate::scenes::Scene;
+use crate::shading::{Light, Material};
+use crate::world::World;
+
+/// The name reported as the file in configuration errors.
+const SOURCE: &str = "<yaml>";
+
+/// Parses a scene written in the book's YAML format.
+///
+/// The document is a list of directives. `add` places a `camera`, a `light`,
+/// or a `sphere`, `plane`, `cube` or `cylinder` in the scene; `define` names a
+/// material or transform for later reuse, optionally `extend`ing an earlier
+/// definition. Transforms are lists of `[translate, x, y, z]`,
+/// `[scale, x, y, z]`, `[rotate-x, radians]` (and `-y`, `-z`) and
+/// `[shear, xy, xz, yx, yz, zx, zy]` steps, applied in order, and may refer
+/// to defined transforms by name.
+///
+/// ```yaml
+/// - add: camera
+///   width: 100
+///   height: 50
+///   field-of-view: 1.047
+///   from: [0, 1.5, -5]
+///   to: [0, 1, 0]
+///   up: [0, 1, 0]
+///
+/// - add: light
+///   at: [-10, 10, -10]
+///   intensity: [1, 1, 1]
+///
+/// - add: sphere
+///   material:
+///     color: [0.1, 1, 0.5]
+///   transform:
+///     - [translate, -0.5, 1, 0.5]
+/// ```
+///
+/// # Errors
+///
+/// Returns [`IoError::ParseError`] if `source` is not valid YAML,
+/// [`ConfigError::MissingConfig`] if the scene has no camera or a directive
+/// lacks a required key, and [`ConfigError::InvalidConfig`] naming the key
+/// for unknown keys, unknown names and malformed values. Materials are
+/// checked as by the [`Material`] setters, and objects are added as by
+/// [`World::add_object`].
+pub fn parse_yaml(source: &str) -> crate::Result<Scene> {
+    let documents = YamlLoader::load_from_str(source).map_err(|err| IoError::ParseError {
+        filename:    SOURCE.to_string(),
+        line_number: Some(err.marker().line()),
+        reason:      err.info().to_string(),
+    })?;
+
+    let mut builder = SceneBuilder::default();
+    match documents.first() {
+        Some(Yaml::Array(directives)) => {
+            for directive in directives {
+                builder.directive(directive)?;
+            }
+        },
+        Some(other) => return Err(invalid("<root>", other, "a list of directives")),
+        None => {},
+    }
+
+    let camera = builder.camera.ok_or_else(|| missing("camera"))?;
+    Ok(Scene {
+        world: builder.world,
+        camera,
+    })
+}
+
+/// Accumulates the scene and the named definitions seen so far.
+#[derive(Default)]
+struct SceneBuilder {
+    defines: HashMap<String, Yaml>,
+    world:   World,
+    camera:  Option<Camera>,
+}
+
+impl SceneBuilder {
+    fn directive(&mut self, directive: &Yaml) -> crate::Result<()> {
+        let Yaml::Hash(hash) = directive else {
+            return Err(invalid("<directive>", directive, "a mapping"));
+        };
+
+        if let Some(kind) = hash.get(&Yaml::from_str("add")) {
+            let kind = string(kind, "add")?;
+            self.add(kind, hash)
+        } else if let Some(name) = hash.get(&Yaml::from_str("define")) {
+            let name = string(name, "define")?;
+            self.define(name, hash)
+        } else {
+            Err(missing("add"))
+        }
+    }
+
+    fn add(&mut self, kind: &str, hash: &Hash) -> crate::Result<()> {
+        match kind {
+            "camera" => {
+                let fields = fields(hash, &[
+                    "add",
+                    "width",
+                    "height",
+                    "field-of-view",
+                    "from",
+                    "to",
+                    "up",
+                ])?;
+                let (from, to, up) = (
+                    required(&fields, "from")?,
+                    required(&fields, "to")?,
+                    required(&fields, "up")?,
+                );
+                let transform =
+                    Mat4::view_transform(point(from, "from")?, point(to, "to")?, vector(up, "up")?);
+                // Looking at `from` itself, or along `up`, leaves no way to
+                // orient the camera.
+                if !transform.invertible() {
+                    let view = Yaml::Array(vec![from.clone(), to.clone(), up.clone()]);
+                    return Err(invalid(
+                        "transform",
+                        &view,
+                        "a camera looking away from `from` and across `up`",
+                    ));
+                }
+                let camera = Camera::new(
+                    size(required(&fields, "width")?, "width")?,
+                    size(required(&fields, "height")?, "height")?,
+                    number(required(&fields, "field-of-view")?, "field-of-view")?,
+                );
+                self.camera = Some(camera.with_transform(transform));
+            },
+            "light" => {
+                let fields = fields(hash, &["add", "at", "intensity"])?;
+                self.world.add_light(Light::point(
+                    point(required(&fields, "at")?, "at")?,
+                    color(required(&fields, "intensity")?, "intensity")?,
+                ));
+            },
+            "sphere" => {
+                let (material, transform) =
+                    self.surface(&fields(hash, &["add", "material", "transform"])?)?;
+                self.world
+                    .add_object(Sphere::new().with_material(material).with_transform(transform))?;
+            },
+            "plane" => {
+                let (material, transform) =
+                    self.surface(&fields(hash, &["add", "material", "transform"])?)?;
+                self.world
+                    .add_object(Plane::new().with_material(material).with_transform(transform))?;
+            },
+            "cube" => {
+                let (material, transform) =
+                    self.surface(&fields(hash, &["add", "material", "transform"])?)?;
+                self.world
+                    .add_object(Cube::new().with_material(material).with_transform(transform))?;
+            },
+            "cylinder" => {
+                let fields = fields(hash, &["add", "material", "transform", "min", "max", "closed"])?;
+                let (material, transform) = self.surface(&fields)?;
+                let minimum = fields
+                    .get("min")
+                    .map_or(Ok(f64::NEG_INFINITY), |v| number(v, "min"))?;
+                let maximum = fields
+                    .get("max")
+                    .map_or(Ok(f64::INFINITY), |v| number(v, "max"))?;
+                let closed = match fields.get("closed") {
+                    Some(Yaml::Boolean(closed)) => *closed,
+                    Some(other) => return Err(invalid("closed", other, "true or false")),
+                    None => false,
+                };
+                self.world.add_object(
+                    Cylinder::new()
+                        .with_material(material)
+                        .with_transform(transform)
+                        .with_limits(minimum, maximum)
+                        .with_closed(closed),
+                )?;
+            },
+            _ => {
+                return Err(invalid(
+                    "add",
+                    &Yaml::from_str(kind),
+                    "camera, light, sphere, plane, cube or cylinder",
+                ));
+            },
+        }
+        Ok(())
+    }
+
+    fn define(&mut self, name: &str, hash: &Hash) -> crate::Result<()> {
+        let fields = fields(hash, &["define", "extend", "value"])?;
+        let mut value = required(&fields, "value")?.clone();
+
+        // Extending layers the new keys over a copy of the base definition.
+        if let Some(&base) = fields.get("extend") {
+            let base = self.lookup(string(base, "extend")?, "extend")?;
+            let (Yaml::Hash(base), Yaml::Hash(overrides)) = (base, &value) else {
+                return Err(invalid("extend", &value, "a mapping extending a defined mapping"));
+            };
+            let mut merged = base.clone();
+            merged.extend(overrides.iter().map(|(k, v)| (k.clone(), v.clone())));
+            value = Yaml::Hash(merged);
+        }
+
+        self.defines.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    fn surface(&self, fields: &HashMap<&str, &Yaml>) -> crate::Result<(Material, Mat4)> {
+        let material = fields
+            .get("material")
+            .map_or(Ok(Material::new()), |v| self.material(v))?;
+        let transform = fields
+            .get("transform")
+            .map_or(Ok(Mat4::IDENTITY), |v| self.transform(v))?;
+        Ok((material, transform))
+    }
+
+    fn material(&self, value: &Yaml) -> crate::Result<Material> {
+        self.resolve_material(value, &mut Vec::new())
+    }
+
+    /// Builds the material `value` describes. `resolving` holds the defined
+    /// names being expanded, to catch definitions that refer to themselves.
+    fn resolve_material<'a>(
+        &'a self,
+        value: &'a Yaml,
+        resolving: &mut Vec<&'a str>,
+    ) -> crate::Result<Material> {
+        let hash = match value {
+            Yaml::String(name) => {
+                let definition = self.enter(name, "material", resolving)?;
+                let material = self.resolve_material(definition, resolving);
+                resolving.pop();
+                return material;
+            },
+            Yaml::Hash(hash) => hash,
+            other => return Err(invalid("material", other, "a mapping or a defined name")),
+        };
+
+        let fields = fields(hash, &[
+            "color",
+            "ambient",
+            "diffuse",
+            "specular",
+            "shininess",
+            "reflective",
+            "transparency",
+            "refractive-index",
+        ])?;
+
+        let mut material = Material::new();
+        for (&key, &value) in &fields {
+            material = match key {
+                "color" => material.with_color(color(value, key)?),
+                "ambient" => material.with_ambient(number(value, key)?)?,
+                "diffuse" => material.with_diffuse(number(value, key)?)?,
+                "specular" => material.with_specular(number(value, key)?)?,
+                "shininess" => material.with_shininess(number(value, key)?)?,
+                "reflective" => material.with_reflective(number(value, key)?)?,
+                "transparency" => material.with_transparency(number(value, key)?)?,
+                _ => material.with_refractive_index(number(value, key)?),
+            };
+        }
+        Ok(material)
+    }
+
+    /// Builds the transform `value` describes, rejecting one that cannot be
+    /// inverted, e.g. a scale by zero, before any shape is built with it.
+    fn transform(&self, value: &Yaml) -> crate::Result<Mat4> {
+        let transform = self.compose(value, &mut Vec::new())?;
+        if !transform.invertible() {
+            return Err(invalid("transform", value, "an invertible transform"));
+        }
+        Ok(transform)
+    }
+
+    /// Multiplies out the steps of `value`. `resolving` holds the defined
+    /// names being expanded, to catch definitions that refer to themselves.
+    fn compose<'a>(&'a self, value: &'a Yaml, resolving: &mut Vec<&'a str>) -> crate::Result<Mat4> {
+        let steps = match value {
+            Yaml::String(name) => {
+                let definition = self.enter(name, "transform", resolving)?;
+                let transform = self.compose(definition, resolving);
+                resolving.pop();
+                return transform;
+            },
+            Yaml::Array(steps) => steps,
+            other => return Err(invalid("transform", other, "a list of steps or a defined name")),
+        };
+
+        // Each step applies after the ones before it, so it multiplies on the
+        // left.
+        steps.iter().try_fold(Mat4::IDENTITY, |acc, step| {
+            let step = match step {
+                Yaml::String(_) => self.compose(step, resolving)?,
+                Yaml::Array(parts) => transform_step(parts)?,
+                other => return Err(invalid("transform", other, "a step or a defined name")),
+            };
+            Ok(step * acc)
+        })
+    }
+
+    /// Looks up `name` and marks it as being expanded, failing if it
+    /// already is, which means its definition refers back to itself.
+    fn enter<'a>(
+        &'a self,
+        name: &'a str,
+        key: &str,
+        resolving: &mut Vec<&'a str>,
+    ) -> crate::Result<&'a Yaml> {
+        if resolving.contains(&name) {
+            return Err(invalid(
+                key,
+                &Yaml::from_str(name),
+                "a definition that does not refer to itself",
+            ));
+        }
+        let definition = self.lookup(name, key)?;
+        resolving.push(name);
+        Ok(definition)
+    }
+
+    fn lookup(&self, name: &str, key: &str) -> crate::Result<&Yaml> {
+        self.defines
+            .get(name)
+            .ok_or_else(|| invalid(key, &Yaml::from_str(name), "a defined name"))
+    }
+}
+
+/// Returns the matrix for a single `[operation, arguments...]` step.
+fn transform_step(parts: &[Yaml]) -> crate::Result<Mat4> {
+    let Some((Yaml::String(op), args)) = parts.split_first() else {
+        return Err(invalid(
+            "transform",
+            &Yaml::Array(parts.to_vec()),
+            "[operation, arguments...]",
+        ));
+    };
+    let args = args
+        .iter()
+        .map(|arg| number(arg, op))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    match (op.as_str(), args.as_slice()) {
+        ("translate", &[x, y, z]) => Ok(Mat4::translation(x, y, z)),
+        ("scale", &[x, y, z]) => Ok(Mat4::scaling(x, y, z)),
+        ("rotate-x", &[r]) => Ok(Mat4::rotation_x(r)),
+        ("rotate-y", &[r]) => Ok(Mat4::rotation_y(r)),
+        ("rotate-z", &[r]) => Ok(Mat4::rotation_z(r)),
+        ("shear", &[xy, xz, yx, yz, zx, zy]) => Ok(Mat4::shearing(xy, xz, yx, yz, zx, zy)),
+        ("translate" | "scale" | "rotate-x" | "rotate-y" | "rotate-z" | "shear", _) => Err(invalid(
+            op,
+            &Yaml::Array(parts[1..].to_vec()),
+            "the operation's arguments",
+        )),
+        _ => Err(invalid(
+            "transform",
+            &Yaml::from_str(op),
+            "translate, scale, rotate-x, rotate-y, rotate-z or shear",
+        )),
+    }
+}
+
+/// Collects the keys of `hash`, rejecting any not listed in `allowed`.
+fn fields<'a>(hash: &'a Hash, allowed: &[&str]) -> crate::Result<HashMap<&'a str, &'a Yaml>> {
+    hash.iter()
+        .map(|(key, value)| match key.as_str() {
+            Some(key) if allowed.contains(&key) => Ok((key, value)),
+            _ => Err(invalid(
+                &describe(key),
+                value,
+                &format!("one of {}", allowed.join(", ")),
+            )),
+        })
+        .collect()
+}
+
+fn required<'a>(fields: &HashMap<&str, &'a Yaml>, key: &str) -> crate::Result<&'a Yaml> {
+    fields.get(key).copied().ok_or_else(|| missing(key))
+}
+
+fn string<'a>(value: &'a Yaml, key: &str) -> crate::Result<&'a str> {
+    value.as_str().ok_or_else(|| invalid(key, value, "a name"))
+}
+
+fn number(value: &Yaml, key: &str) -> crate::Result<f64> {
+    match value {
+        Yaml::Integer(n) => Ok(*n as f64),
+        _ => value.as_f64().ok_or_else(|| invalid(key, value, "a number")),
+    }
+}
+
+fn size(value: &Yaml, key: &str) -> crate::Result<usize> {
+    match value {
+        Yaml::Integer(n) if *n > 0 => Ok(*n as usize),
+        _ => Err(invalid(key, value, "a positive integer")),
+    }
+}
+
+fn triple(value: &Yaml, key: &str) -> crate::Result<[f64; 3]> {
+    match value.as_vec().map(Vec::as_slice) {
+        Some([x, y, z]) => Ok([number(x, key)?, number(y, key)?, number(z, key)?]),
+        _ => Err(invalid(key, value, "a list of three numbers")),
+    }
+}
+
+fn point(value: &Yaml, key: &str) -> crate::Result<Point3> {
+    let [x, y, z] = triple(value, key)?;
+    Ok(Point3::new(x, y, z))
+}
+
+fn vector(value: &Yaml, key: &str) -> crate::Result<Vec3> {
+    let [x, y, z] = triple(value, key)?;
+    Ok(Vec3::new(x, y, z))
+}
+
+fn color(value: &Yaml, key: &str) -> crate::Result<Color3> {
+    let [r, g, b] = triple(value, key)?;
+    Ok(Color3::new(r, g, b))
+}
+
+fn missing(key: &str) -> TracerError {
+    ConfigError::MissingConfig {
+        key:  key.to_string(),
+        file: SOURCE.to_string(),
+    }
+    .into()
+}
+
+fn invalid(key: &str, value: &Yaml, expected: &str) -> TracerError {
+    ConfigError::InvalidConfig {
+        key:      key.to_string(),
+        value:    describe(value),
+        expected: expected.to_string(),
+    }
+    .into()
+}
+
+/// Renders `value` compactly for error messages.
+fn describe(value: &Yaml) -> String {
+    match value {
+        Yaml::Real(s) | Yaml::String(s) => s.clone(),
+        Yaml::Integer(n) => n.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Array(items) => {
+            let items: Vec<_> = items.iter().map(describe).collect();
+            format!("[{}]", items.join(", "))
+        },
+        Yaml::Hash(hash) => {
+            let entries: Vec<_> = hash
+                .iter()
+                .map(|(k, v)| format!("{}: {}", describe(k), describe(v)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        },
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => "null".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{color as rgb, point as pt};
+
+    const MINIMAL: &str = "
+- add: camera
+  width: 11
+  height: 11
+  field-of-view: 1.5708
+  from: [0, 0, -5]
+  to: [0, 0, 0]
+  up: [0, 1, 0]
+
+- add: light
+  at: [-10, 10, -10]
+  intensity: [1, 1, 1]
+
+- add: sphere
+  material:
+    color: [1, 0.2, 1]
+";
+
+    fn config_error(result: crate::Result<Scene>) -> ConfigError {
+        match result.unwrap_err() {
+            TracerError::Config(err) => err,
+            err => panic!("expected a config error, got {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parsing_a_minimal_scene() {
+        let scene = parse_yaml(MINIMAL).unwrap();
+
+        assert_eq!(scene.camera.hsize(), 11);
+        assert_eq!(scene.camera.vsize(), 11);
+        assert_eq!(scene.world.lights(), [Light::point(
+            pt(-10, 10, -10),
+            rgb(1, 1, 1)
+        )]);
+        assert_eq!(scene.world.objects().len(), 1);
+        assert_eq!(scene.world.objects()[0].material().color, rgb(1.0, 0.2, 1.0));
+
+        let image = scene.render();
+        assert_ne!(image.pixel_at(5, 5), Color3::BLACK);
+    }
+
+    #[test]
+    fn test_defines_can_extend_materials_and_compose_transforms() {
+        let scene = parse_yaml(
+            "
+- add: camera
+  width: 10
+  height: 10
+  field-of-view: 1
+  from: [0, 0, -5]
+  to: [0, 0, 0]
+  up: [0, 1, 0]
+
+- define: white-material
+  value:
+    color: [1, 1, 1]
+    diffuse: 0.7
+    reflective: 0.1
+
+- define: blue-material
+  extend: white-material
+  value:
+    color: [0.537, 0.831, 0.914]
+
+- define: standard-transform
+  value:
+    - [translate, 1, -1, 1]
+    - [scale, 0.5, 0.5, 0.5]
+
+- add: cube
+  material: blue-material
+  transform:
+    - standard-transform
+    - [scale, 2, 2, 2]
+",
+        )
+        .unwrap();
+
+        let object = &scene.world.objects()[0];
+        let material = object.material();
+        assert_eq!(material.color, rgb(0.537, 0.831, 0.914));
+        assert_eq!(material.diffuse, 0.7);
+        assert_eq!(material.reflective, 0.1);
+        assert_eq!(
+            object.transform(),
+            Mat4::scaling(2.0, 2.0, 2.0) * Mat4::scaling(0.5, 0.5, 0.5) * Mat4::translation(1.0, -1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported() {
+        let source = MINIMAL.replace("  intensity:", "  brightness:");
+        let ConfigError::InvalidConfig { key, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!(key, "brightness");
+    }
+
+    #[test]
+    fn test_unknown_shapes_are_reported() {
+        let source = MINIMAL.replace("add: sphere", "add: torus");
+        let ConfigError::InvalidConfig { key, value, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!((key.as_str(), value.as_str()), ("add", "torus"));
+    }
+
+    #[test]
+    fn test_non_invertible_transforms_are_reported() {
+        let source = format!("{MINIMAL}  transform:\n    - [scale, 0, 1, 1]\n");
+        let ConfigError::InvalidConfig { key, value, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!(
+            (key.as_str(), value.as_str()),
+            ("transform", "[[scale, 0, 1, 1]]")
+        );
+
+        let source = MINIMAL.replace("to: [0, 0, 0]", "to: [0, 0, -5]");
+        let ConfigError::InvalidConfig { key, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!(key, "transform");
+    }
+
+    #[test]
+    fn test_self_referential_defines_are_reported() {
+        let source = MINIMAL
+            .replace("  material:\n    color: [1, 0.2, 1]\n", "  material: looped\n")
+            .replace(
+                "- add: sphere",
+                "- define: looped\n  value: looped\n\n- add: sphere",
+            );
+        let ConfigError::InvalidConfig { key, value, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!((key.as_str(), value.as_str()), ("material", "looped"));
+    }
+
+    #[test]
+    fn test_cyclic_transform_defines_are_reported() {
+        let cyclic = "
+- define: first
+  value:
+    - [scale, 2, 2, 2]
+    - second
+
+- define: second
+  value:
+    - [translate, 1, 0, 0]
+    - first
+";
+        let source = format!("{cyclic}{MINIMAL}  transform: first\n");
+        let ConfigError::InvalidConfig { key, value, .. } = config_error(parse_yaml(&source)) else {
+            panic!("expected an invalid config error")
+        };
+        assert_eq!((key.as_str(), value.as_str()), ("transform", "first"));
+
+        // Using the same definition twice is not a cycle
+        let repeated = "\n- define: double\n  value:\n    - [scale, 2, 2, 2]\n";
+        let source = format!("{repeated}{MINIMAL}  transform: [double, double]\n");
+        let scene = parse_yaml(&source).unwrap();
+        assert_eq!(scene.world.objects()[0].transform(), Mat4::scaling(4.0, 4.0, 4.0));
+    }
+
+    #[test]
+    fn test_a_scene_needs_a_camera() {
+        let source = "- add: light\n  at: [0, 0, 0]\n  intensity: [1, 1, 1]\n";
+        assert_eq!(config_error(parse_yaml(source)), ConfigError::MissingConfig {
+            key:  "camera".to_string(),
+            file: SOURCE.to_string(),
+        });
+    }
+
+    #[test]
+    fn test_malformed_yaml_is_a_parse_error() {
+        let err = parse_yaml("- add: [camera\n").unwrap_err();
+        assert!(
+            matches!(err, TracerError::Io(IoError::ParseError { .. })),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::Pattern;
+use crate::shading::pattern::pattern_at_child;
+
+/// The average of two patterns evaluated at the same point.
+///
+/// Blending two perpendicular stripe patterns produces a plaid.
+#[derive(Debug)]
+pub struct BlendedPattern {
+    a:         Box<dyn Pattern>,
+    b:         Box<dyn Pattern>,
+    transform: Mat4,
+    inverse:   Mat4,
+}
+
+impl BlendedPattern {
+    /// Creates a pattern averaging `a` and `b`.
+    #[inline]
+    #[must_use]
+    pub fn new(a: impl Pattern + 'static, b: impl Pattern + 'static) -> Self {
+        Self {
+            a:         Box::new(a),
+            b:         Box::new(b),
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the first blended pattern.
+    #[inline]
+    pub fn a(&self) -> &dyn Pattern { self.a.as_ref() }
+
+    /// Returns the second blended pattern.
+    #[inline]
+    pub fn b(&self) -> &dyn Pattern { self.b.as_ref() }
+}
+
+impl Pattern for BlendedPattern {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        (pattern_at_child(self.a.as_ref(), point) + pattern_at_child(self.b.as_ref(), point)) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use crate::prelude::{color, point};
+    use crate::shading::StripePattern;
+
+    const WHITE: Color3 = Color3::WHITE;
+    const BLACK: Color3 = Color3::BLACK;
+
+    fn plaid() -> BlendedPattern {
+        let green = color(0, 1, 0);
+        BlendedPattern::new(
+            StripePattern::new(WHITE, BLACK),
+            StripePattern::new(WHITE, green).with_transform(Mat4::rotation_y(FRAC_PI_2)),
+        )
+    }
+
+    #[test]
+    fn test_blended_pattern_averages_matching_stripes() {
+        assert_eq!(plaid().pattern_at(point(0.5, 0.0, -0.5)), WHITE);
+    }
+
+    #[test]
+    fn test_blended_pattern_averages_overlapping_stripes() {
+        let pattern = plaid();
+        assert_eq!(pattern.pattern_at(point(1.5, 0.0, -0.5)), color(0.5, 0.5, 0.5));
+        assert_eq!(pattern.pattern_at(point(0.5, 0.0, 0.5)), color(0.5, 1.0, 0.5));
+        assert_eq!(pattern.pattern_at(point(1.5, 0.0, 0.5)), color(0.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_blended_pattern_is_constant_in_y() {
+        let pattern = plaid();
+        assert_eq!(
+            pattern.pattern_at(point(1.5, 3.0, 0.5)),
+            pattern.pattern_at(point(1.5, -2.0, 0.5))
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::math;
+use crate::primitives::{Color3, Point3, Vec3};
+
+/// A light source with no size, radiating equally in every direction from a
+/// single point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointLight {
+    pub position:  Point3,
+    pub intensity: Color3,
+}
+
+impl PointLight {
+    /// Creates a new point light at `position` emitting `intensity`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(position: Point3, intensity: Color3) -> Self { Self { position, intensity } }
+}
+
+/// A light radiating from a single point in a cone around `direction`.
+///
+/// Points within `inner_angle` of the axis receive the full intensity, which
+/// fades smoothly to nothing at `outer_angle`. Both angles are measured in
+/// radians from the axis to the edge of the cone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpotLight {
+    pub position:    Point3,
+    pub direction:   Vec3,
+    pub inner_angle: f64,
+    pub outer_angle: f64,
+    pub intensity:   Color3,
+}
+
+impl SpotLight {
+    /// Creates a new spotlight at `position` shining along `direction`.
+    ///
+    /// `direction` is normalized, so it may have any non-zero length.
+    ///
+    /// # Panics
+    ///
+    /// Will panic unless `0 <= inner_angle <= outer_angle`.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        position: Point3,
+        direction: Vec3,
+        inner_angle: f64,
+        outer_angle: f64,
+        intensity: Color3,
+    ) -> Self {
+        assert!(
+            0.0 <= inner_angle && inner_angle <= outer_angle,
+            "spotlight angles must satisfy 0 <= inner <= outer, got {inner_angle} and {outer_angle}"
+        );
+        Self {
+            position,
+            direction: direction.normalize(),
+            inner_angle,
+            outer_angle,
+            intensity,
+        }
+    }
+
+    /// Returns the fraction of the intensity, in `[0, 1]`, that reaches
+    /// `point`. Coinciding cones give a hard edge instead of a falloff.
+    #[must_use]
+    pub fn attenuation(&self, point: Point3) -> f64 {
+        let angle = math::acos_approx((point - self.position).normalize().dot(self.direction));
+        if self.inner_angle == self.outer_angle {
+            return if angle <= self.outer_angle { 1.0 } else { 0.0 };
+        }
+        1.0 - math::smoothstep(self.inner_angle, self.outer_angle, angle)
+    }
+}
+
+/// Any light source the renderer knows how to shade with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Light {
+    Point(PointLight),
+    Spot(SpotLight),
+}
+
+impl Light {
+    /// Creates a point light at `position` emitting `intensity`.
+    #[inline]
+    #[must_use]
+    pub const fn point(position: Point3, intensity: Color3) -> Self {
+        Self::Point(PointLight::new(position, intensity))
+    }
+
+    /// Returns where the light is emitted from.
+    #[inline]
+    pub const fn position(&self) -> Point3 {
+        match self {
+            Self::Point(light) => light.position,
+            Self::Spot(light) => light.position,
+        }
+    }
+
+    /// Returns the color and brightness of the light.
+    #[inline]
+    pub const fn intensity(&self) -> Color3 {
+        match self {
+            Self::Point(light) => light.intensity,
+            Self::Spot(light) => light.intensity,
+        }
+    }
+
+    /// Returns the fraction of the intensity, in `[0, 1]`, that reaches
+    /// `point`. Point lights reach everywhere.
+    #[must_use]
+    pub fn attenuation(&self, point: Point3) -> f64 {
+        match self {
+            Self::Point(_) => 1.0,
+            Self::Spot(light) => light.attenuation(point),
+        }
+    }
+}
+
+impl From<PointLight> for Light {
+    #[inline]
+    fn from(light: PointLight) -> Self { Self::Point(light) }
+}
+
+impl From<SpotLight> for Light {
+    #[inline]
+    fn from(light: SpotLight) -> Self { Self::Spot(light) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{color, point, vector};
+
+    #[test]
+    fn test_point_light_has_a_position_and_intensity() {
+        let intensity = color(1, 1, 1);
+        let position = point(0, 0, 0);
+        let light = PointLight::new(position, intensity);
+        assert_eq!(light.position, position);
+        assert_eq!(light.intensity, intensity);
+    }
+
+    #[test]
+    fn test_spotlight_is_fully_lit_on_its_axis() {
+        let light = SpotLight::new(point(0, 0, 0), vector(0, 0, 2), 0.2, 0.4, color(1, 1, 1));
+        assert_eq!(light.direction, vector(0, 0, 1));
+        assert_eq!(light.attenuation(point(0, 0, 5)), 1.0);
+    }
+
+    #[test]
+    fn test_spotlight_is_dark_outside_its_outer_cone() {
+        let light = SpotLight::new(point(0, 0, 0), vector(0, 0, 1), 0.2, 0.4, color(1, 1, 1));
+        assert_eq!(light.attenuation(point(0.0, math::tan(0.41), 1.0)), 0.0);
+        assert_eq!(light.attenuation(point(0, 0, -1)), 0.0);
+    }
+
+    #[test]
+    fn test_spotlight_falls_off_monotonically_between_its_cones() {
+        let light = SpotLight::new(point(0, 0, 0), vector(0, 0, 1), 0.2, 0.4, color(1, 1, 1));
+        let samples: Vec<f64> = (0..=20)
+            .map(|i| light.attenuation(point(0.0, math::tan(0.2 + 0.01 * f64::from(i)), 1.0)))
+            .collect();
+
+        assert!((samples[0] - 1.0).abs() < 1e-9);
+        assert!(samples[20].abs() < 1e-9);
+        assert!(samples.windows(2).all(|pair| pair[1] <= pair[0]), "{samples:?}");
+        assert!(samples[10] > 0.0 && samples[10] < 1.0);
+    }
+
+    #[test]
+    fn test_spotlight_with_equal_cones_has_a_hard_edge() {
+        let light = SpotLight::new(point(0, 0, 0), vector(0, 0, 1), 0.3, 0.3, color(1, 1, 1));
+        assert_eq!(light.attenuation(point(0, 0, 1)), 1.0);
+        assert_eq!(light.attenuation(point(0.0, math::tan(0.29), 1.0)), 1.0);
+        assert_eq!(light.attenuation(point(0.0, math::tan(0.31), 1.0)), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "0 <= inner <= outer")]
+    fn test_spotlight_rejects_an_inner_cone_wider_than_the_outer() {
+        let _ = SpotLight::new(point(0, 0, 0), vector(0, 0, 1), 0.4, 0.2, color(1, 1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "0 <= inner <= outer")]
+    fn test_spotlight_rejects_a_negative_inner_angle() {
+        let _ = SpotLight::new(point(0, 0, 0), vector(0, 0, 1), -0.1, 0.2, color(1, 1, 1));
+    }
+
+    #[test]
+    fn test_light_enum_forwards_to_the_underlying_light() {
+        let spot = SpotLight::new(point(1, 2, 3), vector(0, -1, 0), 0.2, 0.4, color(0.5, 0.5, 0.5));
+        let light = Light::from(spot);
+        assert_eq!(light.position(), point(1, 2, 3));
+        assert_eq!(light.intensity(), color(0.5, 0.5, 0.5));
+        assert_eq!(
+            Light::point(point(0, 0, 0), color(1, 1, 1)).attenuation(point(5, 5, 5)),
+            1.0
+        );
+    }
+}
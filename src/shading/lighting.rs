@@ -0,0 +1,204 @@
+use crate::math;
+use crate::primitives::{Color3, Point3, Vec3};
+use crate::shading::{Light, Material};
+
+/// The separate terms of the Phong reflection model for one light, as
+/// returned by [`lighting_components`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightComponents {
+    pub ambient:  Color3,
+    pub diffuse:  Color3,
+    pub specular: Color3,
+}
+
+impl LightComponents {
+    /// Returns the sum of the three terms, as [`lighting`] does.
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> Color3 { self.ambient + self.diffuse + self.specular }
+}
+
+/// Shades `point` with the Phong reflection model, summing the ambient,
+/// diffuse and specular contributions of `light`.
+///
+/// `eyev` and `normalv` must be unit vectors. A point `in_shadow`, or outside
+/// a spotlight's cone, receives ambient light only.
+pub fn lighting(
+    material: &Material,
+    light: &Light,
+    point: Point3,
+    eyev: Vec3,
+    normalv: Vec3,
+    in_shadow: bool,
+) -> Color3 {
+    lighting_components(material, light, point, eyev, normalv, in_shadow).total()
+}
+
+/// Like [`lighting`], but returns the ambient, diffuse and specular terms
+/// separately, e.g. to render each as its own pass.
+///
+/// The diffuse and specular terms already include the light's attenuation,
+/// and are black wherever `lighting` gives ambient light only.
+pub fn lighting_components(
+    material: &Material,
+    light: &Light,
+    point: Point3,
+    eyev: Vec3,
+    normalv: Vec3,
+    in_shadow: bool,
+) -> LightComponents {
+    let effective_color = material.color * light.intensity();
+    let ambient_only = LightComponents {
+        ambient:  effective_color * material.ambient,
+        diffuse:  Color3::BLACK,
+        specular: Color3::BLACK,
+    };
+    let attenuation = light.attenuation(point);
+    if in_shadow || attenuation == 0.0 {
+        return ambient_only;
+    }
+
+    // A negative cosine means the light is on the other side of the surface.
+    let lightv = (light.position() - point).normalize();
+    let light_dot_normal = lightv.dot(normalv);
+    if light_dot_normal < 0.0 {
+        return ambient_only;
+    }
+
+    let diffuse = effective_color * material.diffuse * light_dot_normal;
+
+    // A negative cosine means the light reflects away from the eye.
+    let reflectv = (-lightv).reflect(normalv);
+    let reflect_dot_eye = reflectv.dot(eyev);
+    let specular = if reflect_dot_eye <= 0.0 {
+        Color3::BLACK
+    } else {
+        light.intensity() * material.specular * math::powf(reflect_dot_eye, material.shininess)
+    };
+
+    LightComponents {
+        diffuse: diffuse * attenuation,
+        specular: specular * attenuation,
+        ..ambient_only
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+    use crate::prelude::{color, point, vector};
+    use crate::shading::SpotLight;
+
+    fn setup() -> (Material, Point3) { (Material::default(), point(0, 0, 0)) }
+
+    #[test]
+    fn test_eye_between_light_and_surface() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 0, -10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, false);
+        assert_eq!(result, color(1.9, 1.9, 1.9));
+    }
+
+    #[test]
+    fn test_eye_between_light_and_surface_eye_offset_45_degrees() {
+        let (m, position) = setup();
+        let eyev = vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 0, -10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, false);
+        assert_eq!(result, color(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_eye_opposite_surface_light_offset_45_degrees() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 10, -10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, false);
+        assert_eq!(result, color(0.7364, 0.7364, 0.7364));
+    }
+
+    #[test]
+    fn test_eye_in_the_path_of_the_reflection_vector() {
+        let (m, position) = setup();
+        let eyev = vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 10, -10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, false);
+        assert_eq!(result, color(1.6364, 1.6364, 1.6364));
+    }
+
+    #[test]
+    fn test_light_behind_the_surface() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 0, 10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, false);
+        assert_eq!(result, color(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_surface_in_shadow() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 0, -10), color(1, 1, 1));
+        let result = lighting(&m, &light, position, eyev, normalv, true);
+        assert_eq!(result, color(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_spotlight_on_axis_matches_a_point_light() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let spot = SpotLight::new(point(0, 0, -10), vector(0, 0, 1), 0.2, 0.4, color(1, 1, 1));
+        let result = lighting(&m, &spot.into(), position, eyev, normalv, false);
+        assert_eq!(result, color(1.9, 1.9, 1.9));
+    }
+
+    #[test]
+    fn test_spotlight_outside_its_cone_gives_ambient_only() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let spot = SpotLight::new(point(0, 0, -10), vector(0, 1, 1), 0.2, 0.4, color(1, 1, 1));
+        let result = lighting(&m, &spot.into(), position, eyev, normalv, false);
+        assert_eq!(result, color(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_lighting_components_sum_to_lighting() {
+        let (m, position) = setup();
+        let eyev = vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let normalv = vector(0, 0, -1);
+        let spot = SpotLight::new(point(0, 10, -10), vector(0, -1, 1), 0.1, 0.3, color(1, 0.5, 1));
+        for light in [Light::point(point(0, 10, -10), color(1, 1, 1)), spot.into()] {
+            let parts = lighting_components(&m, &light, position, eyev, normalv, false);
+            assert_ne!(parts.diffuse, Color3::BLACK);
+            assert_ne!(parts.specular, Color3::BLACK);
+            assert_eq!(
+                parts.ambient + parts.diffuse + parts.specular,
+                lighting(&m, &light, position, eyev, normalv, false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_lighting_components_of_a_surface_in_shadow() {
+        let (m, position) = setup();
+        let eyev = vector(0, 0, -1);
+        let normalv = vector(0, 0, -1);
+        let light = Light::point(point(0, 0, -10), color(1, 1, 1));
+        let parts = lighting_components(&m, &light, position, eyev, normalv, true);
+        assert_eq!(parts.ambient, color(0.1, 0.1, 0.1));
+        assert_eq!(parts.diffuse, Color3::BLACK);
+        assert_eq!(parts.specular, Color3::BLACK);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::error::ShadingError;
+use crate::math;
+use crate::primitives::Color3;
+
+/// Surface properties for the Phong reflection model.
+///
+/// Fields are public for direct tweaking; the `with_*` setters check that
+/// each reflectance coefficient lies within `[0, 1]` and that `shininess` is
+/// positive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    pub color:            Color3,
+    pub ambient:          f64,
+    pub diffuse:          f64,
+    pub specular:         f64,
+    pub shininess:        f64,
+    pub reflective:       f64,
+    pub transparency:     f64,
+    pub refractive_index: f64,
+}
+
+impl Material {
+    /// Creates the default material: white, mostly diffuse, with a tight
+    /// specular highlight.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            color:            Color3::WHITE,
+            ambient:          0.1,
+            diffuse:          0.9,
+            specular:         0.9,
+            shininess:        200.0,
+            reflective:       0.0,
+            transparency:     0.0,
+            refractive_index: 1.0,
+        }
+    }
+
+    /// Creates a flat, chalky surface of `color` that scatters light evenly
+    /// with no highlight or reflection.
+    #[inline]
+    #[must_use]
+    pub const fn matte(color: Color3) -> Self {
+        Self {
+            color,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.0,
+            shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+        }
+    }
+
+    /// Creates a polished metal tinted `color`.
+    ///
+    /// `fuzz`, clamped to `[0, 1]`, roughens the surface from a mirror to
+    /// brushed metal by widening the highlight and weakening the reflection.
+    #[must_use]
+    pub const fn metal(color: Color3, fuzz: f64) -> Self {
+        let fuzz = math::clamp(fuzz, 0.0, 1.0);
+        Self {
+            color,
+            ambient: 0.1,
+            diffuse: 0.3,
+            specular: 1.0,
+            shininess: math::lerp(300.0, 20.0, fuzz),
+            reflective: math::lerp(0.9, 0.6, fuzz),
+            transparency: 0.0,
+            refractive_index: 1.0,
+        }
+    }
+
+    /// Creates a clear, glass-like material that bends light by
+    /// `refractive_index`, e.g. `1.5` for glass or `1.33` for water.
+    ///
+    /// It both reflects and transmits, so [`World::shade_hit`] splits light
+    /// between the two by the Fresnel effect.
+    ///
+    /// [`World::shade_hit`]: crate::world::World::shade_hit
+    #[inline]
+    #[must_use]
+    pub const fn dielectric(refractive_index: f64) -> Self {
+        Self {
+            color: Color3::splat(0.95),
+            ambient: 0.0,
+            diffuse: 0.1,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 1.0,
+            transparency: 1.0,
+            refractive_index,
+        }
+    }
+
+    /// Returns the material with its surface color set to `color`.
+    #[inline]
+    #[must_use]
+    pub const fn with_color(mut self, color: Color3) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns the material with its ambient reflectance set to `ambient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `ambient` is outside
+    /// `[0, 1]`.
+    pub fn with_ambient(mut self, ambient: f64) -> crate::Result<Self> {
+        self.ambient = unit("ambient", ambient)?;
+        Ok(self)
+    }
+
+    /// Returns the material with its diffuse reflectance set to `diffuse`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `diffuse` is outside
+    /// `[0, 1]`.
+    pub fn with_diffuse(mut self, diffuse: f64) -> crate::Result<Self> {
+        self.diffuse = unit("diffuse", diffuse)?;
+        Ok(self)
+    }
+
+    /// Returns the material with its specular reflectance set to `specular`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `specular` is outside
+    /// `[0, 1]`.
+    pub fn with_specular(mut self, specular: f64) -> crate::Result<Self> {
+        self.specular = unit("specular", specular)?;
+        Ok(self)
+    }
+
+    /// Returns the material with its specular exponent set to `shininess`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `shininess` is not
+    /// positive.
+    pub fn with_shininess(mut self, shininess: f64) -> crate::Result<Self> {
+        if shininess > 0.0 {
+            self.shininess = shininess;
+            Ok(self)
+        } else {
+            Err(ShadingError::InvalidMaterial {
+                property:    "shininess".to_string(),
+                value:       shininess,
+                valid_range: (0.0, f64::INFINITY),
+            }
+            .into())
+        }
+    }
+
+    /// Returns the material with its reflectivity set to `reflective`, where
+    /// `0` is matte and `1` is a perfect mirror.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `reflective` is outside
+    /// `[0, 1]`.
+    pub fn with_reflective(mut self, reflective: f64) -> crate::Result<Self> {
+        self.reflective = unit("reflective", reflective)?;
+        Ok(self)
+    }
+
+    /// Returns the material with its transparency set to `transparency`,
+    /// where `0` is opaque and `1` lets all light through.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::InvalidMaterial`] if `transparency` is outside
+    /// `[0, 1]`.
+    pub fn with_transparency(mut self, transparency: f64) -> crate::Result<Self> {
+        self.transparency = unit("transparency", transparency)?;
+        Ok(self)
+    }
+
+    /// Returns the material with its index of refraction set to
+    /// `refractive_index`, e.g. `1.0` for vacuum or `1.5` for glass.
+    #[inline]
+    #[must_use]
+    pub const fn with_refractive_index(mut self, refractive_index: f64) -> Self {
+        self.refractive_index = refractive_index;
+        self
+    }
+}
+
+impl Default for Material {
+    #[inline(always)]
+    fn default() -> Self { Self::new() }
+}
+
+/// Passes `value` through if it lies within `[0, 1]`.
+fn unit(property: &str, value: f64) -> crate::Result<f64> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(ShadingError::InvalidMaterial {
+            property: property.to_string(),
+            value,
+            valid_range: (0.0, 1.0),
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TracerError;
+    use crate::prelude::color;
+
+    #[test]
+    fn test_default_material() {
+        let m = Material::default();
+        assert_eq!(m.color, color(1, 1, 1));
+        assert_eq!(m.ambient, 0.1);
+        assert_eq!(m.diffuse, 0.9);
+        assert_eq!(m.specular, 0.9);
+        assert_eq!(m.shininess, 200.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.refractive_index, 1.0);
+    }
+
+    #[test]
+    fn test_matte_preset_has_no_highlight_or_reflection() {
+        let m = Material::matte(color(0.2, 0.6, 0.3));
+        assert_eq!(m.color, color(0.2, 0.6, 0.3));
+        assert!(m.diffuse > 0.5);
+        assert_eq!(m.specular, 0.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+    }
+
+    #[test]
+    fn test_metal_preset_is_reflective_and_tinted() {
+        let polished = Material::metal(color(0.8, 0.6, 0.2), 0.0);
+        assert_eq!(polished.color, color(0.8, 0.6, 0.2));
+        assert!(polished.reflective > 0.5);
+        assert_eq!(polished.transparency, 0.0);
+
+        let brushed = Material::metal(color(0.8, 0.6, 0.2), 1.0);
+        assert!(brushed.reflective > 0.5);
+        assert!(brushed.reflective < polished.reflective);
+        assert!(brushed.shininess < polished.shininess);
+        assert_eq!(
+            Material::metal(Color3::WHITE, 7.0),
+            Material::metal(Color3::WHITE, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_dielectric_preset_is_transparent() {
+        let m = Material::dielectric(1.5);
+        assert_eq!(m.transparency, 1.0);
+        assert_eq!(m.refractive_index, 1.5);
+        assert!(m.reflective > 0.0);
+        assert!(m.color.r() > 0.9 && m.color.g() > 0.9 && m.color.b() > 0.9);
+    }
+
+    #[test]
+    fn test_builder_setters() {
+        let m = Material::new()
+            .with_color(color(1.0, 0.2, 1.0))
+            .with_ambient(1.0)
+            .and_then(|m| m.with_diffuse(0.7))
+            .and_then(|m| m.with_specular(0.2))
+            .and_then(|m| m.with_reflective(0.5))
+            .and_then(|m| m.with_transparency(0.25))
+            .and_then(|m| m.with_shininess(50.0))
+            .unwrap()
+            .with_refractive_index(1.5);
+
+        let expected = Material {
+            color:            color(1.0, 0.2, 1.0),
+            ambient:          1.0,
+            diffuse:          0.7,
+            specular:         0.2,
+            shininess:        50.0,
+            reflective:       0.5,
+            transparency:     0.25,
+            refractive_index: 1.5,
+        };
+        assert_eq!(m, expected);
+        assert_eq!(m.clone(), expected);
+        assert_ne!(m, Material::default());
+    }
+
+    #[test]
+    fn test_out_of_range_coefficients_are_rejected() {
+        let err = Material::new().with_ambient(1.5).unwrap_err();
+        let TracerError::Shading(err) = err else {
+            panic!("expected a shading error, got {err:?}")
+        };
+        assert_eq!(err, ShadingError::InvalidMaterial {
+            property:    "ambient".to_string(),
+            value:       1.5,
+            valid_range: (0.0, 1.0),
+        });
+        assert!(Material::new().with_diffuse(-0.1).is_err());
+        assert!(Material::new().with_specular(f64::NAN).is_err());
+        assert!(Material::new().with_reflective(1.01).is_err());
+        assert!(Material::new().with_transparency(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_reflective_and_transparency_are_rejected_outside_unit_range() {
+        for (property, value, result) in [
+            ("reflective", -0.5, Material::new().with_reflective(-0.5)),
+            ("reflective", 2.0, Material::new().with_reflective(2.0)),
+            ("transparency", -0.01, Material::new().with_transparency(-0.01)),
+            ("transparency", 1.5, Material::new().with_transparency(1.5)),
+        ] {
+            let TracerError::Shading(err) = result.unwrap_err() else {
+                panic!("expected a shading error for {property}")
+            };
+            assert_eq!(err, ShadingError::InvalidMaterial {
+                property: property.to_string(),
+                value,
+                valid_range: (0.0, 1.0),
+            });
+        }
+    }
+
+    #[test]
+    fn test_non_positive_shininess_is_rejected() {
+        for value in [0.0, -10.0] {
+            let TracerError::Shading(err) = Material::new().with_shininess(value).unwrap_err() else {
+                panic!("expected a shading error for shininess {value}")
+            };
+            assert_eq!(err, ShadingError::InvalidMaterial {
+                property: "shininess".to_string(),
+                value,
+                valid_range: (0.0, f64::INFINITY),
+            });
+        }
+        assert!(Material::new().with_shininess(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_boundary_values_are_accepted() {
+        let m = Material::new()
+            .with_reflective(1.0)
+            .and_then(|m| m.with_transparency(0.0))
+            .and_then(|m| m.with_shininess(0.5))
+            .unwrap();
+        assert_eq!(m.reflective, 1.0);
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.shininess, 0.5);
+    }
+}
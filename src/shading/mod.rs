@@ -1 +1,33 @@
+mod blended;
+mod light;
+mod lighting;
+mod material;
+mod nested;
+mod noise;
+mod pattern;
+mod perturbed;
+mod stripe;
+mod texture;
+mod uv;
 
+pub use blended::BlendedPattern;
+pub use light::{Light, PointLight, SpotLight};
+pub use lighting::{LightComponents, lighting, lighting_components};
+pub use material::Material;
+pub use nested::NestedPattern;
+pub use noise::value_noise;
+pub use pattern::{Pattern, pattern_at_shape};
+pub use perturbed::PerturbedPattern;
+pub use stripe::StripePattern;
+pub use texture::{CubeMap, Sampling, UvImage};
+pub use uv::{
+    Face,
+    TextureMap,
+    UvCheckers,
+    UvMapping,
+    UvPattern,
+    cube_uv,
+    cylindrical_map,
+    planar_map,
+    spherical_map,
+};
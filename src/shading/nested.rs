@@ -0,0 +1,124 @@
+use crate::math;
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::Pattern;
+use crate::shading::pattern::pattern_at_child;
+
+/// A three-dimensional checker whose two cells are filled by other patterns.
+///
+/// Each cell delegates to its child at the lookup point, transformed by the
+/// child's own transform, so a checker of stripes is just two boxed
+/// [`StripePattern`](crate::shading::StripePattern)s.
+#[derive(Debug)]
+pub struct NestedPattern {
+    a:         Box<dyn Pattern>,
+    b:         Box<dyn Pattern>,
+    transform: Mat4,
+    inverse:   Mat4,
+}
+
+impl NestedPattern {
+    /// Creates a checker that fills the cell at the origin with `a` and
+    /// alternating cells with `b`.
+    #[inline]
+    #[must_use]
+    pub fn new(a: impl Pattern + 'static, b: impl Pattern + 'static) -> Self {
+        Self {
+            a:         Box::new(a),
+            b:         Box::new(b),
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the pattern filling the even cells.
+    #[inline]
+    pub fn a(&self) -> &dyn Pattern { self.a.as_ref() }
+
+    /// Returns the pattern filling the odd cells.
+    #[inline]
+    pub fn b(&self) -> &dyn Pattern { self.b.as_ref() }
+}
+
+impl Pattern for NestedPattern {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        let cell = math::floor(point.x()) + math::floor(point.y()) + math::floor(point.z());
+        let child = if cell.rem_euclid(2.0) == 0.0 {
+            &self.a
+        } else {
+            &self.b
+        };
+        pattern_at_child(child.as_ref(), point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Sphere;
+    use crate::prelude::{color, point};
+    use crate::shading::{StripePattern, pattern_at_shape};
+
+    const WHITE: Color3 = Color3::WHITE;
+    const BLACK: Color3 = Color3::BLACK;
+
+    fn checker_of_stripes() -> NestedPattern {
+        let red = color(1, 0, 0);
+        let blue = color(0, 0, 1);
+        NestedPattern::new(
+            StripePattern::new(WHITE, BLACK).with_transform(Mat4::scaling(0.5, 0.5, 0.5)),
+            StripePattern::new(red, blue).with_transform(Mat4::scaling(0.5, 0.5, 0.5)),
+        )
+    }
+
+    #[test]
+    fn test_nested_pattern_delegates_to_the_even_child() {
+        let pattern = checker_of_stripes();
+        assert_eq!(pattern.pattern_at(point(0.25, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0.75, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(point(1.25, 1.0, 0.0)), WHITE);
+    }
+
+    #[test]
+    fn test_nested_pattern_delegates_to_the_odd_child() {
+        let pattern = checker_of_stripes();
+        assert_eq!(pattern.pattern_at(point(1.25, 0.0, 0.0)), color(1, 0, 0));
+        assert_eq!(pattern.pattern_at(point(1.75, 0.0, 0.0)), color(0, 0, 1));
+        assert_eq!(pattern.pattern_at(point(0.25, 0.0, 1.0)), color(1, 0, 0));
+        assert_eq!(pattern.pattern_at(point(0.75, 0.5, -0.5)), color(0, 0, 1));
+    }
+
+    #[test]
+    fn test_nested_pattern_transform_applies_before_the_children() {
+        let object = Sphere::new();
+        let pattern = checker_of_stripes().with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        assert_eq!(pattern_at_shape(&pattern, &object, point(0.5, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern_at_shape(&pattern, &object, point(1.5, 0.0, 0.0)), BLACK);
+        assert_eq!(
+            pattern_at_shape(&pattern, &object, point(2.5, 0.0, 0.0)),
+            color(1, 0, 0)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::math;
+use crate::primitives::Point3;
+
+/// Returns smoothly varying value noise in `[-1, 1]` at `point`.
+///
+/// Every integer lattice point is assigned a pseudo-random value derived from
+/// its coordinates and `seed`; points in between blend the eight surrounding
+/// lattice values with a smoothstep fade. The same `point` and `seed` always
+/// produce the same value.
+#[must_use]
+pub fn value_noise(point: Point3, seed: u64) -> f64 {
+    let (x0, y0, z0) = (
+        math::floor(point.x()),
+        math::floor(point.y()),
+        math::floor(point.z()),
+    );
+    let (tx, ty, tz) = (fade(point.x() - x0), fade(point.y() - y0), fade(point.z() - z0));
+    let (x0, y0, z0) = (x0 as i64, y0 as i64, z0 as i64);
+
+    let corner = |dx: i64, dy: i64, dz: i64| lattice(x0 + dx, y0 + dy, z0 + dz, seed);
+    let along_x = |dy: i64, dz: i64| math::lerp(corner(0, dy, dz), corner(1, dy, dz), tx);
+    let along_y = |dz: i64| math::lerp(along_x(0, dz), along_x(1, dz), ty);
+
+    math::lerp(along_y(0), along_y(1), tz)
+}
+
+/// Eases `t` in `[0, 1]` so the noise has no creases at lattice boundaries.
+#[inline]
+const fn fade(t: f64) -> f64 { math::smoothstep(0.0, 1.0, t) }
+
+/// Returns the pseudo-random value in `[-1, 1]` at lattice point
+/// (`x`, `y`, `z`).
+fn lattice(x: i64, y: i64, z: i64, seed: u64) -> f64 {
+    let mut h = seed ^ 0x9e37_79b9_7f4a_7c15;
+    for coord in [x, y, z] {
+        h = (h ^ coord as u64).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        h ^= h >> 31;
+    }
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    (h >> 11) as f64 / (1_u64 << 52) as f64 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::point;
+
+    #[test]
+    fn test_value_noise_is_deterministic() {
+        let p = point(1.3, -2.7, 0.45);
+        assert_eq!(value_noise(p, 7), value_noise(p, 7));
+    }
+
+    #[test]
+    fn test_value_noise_depends_on_the_seed() {
+        let p = point(1.3, -2.7, 0.45);
+        assert_ne!(value_noise(p, 1), value_noise(p, 2));
+    }
+
+    #[test]
+    fn test_value_noise_stays_in_range() {
+        for i in 0..1000 {
+            let t = f64::from(i) * 0.137;
+            let n = value_noise(point(t, -t * 0.5, t * 1.7), 3);
+            assert!((-1.0..=1.0).contains(&n), "noise {n} out of range at step {i}");
+        }
+    }
+
+    #[test]
+    fn test_value_noise_matches_lattice_values_at_integer_points() {
+        assert_eq!(value_noise(point(2, -1, 5), 9), lattice(2, -1, 5, 9));
+    }
+}
@@ -0,0 +1,42 @@
+use core::fmt::Debug;
+
+use crate::geometry::Shape;
+use crate::primitives::{Color3, Mat4, Point3};
+
+/// A procedural color that varies across space.
+///
+/// Patterns are defined in their own pattern space and carry a transform
+/// that places them relative to the object they decorate.
+pub trait Pattern: Debug + Send + Sync {
+    /// Returns the pattern-to-object transform.
+    fn transform(&self) -> Mat4;
+
+    /// Returns the object-to-pattern transform.
+    fn inverse_transform(&self) -> Mat4;
+
+    /// Sets the pattern-to-object transform.
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if `transform` is not invertible.
+    fn set_transform(&mut self, transform: Mat4);
+
+    /// Returns the color at the pattern-space `point`.
+    fn pattern_at(&self, point: Point3) -> Color3;
+}
+
+/// Returns the color of `pattern` on `object` at `world_point`, converting
+/// the point into object space and then into pattern space.
+pub fn pattern_at_shape(pattern: &dyn Pattern, object: &dyn Shape, world_point: Point3) -> Color3 {
+    let object_point = object.inverse_transform() * world_point;
+    pattern.pattern_at(pattern.inverse_transform() * object_point)
+}
+
+/// Returns the color of a child `pattern` at `point`, given in the pattern
+/// space of its parent.
+///
+/// The child's own transform is applied on top of the parent's, so nested
+/// patterns can be scaled and rotated independently.
+pub(crate) fn pattern_at_child(pattern: &dyn Pattern, point: Point3) -> Color3 {
+    pattern.pattern_at(pattern.inverse_transform() * point)
+}
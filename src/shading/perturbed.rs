@@ -0,0 +1,136 @@
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::Pattern;
+use crate::shading::noise::value_noise;
+use crate::shading::pattern::pattern_at_child;
+
+/// A pattern whose lookups are jittered by value noise before delegating to
+/// an inner pattern, giving its edges an organic wobble.
+#[derive(Debug)]
+pub struct PerturbedPattern {
+    inner:     Box<dyn Pattern>,
+    amplitude: f64,
+    seed:      u64,
+    transform: Mat4,
+    inverse:   Mat4,
+}
+
+impl PerturbedPattern {
+    /// Creates a pattern displacing lookups into `inner` by up to
+    /// `amplitude` along each axis.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: impl Pattern + 'static, amplitude: f64) -> Self {
+        Self {
+            inner: Box::new(inner),
+            amplitude,
+            seed: 0,
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its noise seeded by `seed`.
+    #[inline]
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the perturbed pattern.
+    #[inline]
+    pub fn inner(&self) -> &dyn Pattern { self.inner.as_ref() }
+
+    /// Returns the largest displacement along any axis.
+    #[inline]
+    pub const fn amplitude(&self) -> f64 { self.amplitude }
+
+    /// Returns the noise seed.
+    #[inline]
+    pub const fn seed(&self) -> u64 { self.seed }
+}
+
+impl Pattern for PerturbedPattern {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        if self.amplitude == 0.0 {
+            return pattern_at_child(self.inner.as_ref(), point);
+        }
+
+        // Each axis samples its own noise field so the offset isn't always
+        // along the diagonal.
+        let seed = self.seed.wrapping_mul(3);
+        let offset = [0, 1, 2].map(|axis| self.amplitude * value_noise(point, seed.wrapping_add(axis)));
+        let jittered = Point3::new(
+            point.x() + offset[0],
+            point.y() + offset[1],
+            point.z() + offset[2],
+        );
+        pattern_at_child(self.inner.as_ref(), jittered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::point;
+    use crate::shading::StripePattern;
+
+    const WHITE: Color3 = Color3::WHITE;
+    const BLACK: Color3 = Color3::BLACK;
+
+    #[test]
+    fn test_zero_amplitude_matches_the_inner_pattern() {
+        let inner = StripePattern::new(WHITE, BLACK);
+        let pattern = PerturbedPattern::new(inner, 0.0);
+        for p in [
+            point(0.0, 0.0, 0.0),
+            point(0.99, 0.3, -2.0),
+            point(-0.01, 5.0, 1.0),
+            point(1.5, -1.5, 0.25),
+        ] {
+            assert_eq!(pattern.pattern_at(p), inner.pattern_at(p));
+        }
+    }
+
+    #[test]
+    fn test_nonzero_amplitude_moves_the_stripe_edge() {
+        let inner = StripePattern::new(WHITE, BLACK);
+        let pattern = PerturbedPattern::new(inner, 0.5);
+        let p = point(0.9, 1.0, 0.5);
+        assert_eq!(inner.pattern_at(p), WHITE);
+        assert_eq!(pattern.pattern_at(p), BLACK);
+    }
+
+    #[test]
+    fn test_perturbation_is_reproducible() {
+        let a = PerturbedPattern::new(StripePattern::new(WHITE, BLACK), 0.3).with_seed(42);
+        let b = PerturbedPattern::new(StripePattern::new(WHITE, BLACK), 0.3).with_seed(42);
+        for i in 0..50 {
+            let p = point(f64::from(i) * 0.1, 0.2, -0.3);
+            assert_eq!(a.pattern_at(p), b.pattern_at(p));
+        }
+    }
+}
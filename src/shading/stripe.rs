@@ -0,0 +1,134 @@
+use crate::math;
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::Pattern;
+
+/// Alternating bands of two colors along the x axis, each one unit wide.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StripePattern {
+    a:         Color3,
+    b:         Color3,
+    transform: Mat4,
+    inverse:   Mat4,
+}
+
+impl StripePattern {
+    /// Creates a stripe pattern starting with `a` at the origin.
+    #[inline]
+    #[must_use]
+    pub const fn new(a: Color3, b: Color3) -> Self {
+        Self {
+            a,
+            b,
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the color of the even stripes.
+    #[inline]
+    pub const fn a(&self) -> Color3 { self.a }
+
+    /// Returns the color of the odd stripes.
+    #[inline]
+    pub const fn b(&self) -> Color3 { self.b }
+}
+
+impl Pattern for StripePattern {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        if math::floor(point.x()).rem_euclid(2.0) == 0.0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Sphere;
+    use crate::prelude::point;
+    use crate::shading::pattern_at_shape;
+
+    const WHITE: Color3 = Color3::WHITE;
+    const BLACK: Color3 = Color3::BLACK;
+
+    #[test]
+    fn test_creating_a_stripe_pattern() {
+        let pattern = StripePattern::new(WHITE, BLACK);
+        assert_eq!(pattern.a(), WHITE);
+        assert_eq!(pattern.b(), BLACK);
+        assert_eq!(pattern.transform(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_stripe_pattern_is_constant_in_y() {
+        let pattern = StripePattern::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(point(0, 0, 0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0, 1, 0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0, 2, 0)), WHITE);
+    }
+
+    #[test]
+    fn test_stripe_pattern_is_constant_in_z() {
+        let pattern = StripePattern::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(point(0, 0, 0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0, 0, 1)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0, 0, 2)), WHITE);
+    }
+
+    #[test]
+    fn test_stripe_pattern_alternates_in_x() {
+        let pattern = StripePattern::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(point(0.0, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(0.9, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(point(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(point(-0.1, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(point(-1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(point(-1.1, 0.0, 0.0)), WHITE);
+    }
+
+    #[test]
+    fn test_stripes_with_an_object_transformation() {
+        let object = Sphere::new().with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        let pattern = StripePattern::new(WHITE, BLACK);
+        assert_eq!(pattern_at_shape(&pattern, &object, point(1.5, 0.0, 0.0)), WHITE);
+    }
+
+    #[test]
+    fn test_stripes_with_a_pattern_transformation() {
+        let object = Sphere::new();
+        let pattern = StripePattern::new(WHITE, BLACK).with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        assert_eq!(pattern_at_shape(&pattern, &object, point(1.5, 0.0, 0.0)), WHITE);
+    }
+
+    #[test]
+    fn test_stripes_with_both_an_object_and_a_pattern_transformation() {
+        let object = Sphere::new().with_transform(Mat4::scaling(2.0, 2.0, 2.0));
+        let pattern = StripePattern::new(WHITE, BLACK).with_transform(Mat4::translation(0.5, 0.0, 0.0));
+        assert_eq!(pattern_at_shape(&pattern, &object, point(2.5, 0.0, 0.0)), WHITE);
+    }
+}
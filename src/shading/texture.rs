@@ -0,0 +1,267 @@
+use crate::graphics::canvas::Canvas;
+use crate::math;
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::{Face, Pattern, UvPattern, cube_uv};
+
+/// How an [`UvImage`] turns texture coordinates that fall between pixel
+/// centers into a color.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Sampling {
+    /// Use the color of the closest pixel.
+    #[default]
+    Nearest,
+    /// Blend the four surrounding pixels by distance.
+    Bilinear,
+}
+
+/// A texture backed by an image, such as one loaded with
+/// [`Canvas::from_ppm`].
+///
+/// The texture origin `(0, 0)` is the bottom-left corner of the image, so
+/// `v` is flipped to reach the canvas rows, which run top to bottom.
+/// Coordinates outside `[0, 1]` are clamped to the nearest edge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UvImage {
+    canvas:   Canvas,
+    sampling: Sampling,
+}
+
+impl UvImage {
+    /// Creates a texture sampling `canvas` with [`Sampling::Nearest`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `canvas` has no pixels.
+    #[inline]
+    #[must_use]
+    pub fn new(canvas: Canvas) -> Self {
+        assert!(
+            canvas.width() > 0 && canvas.height() > 0,
+            "texture canvas must not be empty"
+        );
+        Self {
+            canvas,
+            sampling: Sampling::Nearest,
+        }
+    }
+
+    /// Returns the texture with its sampling mode set to `sampling`.
+    #[inline]
+    #[must_use]
+    pub const fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Returns the image being sampled.
+    #[inline]
+    pub const fn canvas(&self) -> &Canvas { &self.canvas }
+
+    /// Returns how colors between pixel centers are chosen.
+    #[inline]
+    pub const fn sampling(&self) -> Sampling { self.sampling }
+}
+
+impl UvPattern for UvImage {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color3 {
+        let u = math::clamp(u, 0.0, 1.0);
+        let v = 1.0 - math::clamp(v, 0.0, 1.0);
+
+        let x = u * (self.canvas.width() - 1) as f64;
+        let y = v * (self.canvas.height() - 1) as f64;
+
+        match self.sampling {
+            Sampling::Nearest => self
+                .canvas
+                .pixel_at(math::round(x) as usize, math::round(y) as usize),
+            Sampling::Bilinear => {
+                let (x0, y0) = (math::floor(x) as usize, math::floor(y) as usize);
+                let (x1, y1) = (math::ceil(x) as usize, math::ceil(y) as usize);
+                let (tx, ty) = (x - x0 as f64, y - y0 as f64);
+
+                let top = self
+                    .canvas
+                    .pixel_at(x0, y0)
+                    .lerp(self.canvas.pixel_at(x1, y0), tx);
+                let bottom = self
+                    .canvas
+                    .pixel_at(x0, y1)
+                    .lerp(self.canvas.pixel_at(x1, y1), tx);
+                top.lerp(bottom, ty)
+            },
+        }
+    }
+}
+
+/// A cube-mapped environment, with one image for each face of the cube.
+///
+/// Points are projected onto the unit cube with [`cube_uv`], so a `CubeMap`
+/// on a large cube surrounding a scene serves as a skybox.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubeMap {
+    faces:     [UvImage; 6],
+    transform: Mat4,
+    inverse:   Mat4,
+}
+
+impl CubeMap {
+    /// Creates a cube map from the image on each face.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        left: UvImage,
+        front: UvImage,
+        right: UvImage,
+        back: UvImage,
+        up: UvImage,
+        down: UvImage,
+    ) -> Self {
+        Self {
+            faces:     [left, front, right, back, up, down],
+            transform: Mat4::IDENTITY,
+            inverse:   Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the image on `face`.
+    #[inline]
+    pub const fn face(&self, face: Face) -> &UvImage {
+        match face {
+            Face::Left => &self.faces[0],
+            Face::Front => &self.faces[1],
+            Face::Right => &self.faces[2],
+            Face::Back => &self.faces[3],
+            Face::Up => &self.faces[4],
+            Face::Down => &self.faces[5],
+        }
+    }
+}
+
+impl Pattern for CubeMap {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        let (face, u, v) = cube_uv(point);
+        self.face(face).uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{color, point};
+
+    fn marked_corners() -> UvImage {
+        let mut canvas = Canvas::new(10, 10);
+        canvas.write_pixel(0, 0, color(1, 0, 0));
+        canvas.write_pixel(9, 0, color(0, 1, 0));
+        canvas.write_pixel(0, 9, color(0, 0, 1));
+        canvas.write_pixel(9, 9, color(1, 1, 1));
+        UvImage::new(canvas)
+    }
+
+    #[test]
+    fn test_uv_image_maps_corners_to_pixels() {
+        let image = marked_corners();
+        assert_eq!(image.uv_pattern_at(0.0, 1.0), color(1, 0, 0));
+        assert_eq!(image.uv_pattern_at(1.0, 1.0), color(0, 1, 0));
+        assert_eq!(image.uv_pattern_at(0.0, 0.0), color(0, 0, 1));
+        assert_eq!(image.uv_pattern_at(1.0, 0.0), color(1, 1, 1));
+        assert_eq!(image.uv_pattern_at(0.5, 0.5), Color3::BLACK);
+    }
+
+    #[test]
+    fn test_uv_image_clamps_out_of_range_coordinates() {
+        let image = marked_corners();
+        assert_eq!(image.uv_pattern_at(-0.5, 2.0), color(1, 0, 0));
+        assert_eq!(image.uv_pattern_at(3.0, 1.5), color(0, 1, 0));
+        assert_eq!(image.uv_pattern_at(-1.0, -1.0), color(0, 0, 1));
+        assert_eq!(image.uv_pattern_at(1.5, -0.25), color(1, 1, 1));
+    }
+
+    #[test]
+    fn test_uv_image_samples_a_loaded_ppm() {
+        let ppm = "P3\n2 2\n255\n255 0 0  0 255 0\n0 0 255  255 255 255\n";
+        let image = UvImage::new(Canvas::from_ppm(ppm).unwrap());
+        assert_eq!(image.uv_pattern_at(0.2, 0.9), color(1, 0, 0));
+        assert_eq!(image.uv_pattern_at(0.8, 0.1), color(1, 1, 1));
+    }
+
+    #[test]
+    fn test_bilinear_sampling_blends_neighboring_pixels() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(1, 0, Color3::WHITE);
+        let image = UvImage::new(canvas).with_sampling(Sampling::Bilinear);
+        assert_eq!(image.uv_pattern_at(0.0, 0.5), Color3::BLACK);
+        assert_eq!(image.uv_pattern_at(0.25, 0.5), color(0.25, 0.25, 0.25));
+        assert_eq!(image.uv_pattern_at(1.0, 0.5), Color3::WHITE);
+    }
+
+    #[test]
+    fn test_cube_map_picks_the_image_for_each_face() {
+        let solid = |c: Color3| {
+            let mut canvas = Canvas::new(1, 1);
+            canvas.fill(c);
+            UvImage::new(canvas)
+        };
+        let colors = [
+            color(1, 1, 0),
+            color(0, 1, 1),
+            color(1, 0, 0),
+            color(0, 1, 0),
+            color(1, 0, 1),
+            color(1, 1, 1),
+        ];
+        let [left, front, right, back, up, down] = colors.map(solid);
+        let cube = CubeMap::new(left, front, right, back, up, down);
+
+        let cases = [
+            (point(-1.0, 0.0, 0.0), color(1, 1, 0)),
+            (point(0.0, 0.0, 1.0), color(0, 1, 1)),
+            (point(1.0, 0.0, 0.0), color(1, 0, 0)),
+            (point(0.0, 0.0, -1.0), color(0, 1, 0)),
+            (point(0.0, 1.0, 0.0), color(1, 0, 1)),
+            (point(0.0, -1.0, 0.0), color(1, 1, 1)),
+        ];
+        for (p, expected) in cases {
+            assert_eq!(cube.pattern_at(p), expected, "at {p:?}");
+        }
+        for (face, expected) in Face::ALL.into_iter().zip(colors) {
+            assert_eq!(cube.face(face).canvas().pixel_at(0, 0), expected);
+        }
+    }
+
+    #[test]
+    fn test_cube_map_samples_within_a_face() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(0, 0, color(1, 0, 0));
+        canvas.write_pixel(1, 1, color(0, 0, 1));
+        let blank = || UvImage::new(Canvas::new(1, 1));
+        let cube = CubeMap::new(blank(), UvImage::new(canvas), blank(), blank(), blank(), blank());
+
+        assert_eq!(cube.pattern_at(point(-0.9, 0.9, 1.0)), color(1, 0, 0));
+        assert_eq!(cube.pattern_at(point(0.9, -0.9, 1.0)), color(0, 0, 1));
+        assert_eq!(cube.pattern_at(point(0.9, 0.9, 1.0)), Color3::BLACK);
+    }
+}
@@ -0,0 +1,411 @@
+use core::f64::consts::{PI, TAU};
+use core::fmt::Debug;
+
+use crate::error::GeometryError;
+use crate::math;
+use crate::primitives::{Color3, Inverse, Mat4, Point3};
+use crate::shading::Pattern;
+
+/// A two-dimensional pattern addressed by texture coordinates.
+pub trait UvPattern: Debug + Send + Sync {
+    /// Returns the color at texture coordinates (`u`, `v`), each in `[0, 1]`.
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color3;
+}
+
+/// Returns the texture coordinates of `point` on the unit sphere.
+///
+/// `u` runs once around the equator, starting and ending at `-z`, and `v`
+/// runs from the south pole at `0` to the north pole at `1`.
+#[must_use]
+pub fn spherical_map(point: Point3) -> (f64, f64) {
+    let theta = math::atan2(point.x(), point.z());
+    let radius = (point - Point3::new(0.0, 0.0, 0.0)).length();
+    let phi = math::acos_approx(point.y() / radius);
+
+    let raw_u = theta / TAU;
+    (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+}
+
+/// Returns the texture coordinates of `point` on the xz plane, repeating
+/// every unit.
+#[must_use]
+pub fn planar_map(point: Point3) -> (f64, f64) {
+    (math::rem_euclid(point.x(), 1.0), math::rem_euclid(point.z(), 1.0))
+}
+
+/// Returns the texture coordinates of `point` on the unit cylinder.
+///
+/// `u` wraps around the axis as in [`spherical_map`], and `v` repeats every
+/// unit along `y`.
+#[must_use]
+pub fn cylindrical_map(point: Point3) -> (f64, f64) {
+    let theta = math::atan2(point.x(), point.z());
+    let raw_u = theta / TAU;
+    (1.0 - (raw_u + 0.5), math::rem_euclid(point.y(), 1.0))
+}
+
+/// One of the six faces of an axis-aligned cube.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Face {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+impl Face {
+    /// Every face, in the order [`CubeMap::new`](crate::shading::CubeMap::new)
+    /// takes them.
+    pub const ALL: [Self; 6] = [
+        Self::Left,
+        Self::Front,
+        Self::Right,
+        Self::Back,
+        Self::Up,
+        Self::Down,
+    ];
+
+    /// Returns the face of the cube centered on the origin that `point` lies
+    /// on, chosen by its largest coordinate.
+    #[must_use]
+    pub const fn from_point(point: Point3) -> Self {
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let coord = math::max(math::max(math::abs(x), math::abs(y)), math::abs(z));
+
+        if coord == x {
+            Self::Right
+        } else if coord == -x {
+            Self::Left
+        } else if coord == y {
+            Self::Up
+        } else if coord == -y {
+            Self::Down
+        } else if coord == z {
+            Self::Front
+        } else {
+            Self::Back
+        }
+    }
+}
+
+/// Returns the face of the unit cube that `point` lies on, along with its
+/// texture coordinates on that face.
+///
+/// Each face is unfolded as seen from outside the cube, with `u` increasing
+/// to the right and `v` increasing upward.
+#[must_use]
+pub fn cube_uv(point: Point3) -> (Face, f64, f64) {
+    let (x, y, z) = (point.x(), point.y(), point.z());
+    let wrap = |t: f64| math::rem_euclid(t, 2.0) / 2.0;
+
+    let face = Face::from_point(point);
+    let (u, v) = match face {
+        Face::Front => (wrap(x + 1.0), wrap(y + 1.0)),
+        Face::Back => (wrap(1.0 - x), wrap(y + 1.0)),
+        Face::Left => (wrap(z + 1.0), wrap(y + 1.0)),
+        Face::Right => (wrap(1.0 - z), wrap(y + 1.0)),
+        Face::Up => (wrap(x + 1.0), wrap(1.0 - z)),
+        Face::Down => (wrap(x + 1.0), wrap(z + 1.0)),
+    };
+    (face, u, v)
+}
+
+/// How a point on a surface is flattened into texture coordinates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UvMapping {
+    Spherical,
+    Planar,
+    Cylindrical,
+}
+
+impl UvMapping {
+    /// Returns the texture coordinates of `point` under this mapping.
+    #[must_use]
+    pub fn map(self, point: Point3) -> (f64, f64) {
+        match self {
+            Self::Spherical => spherical_map(point),
+            Self::Planar => planar_map(point),
+            Self::Cylindrical => cylindrical_map(point),
+        }
+    }
+
+    /// Returns the texture coordinates of `point` under this mapping,
+    /// checking that they landed inside the texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidUvCoordinates`] if either coordinate
+    /// falls outside `[0, 1]`, which happens for non-finite points and for
+    /// the center of a spherical mapping.
+    pub fn try_map(self, point: Point3) -> crate::Result<(f64, f64)> {
+        let (u, v) = self.map(point);
+        if (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v) {
+            Ok((u, v))
+        } else {
+            Err(GeometryError::InvalidUvCoordinates {
+                u,
+                v,
+                shape: self.shape().to_string(),
+            }
+            .into())
+        }
+    }
+
+    /// Returns the name of the surface this mapping wraps.
+    #[inline]
+    const fn shape(self) -> &'static str {
+        match self {
+            Self::Spherical => "sphere",
+            Self::Planar => "plane",
+            Self::Cylindrical => "cylinder",
+        }
+    }
+}
+
+/// A checkerboard of `width`×`height` squares over texture space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvCheckers {
+    pub width:  f64,
+    pub height: f64,
+    pub a:      Color3,
+    pub b:      Color3,
+}
+
+impl UvCheckers {
+    /// Creates a checkerboard of `width`×`height` squares starting with `a`
+    /// at the texture origin.
+    #[inline]
+    #[must_use]
+    pub const fn new(width: f64, height: f64, a: Color3, b: Color3) -> Self { Self { width, height, a, b } }
+}
+
+impl UvPattern for UvCheckers {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color3 {
+        let u2 = math::floor(u * self.width);
+        let v2 = math::floor(v * self.height);
+        if math::rem_euclid(u2 + v2, 2.0) == 0.0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+/// A [`UvPattern`] wrapped onto a surface by a [`UvMapping`].
+#[derive(Debug)]
+pub struct TextureMap {
+    uv_pattern: Box<dyn UvPattern>,
+    mapping:    UvMapping,
+    transform:  Mat4,
+    inverse:    Mat4,
+}
+
+impl TextureMap {
+    /// Creates a pattern that maps each point with `mapping` and looks up the
+    /// result in `uv_pattern`.
+    #[inline]
+    #[must_use]
+    pub fn new(uv_pattern: impl UvPattern + 'static, mapping: UvMapping) -> Self {
+        Self {
+            uv_pattern: Box::new(uv_pattern),
+            mapping,
+            transform: Mat4::IDENTITY,
+            inverse: Mat4::IDENTITY,
+        }
+    }
+
+    /// Returns the pattern with its transform set to `transform`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `transform` is not invertible.
+    #[inline]
+    #[must_use]
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Returns the texture being mapped.
+    #[inline]
+    pub fn uv_pattern(&self) -> &dyn UvPattern { self.uv_pattern.as_ref() }
+
+    /// Returns how points are flattened into texture coordinates.
+    #[inline]
+    pub const fn mapping(&self) -> UvMapping { self.mapping }
+}
+
+impl Pattern for TextureMap {
+    #[inline]
+    fn transform(&self) -> Mat4 { self.transform }
+
+    #[inline]
+    fn inverse_transform(&self) -> Mat4 { self.inverse }
+
+    fn set_transform(&mut self, transform: Mat4) {
+        self.inverse = transform.inverse().expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn pattern_at(&self, point: Point3) -> Color3 {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+    use crate::cmp::float::is_equal;
+    use crate::error::TracerError;
+    use crate::prelude::point;
+
+    const WHITE: Color3 = Color3::WHITE;
+    const BLACK: Color3 = Color3::BLACK;
+
+    fn assert_uv(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            is_equal(actual.0, expected.0) && is_equal(actual.1, expected.1),
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_checker_pattern_in_2d() {
+        let checkers = UvCheckers::new(2.0, 2.0, BLACK, WHITE);
+        assert_eq!(checkers.uv_pattern_at(0.0, 0.0), BLACK);
+        assert_eq!(checkers.uv_pattern_at(0.5, 0.0), WHITE);
+        assert_eq!(checkers.uv_pattern_at(0.0, 0.5), WHITE);
+        assert_eq!(checkers.uv_pattern_at(0.5, 0.5), BLACK);
+        assert_eq!(checkers.uv_pattern_at(1.0, 1.0), BLACK);
+    }
+
+    #[test]
+    fn test_using_a_spherical_mapping_on_a_3d_point() {
+        let cases = [
+            (point(0, 0, -1), (0.0, 0.5)),
+            (point(1, 0, 0), (0.25, 0.5)),
+            (point(0, 0, 1), (0.5, 0.5)),
+            (point(-1, 0, 0), (0.75, 0.5)),
+            (point(0, 1, 0), (0.5, 1.0)),
+            (point(0, -1, 0), (0.5, 0.0)),
+            (point(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0), (0.25, 0.75)),
+        ];
+        for (p, uv) in cases {
+            assert_uv(spherical_map(p), uv);
+        }
+    }
+
+    #[test]
+    fn test_using_a_texture_map_pattern_with_a_spherical_map() {
+        let pattern = TextureMap::new(UvCheckers::new(16.0, 8.0, BLACK, WHITE), UvMapping::Spherical);
+        let cases = [
+            (point(0.4315, 0.4670, 0.7719), WHITE),
+            (point(-0.9654, 0.2552, -0.0534), BLACK),
+            (point(0.1039, 0.7090, 0.6975), WHITE),
+            (point(-0.4986, -0.7856, -0.3663), BLACK),
+            (point(-0.0317, -0.9395, 0.3411), BLACK),
+            (point(0.4809, -0.7721, 0.4154), BLACK),
+            (point(0.0285, -0.9612, -0.2745), BLACK),
+            (point(-0.5734, -0.2162, -0.7903), WHITE),
+            (point(0.7688, -0.1470, 0.6223), BLACK),
+            (point(-0.7652, 0.2175, 0.6060), BLACK),
+        ];
+        for (p, expected) in cases {
+            assert_eq!(pattern.pattern_at(p), expected, "at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_using_a_planar_mapping_on_a_3d_point() {
+        let cases = [
+            (point(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (point(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (point(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (point(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (point(0.25, 0.0, -1.75), (0.25, 0.25)),
+            (point(1, 0, -1), (0.0, 0.0)),
+            (point(0, 0, 0), (0.0, 0.0)),
+        ];
+        for (p, uv) in cases {
+            assert_uv(planar_map(p), uv);
+        }
+    }
+
+    #[test]
+    fn test_using_a_cylindrical_mapping_on_a_3d_point() {
+        let cases = [
+            (point(0, 0, -1), (0.0, 0.0)),
+            (point(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (point(0, 1, -1), (0.0, 0.0)),
+            (point(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), (0.125, 0.5)),
+            (point(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (point(FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), (0.375, 0.5)),
+            (point(0.0, -0.25, 1.0), (0.5, 0.75)),
+            (point(-FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), (0.625, 0.5)),
+            (point(-1.0, 1.25, 0.0), (0.75, 0.25)),
+            (point(-FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), (0.875, 0.5)),
+        ];
+        for (p, uv) in cases {
+            assert_uv(cylindrical_map(p), uv);
+        }
+    }
+
+    #[test]
+    fn test_identifying_the_face_of_a_cube_from_a_point() {
+        let cases = [
+            (point(-1.0, 0.5, -0.25), Face::Left),
+            (point(1.1, -0.75, 0.8), Face::Right),
+            (point(0.1, 0.6, 0.9), Face::Front),
+            (point(-0.7, 0.0, -2.0), Face::Back),
+            (point(0.5, 1.0, 0.9), Face::Up),
+            (point(-0.2, -1.3, 1.1), Face::Down),
+        ];
+        for (p, face) in cases {
+            assert_eq!(Face::from_point(p), face, "at {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_uv_mapping_each_face_of_a_cube() {
+        let cases = [
+            (point(-0.5, 0.5, 1.0), Face::Front, (0.25, 0.75)),
+            (point(0.5, -0.5, 1.0), Face::Front, (0.75, 0.25)),
+            (point(0.5, 0.5, -1.0), Face::Back, (0.25, 0.75)),
+            (point(-0.5, -0.5, -1.0), Face::Back, (0.75, 0.25)),
+            (point(-1.0, 0.5, -0.5), Face::Left, (0.25, 0.75)),
+            (point(-1.0, -0.5, 0.5), Face::Left, (0.75, 0.25)),
+            (point(1.0, 0.5, 0.5), Face::Right, (0.25, 0.75)),
+            (point(1.0, -0.5, -0.5), Face::Right, (0.75, 0.25)),
+            (point(-0.5, 1.0, -0.5), Face::Up, (0.25, 0.75)),
+            (point(0.5, 1.0, 0.5), Face::Up, (0.75, 0.25)),
+            (point(-0.5, -1.0, 0.5), Face::Down, (0.25, 0.75)),
+            (point(0.5, -1.0, -0.5), Face::Down, (0.75, 0.25)),
+        ];
+        for (p, face, uv) in cases {
+            let (actual, u, v) = cube_uv(p);
+            assert_eq!(actual, face, "at {p:?}");
+            assert_uv((u, v), uv);
+        }
+    }
+
+    #[test]
+    fn test_try_map_accepts_points_on_the_surface() {
+        assert_uv(UvMapping::Spherical.try_map(point(1, 0, 0)).unwrap(), (0.25, 0.5));
+    }
+
+    #[test]
+    fn test_try_map_rejects_unmappable_points() {
+        let err = UvMapping::Spherical.try_map(point(0, 0, 0)).unwrap_err();
+        let TracerError::Geometry(GeometryError::InvalidUvCoordinates { shape, .. }) = err else {
+            panic!("expected invalid UV coordinates, got {err:?}")
+        };
+        assert_eq!(shape, "sphere");
+
+        assert!(UvMapping::Planar.try_map(point(f64::NAN, 0.0, 0.0)).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use core::f64::consts::TAU;
+use std::collections::HashMap;
+
+use crate::geometry::{Ray, Shape, hit};
+use crate::graphics::camera::{Camera, jitter};
+use crate::graphics::canvas::Canvas;
+use crate::math;
+use crate::primitives::{Color3, Point3, Vec3};
+use crate::shading::Light;
+use crate::world::{Computations, World};
+
+impl World {
+    /// Edge length of the cubes that [`render_with_caustics`] bins photon
+    /// hits into. Smaller cells give sharper but noisier caustics.
+    ///
+    /// [`render_with_caustics`]: Self::render_with_caustics
+    pub const CAUSTIC_CELL_SIZE: f64 = 0.1;
+
+    /// Renders the world like [`Camera::render`], adding the light that
+    /// reflective and transparent objects focus onto other surfaces.
+    ///
+    /// This is a simplified photon map. Each light shoots `photon_count`
+    /// photons at every top-level object whose own material reflects or
+    /// transmits. The photons follow mirror reflections and refractions,
+    /// and wherever one lands on a diffuse surface its power is recorded in a
+    /// grid of [`CAUSTIC_CELL_SIZE`](Self::CAUSTIC_CELL_SIZE) cubes. Shading
+    /// then adds the power stored around each point the camera sees. Photon
+    /// directions come from a fixed hash, so renders are reproducible.
+    pub fn render_with_caustics(&self, camera: &Camera, photon_count: usize) -> Canvas {
+        let photons = PhotonMap::trace(self, photon_count);
+
+        let mut image = Canvas::new(camera.hsize(), camera.vsize());
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                let ray = camera.ray_for_pixel(x, y);
+                let xs = self.intersect(&ray);
+                let caustic = hit(&xs).map_or(Color3::BLACK, |hit| {
+                    photons.radiance(&Computations::prepare(hit, &ray, &xs))
+                });
+                image.write_pixel(x, y, self.color_at_default(&ray) + caustic);
+            }
+        }
+        image
+    }
+}
+
+/// Photon power accumulated per grid cell.
+struct PhotonMap {
+    cells: HashMap<[i64; 3], Color3>,
+}
+
+impl PhotonMap {
+    /// Shoots `photon_count` photons from every light of `world` at each of
+    /// its reflective or transparent objects.
+    fn trace(world: &World, photon_count: usize) -> Self {
+        let mut map = Self {
+            cells: HashMap::new(),
+        };
+        if photon_count == 0 {
+            return map;
+        }
+
+        let targets: Vec<_> = world
+            .objects()
+            .iter()
+            .filter(|object| is_specular(object.as_ref()))
+            .filter_map(|object| {
+                let bounds = object.parent_space_bounds();
+                let finite = [bounds.min, bounds.max]
+                    .iter()
+                    .all(|p| p.x().is_finite() && p.y().is_finite() && p.z().is_finite());
+                let center = bounds.min.lerp(bounds.max, 0.5);
+                (finite && !bounds.is_empty()).then(|| (center, (bounds.max - center).length()))
+            })
+            .collect();
+
+        for (light_index, light) in world.lights().iter().enumerate() {
+            for (target_index, &(center, radius)) in targets.iter().enumerate() {
+                let stream = ((light_index * targets.len() + target_index) * photon_count) as u64;
+                map.emit(world, light, center, radius, photon_count, stream);
+            }
+        }
+        map
+    }
+
+    /// Shoots `photon_count` photons from `light` into the cone that covers
+    /// the sphere at `center` with `radius`.
+    ///
+    /// Photon power is scaled so that photons landing unfocused on a surface
+    /// facing the light, as far away as the target, add up to the light's
+    /// intensity. That matches the brightness of the direct diffuse term,
+    /// which does not fall off with distance.
+    fn emit(
+        &mut self,
+        world: &World,
+        light: &Light,
+        center: Point3,
+        radius: f64,
+        photon_count: usize,
+        stream: u64,
+    ) {
+        let axis = center - light.position();
+        let distance = axis.length();
+        if distance == 0.0 {
+            return;
+        }
+
+        let axis = axis / distance;
+        let cos_max = if radius < distance {
+            math::sqrt(1.0 - (radius / distance) * (radius / distance))
+        } else {
+            -1.0
+        };
+        let solid_angle = TAU * (1.0 - cos_max);
+        let power = light.intensity() * (solid_angle * distance * distance / photon_count as f64);
+
+        let helper = if math::abs(axis.x()) > 0.9 {
+            Vec3::Y
+        } else {
+            Vec3::X
+        };
+        let u = axis.cross(helper).normalize();
+        let v = axis.cross(u);
+        for i in 0..photon_count {
+            let seed = stream + i as u64;
+            let cos_theta = 1.0 - jitter(seed, 0) * (1.0 - cos_max);
+            let sin_theta = math::sqrt(1.0 - cos_theta * cos_theta);
+            let (sin_phi, cos_phi) = math::sin_cos(TAU * jitter(seed, 1));
+            let direction = axis * cos_theta + (u * cos_phi + v * sin_phi) * sin_theta;
+
+            let attenuation = light.attenuation(light.position() + direction);
+            if attenuation > 0.0 {
+                self.follow(world, Ray::new(light.position(), direction), power * attenuation);
+            }
+        }
+    }
+
+    /// Follows a photon through mirror reflections and refractions, storing
+    /// it where it first lands on a diffuse surface after at least one
+    /// bounce. Photons that reach a diffuse surface directly are left to the
+    /// ordinary direct lighting.
+    fn follow(&mut self, world: &World, mut ray: Ray, mut power: Color3) {
+        for bounce in 0..=World::DEFAULT_DEPTH {
+            let xs = world.intersect(&ray);
+            let Some(hit) = hit(&xs) else {
+                return;
+            };
+            let comps = Computations::prepare(hit, &ray, &xs);
+            let material = comps.object.material();
+
+            if !is_specular(comps.object) {
+                if bounce > 0 {
+                    *self.cells.entry(cell(comps.point)).or_insert(Color3::BLACK) += power;
+                }
+                return;
+            }
+
+            // Transparent surfaces refract, falling back to reflection when
+            // the light is totally internally reflected.
+            let refracted = (material.transparency > 0.0)
+                .then(|| (-comps.eyev).refract(comps.normalv, comps.n1 / comps.n2))
+                .flatten();
+            (ray, power) = match refracted {
+                Some(direction) => (
+                    Ray::new(comps.under_point, direction),
+                    power * material.transparency,
+                ),
+                // Total internal reflection keeps all of the photon's power
+                None if material.transparency > 0.0 => (Ray::new(comps.over_point, comps.reflectv), power),
+                None => (
+                    Ray::new(comps.over_point, comps.reflectv),
+                    power * material.reflective,
+                ),
+            };
+        }
+    }
+
+    /// Returns the light the photons stored around `comps` reflect toward
+    /// the eye.
+    fn radiance(&self, comps: &Computations) -> Color3 {
+        let material = comps.object.material();
+        let Some(&power) = self.cells.get(&cell(comps.point)) else {
+            return Color3::BLACK;
+        };
+        let irradiance = power / (World::CAUSTIC_CELL_SIZE * World::CAUSTIC_CELL_SIZE);
+        material.color * irradiance * material.diffuse
+    }
+}
+
+/// Returns whether photons bounce off or pass through `shape` rather than
+/// landing on it.
+fn is_specular(shape: &dyn Shape) -> bool {
+    let material = shape.material();
+    material.reflective > 0.0 || material.transparency > 0.0
+}
+
+/// Returns the grid cell containing `point`.
+fn cell(point: Point3) -> [i64; 3] {
+    let index = |v: f64| math::floor(v / World::CAUSTIC_CELL_SIZE) as i64;
+    [index(point.x()), index(point.y()), index(point.z())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Plane, Sphere};
+    use crate::prelude::{FRAC_PI_3, point, vector};
+    use crate::primitives::Mat4;
+    use crate::shading::{Material, PointLight};
+
+    fn glass_over_floor() -> (World, Camera) {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(0, 10, 0), Color3::WHITE));
+        w.add_object(Plane::new().with_material(Material::matte(Color3::WHITE)))
+            .unwrap();
+        w.add_object(
+            Sphere::new()
+                .with_transform(Mat4::translation(0.0, 2.0, 0.0))
+                .with_material(Material::dielectric(1.5)),
+        )
+        .unwrap();
+
+        let transform = Mat4::view_transform(point(0, 1, -8), point(0, 0, 0), vector(0, 1, 0));
+        (w, Camera::new(21, 21, FRAC_PI_3).with_transform(transform))
+    }
+
+    #[test]
+    fn test_caustics_brighten_the_floor_beneath_a_glass_sphere() {
+        let (w, camera) = glass_over_floor();
+        let plain = camera.render(&w);
+        let caustics = w.render_with_caustics(&camera, 20_000);
+
+        let (before, after) = (plain.pixel_at(10, 10), caustics.pixel_at(10, 10));
+        assert!(after.r() > before.r() + 0.5, "{before:?} -> {after:?}");
+        // The floor near the camera is lit directly and gathers no photons
+        assert_eq!(caustics.pixel_at(10, 20), plain.pixel_at(10, 20));
+    }
+
+    #[test]
+    fn test_caustics_are_reproducible_and_vanish_without_photons() {
+        let (w, camera) = glass_over_floor();
+        assert_eq!(w.render_with_caustics(&camera, 0), camera.render(&w));
+        assert_eq!(
+            w.render_with_caustics(&camera, 500),
+            w.render_with_caustics(&camera, 500)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use crate::cmp::epsilon::EPSILON;
+use crate::geometry::{Intersection, Ray, Shape};
+use crate::math;
+use crate::primitives::{Point3, Vec3};
+
+/// Values about an intersection that shading needs, computed once up front.
+#[derive(Clone, Copy, Debug)]
+pub struct Computations<'a> {
+    /// Distance along the ray to the intersection.
+    pub t:           f64,
+    /// The object that was hit.
+    pub object:      &'a dyn Shape,
+    /// The world-space point of intersection.
+    pub point:       Point3,
+    /// The point nudged off the surface along the normal, so rays cast from
+    /// it do not immediately strike the same surface again.
+    pub over_point:  Point3,
+    /// The unit vector pointing back toward the eye.
+    pub eyev:        Vec3,
+    /// The unit surface normal, flipped to face the eye.
+    pub normalv:     Vec3,
+    /// The incoming ray's direction mirrored about the normal.
+    pub reflectv:    Vec3,
+    /// The point nudged just below the surface, where refracted rays start.
+    pub under_point: Point3,
+    /// Whether the ray originated inside the object.
+    pub inside:      bool,
+    /// Refractive index of the material the ray is leaving.
+    pub n1:          f64,
+    /// Refractive index of the material the ray is entering.
+    pub n2:          f64,
+}
+
+impl<'a> Computations<'a> {
+    /// Precomputes the shading state for `hit` along `ray`.
+    ///
+    /// `xs` is the full sorted list of intersections along `ray`, which is
+    /// needed to tell which materials the ray is passing between at `hit`.
+    pub fn prepare(hit: &Intersection<'a>, ray: &Ray, xs: &[Intersection<'a>]) -> Self {
+        let object = hit.object();
+        let point = ray.position(hit.t());
+        let eyev = -ray.direction();
+        let mut normalv = object.normal_at_hit(point, hit);
+        let (n1, n2) = refractive_indices(hit, xs);
+
+        let inside = normalv.dot(eyev) < 0.0;
+        if inside {
+            normalv = -normalv;
+        }
+
+        Self {
+            t: hit.t(),
+            object,
+            point,
+            over_point: point + normalv * EPSILON,
+            eyev,
+            normalv,
+            reflectv: ray.direction().reflect(normalv),
+            under_point: point - normalv * EPSILON,
+            inside,
+            n1,
+            n2,
+        }
+    }
+}
+
+/// Returns the refractive indices on either side of `hit`.
+///
+/// Walks `xs` in order, tracking which objects the ray is currently inside:
+/// `n1` belongs to the innermost object just before `hit` and `n2` to the
+/// innermost object just after it, with empty space counting as `1.0`.
+fn refractive_indices(hit: &Intersection, xs: &[Intersection]) -> (f64, f64) {
+    let innermost =
+        |containers: &[&dyn Shape]| containers.last().map_or(1.0, |o| o.material().refractive_index);
+
+    let mut containers: Vec<&dyn Shape> = Vec::new();
+    for i in xs {
+        let n1 = innermost(&containers);
+
+        match containers.iter().position(|&o| core::ptr::addr_eq(o, i.object())) {
+            Some(index) => {
+                containers.remove(index);
+            },
+            None => containers.push(i.object()),
+        }
+
+        if i == hit {
+            return (n1, innermost(&containers));
+        }
+    }
+
+    (1.0, 1.0)
+}
+
+/// Returns the fraction of light reflected at the surface described by
+/// `comps`, using Schlick's approximation of the Fresnel equations.
+pub fn schlick(comps: &Computations) -> f64 {
+    let mut cos = comps.eyev.dot(comps.normalv);
+
+    // Total internal reflection can only happen going into a less dense
+    // medium.
+    if comps.n1 > comps.n2 {
+        let n = comps.n1 / comps.n2;
+        let sin2_t = n * n * (1.0 - cos * cos);
+        if sin2_t > 1.0 {
+            return 1.0;
+        }
+        cos = math::sqrt(1.0 - sin2_t);
+    }
+
+    let r0 = ((comps.n1 - comps.n2) / (comps.n1 + comps.n2)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+}
+
+/// Precomputes the shading state for `hit` along `ray`, given every
+/// intersection `xs` along it.
+#[inline]
+pub fn prepare_computations<'a>(
+    hit: &Intersection<'a>,
+    ray: &Ray,
+    xs: &[Intersection<'a>],
+) -> Computations<'a> {
+    Computations::prepare(hit, ray, xs)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+    use super::*;
+    use crate::cmp::float::is_equal;
+    use crate::geometry::{Plane, Sphere};
+    use crate::prelude::{Mat4, point, vector};
+
+    #[test]
+    fn test_precomputing_the_state_of_an_intersection() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let shape = Sphere::new();
+        let i = Intersection::new(4.0, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert_eq!(comps.t, i.t());
+        assert!(core::ptr::addr_eq(comps.object, &shape));
+        assert_eq!(comps.point, point(0, 0, -1));
+        assert_eq!(comps.eyev, vector(0, 0, -1));
+        assert_eq!(comps.normalv, vector(0, 0, -1));
+    }
+
+    #[test]
+    fn test_precomputing_the_reflection_vector() {
+        let shape = Plane::new();
+        let r = Ray::new(point(0, 1, -1), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert_eq!(comps.reflectv, vector(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn test_hit_when_an_intersection_occurs_on_the_outside() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let shape = Sphere::new();
+        let i = Intersection::new(4.0, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn test_hit_when_an_intersection_occurs_on_the_inside() {
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        let shape = Sphere::new();
+        let i = Intersection::new(1.0, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert_eq!(comps.point, point(0, 0, 1));
+        assert_eq!(comps.eyev, vector(0, 0, -1));
+        assert!(comps.inside);
+        assert_eq!(comps.normalv, vector(0, 0, -1));
+    }
+
+    #[test]
+    fn test_hit_should_offset_the_point() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let shape = Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert!(comps.over_point.z() < -EPSILON / 2.0);
+        assert!(comps.point.z() > comps.over_point.z());
+    }
+
+    #[test]
+    fn test_under_point_is_offset_below_the_surface() {
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let shape = Sphere::glass().with_transform(Mat4::translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &shape);
+        let comps = prepare_computations(&i, &r, &[i]);
+        assert!(comps.under_point.z() > EPSILON / 2.0);
+        assert!(comps.point.z() < comps.under_point.z());
+    }
+
+    #[test]
+    fn test_n1_and_n2_entering_and_leaving_a_single_sphere() {
+        let shape = Sphere::glass();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let xs = [Intersection::new(4.0, &shape), Intersection::new(6.0, &shape)];
+
+        let comps = prepare_computations(&xs[0], &r, &xs);
+        assert_eq!((comps.n1, comps.n2), (1.0, 1.5));
+        let comps = prepare_computations(&xs[1], &r, &xs);
+        assert_eq!((comps.n1, comps.n2), (1.5, 1.0));
+    }
+
+    #[test]
+    fn test_n1_and_n2_at_various_intersections() {
+        let glass = |transform: Mat4, refractive_index: f64| {
+            let sphere = Sphere::glass().with_transform(transform);
+            let material = sphere.material().with_refractive_index(refractive_index);
+            sphere.with_material(material)
+        };
+        let a = glass(Mat4::scaling(2.0, 2.0, 2.0), 1.5);
+        let b = glass(Mat4::translation(0.0, 0.0, -0.25), 2.0);
+        let c = glass(Mat4::translation(0.0, 0.0, 0.25), 2.5);
+        let r = Ray::new(point(0, 0, -4), vector(0, 0, 1));
+        let xs = [
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ];
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+
+        for (hit, n) in xs.iter().zip(expected) {
+            let comps = prepare_computations(hit, &r, &xs);
+            assert_eq!((comps.n1, comps.n2), n, "at t = {}", hit.t());
+        }
+    }
+
+    #[test]
+    fn test_schlick_approximation_under_total_internal_reflection() {
+        let shape = Sphere::glass();
+        let r = Ray::new(point(0.0, 0.0, FRAC_1_SQRT_2), vector(0, 1, 0));
+        let xs = [
+            Intersection::new(-FRAC_1_SQRT_2, &shape),
+            Intersection::new(FRAC_1_SQRT_2, &shape),
+        ];
+        let comps = prepare_computations(&xs[1], &r, &xs);
+        assert_eq!(schlick(&comps), 1.0);
+    }
+
+    #[test]
+    fn test_schlick_approximation_with_a_perpendicular_viewing_angle() {
+        let shape = Sphere::glass();
+        let r = Ray::new(point(0, 0, 0), vector(0, 1, 0));
+        let xs = [Intersection::new(-1.0, &shape), Intersection::new(1.0, &shape)];
+        let comps = prepare_computations(&xs[1], &r, &xs);
+        assert!(is_equal(schlick(&comps), 0.04));
+    }
+
+    #[test]
+    fn test_schlick_approximation_with_small_angle_and_n2_greater_than_n1() {
+        let shape = Sphere::glass();
+        let r = Ray::new(point(0.0, 0.99, -2.0), vector(0, 0, 1));
+        let xs = [Intersection::new(1.8589, &shape)];
+        let comps = prepare_computations(&xs[0], &r, &xs);
+        assert!(is_equal(schlick(&comps), 0.48873), "{}", schlick(&comps));
+    }
+}
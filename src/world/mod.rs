@@ -1 +1,6 @@
+mod caustics;
+mod computations;
+mod scene;
 
+pub use computations::{Computations, prepare_computations, schlick};
+pub use scene::{LightId, ObjectId, World};
@@ -0,0 +1,1085 @@
+use core::fmt;
+use std::sync::OnceLock;
+
+use crate::error::{ShadingError, WorldError};
+use crate::geometry::{Bounds, Intersection, Ray, Shape, Sphere, hit, includes, intersections};
+use crate::math::Interval;
+use crate::primitives::{Color3, Mat4, Point3};
+use crate::shading::{Light, Material, PointLight, lighting};
+use crate::world::{Computations, schlick};
+
+/// Identifies an object added to a [`World`].
+///
+/// Ids are never reused, so an id stays invalid after its object is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ObjectId(u64);
+
+/// Identifies a light added to a [`World`].
+///
+/// Ids are never reused, so an id stays invalid after its light is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LightId(u64);
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "object #{}", self.0) }
+}
+
+impl fmt::Display for LightId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "light #{}", self.0) }
+}
+
+/// A collection of objects and the lights that illuminate them.
+///
+/// The number of objects is capped at [`max_objects`](Self::max_objects) to
+/// catch runaway scene generation early.
+#[derive(Debug)]
+pub struct World {
+    objects:     Vec<Box<dyn Shape>>,
+    lights:      Vec<Light>,
+    object_ids:  Vec<ObjectId>,
+    light_ids:   Vec<LightId>,
+    next_id:     u64,
+    max_objects: usize,
+    /// World-space bounds of each object, computed on the first intersection
+    /// and discarded whenever the objects may have changed.
+    bounds:      OnceLock<Vec<Bounds>>,
+    /// The `(top, bottom)` colors seen by rays that miss every object, or
+    /// `None` for black.
+    sky:         Option<(Color3, Color3)>,
+}
+
+impl World {
+    /// How many times a ray may bounce off reflective or refractive surfaces
+    /// when no limit is given.
+    pub const DEFAULT_DEPTH: usize = 5;
+    /// The default cap on the number of objects in a world.
+    pub const DEFAULT_MAX_OBJECTS: usize = 10_000;
+
+    /// Creates an empty world with no objects and no lights.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            objects:     Vec::new(),
+            lights:      Vec::new(),
+            object_ids:  Vec::new(),
+            light_ids:   Vec::new(),
+            next_id:     0,
+            max_objects: Self::DEFAULT_MAX_OBJECTS,
+            bounds:      OnceLock::new(),
+            sky:         None,
+        }
+    }
+
+    /// Creates the two-sphere scene lit from the upper left that the book
+    /// uses throughout its world tests.
+    #[must_use]
+    pub fn default_world() -> Self {
+        let outer = Sphere::new().with_material(Material {
+            color: Color3::new(0.8, 1.0, 0.6),
+            diffuse: 0.7,
+            specular: 0.2,
+            ..Material::new()
+        });
+        let inner = Sphere::new().with_transform(Mat4::scaling(0.5, 0.5, 0.5));
+
+        let mut world = Self::new();
+        world
+            .add_object(outer)
+            .expect("default world fits within the object limit");
+        world
+            .add_object(inner)
+            .expect("default world fits within the object limit");
+        world.add_light(PointLight::new(Point3::new(-10.0, 10.0, -10.0), Color3::WHITE));
+        world
+    }
+
+    /// Returns the world with room for at most `max_objects` objects.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_objects(mut self, max_objects: usize) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Returns the world with a sky behind its objects, blending from
+    /// `bottom` for rays heading straight down to `top` for rays heading
+    /// straight up.
+    #[inline]
+    #[must_use]
+    pub const fn with_sky_gradient(mut self, top: Color3, bottom: Color3) -> Self {
+        self.sky = Some((top, bottom));
+        self
+    }
+
+    /// Sets the largest number of objects the world may hold.
+    ///
+    /// Objects already in the world are kept even if they exceed the new
+    /// limit; only further additions are refused.
+    #[inline]
+    pub const fn set_max_objects(&mut self, max_objects: usize) { self.max_objects = max_objects; }
+
+    /// Returns the largest number of objects the world may hold.
+    #[inline]
+    pub const fn max_objects(&self) -> usize { self.max_objects }
+
+    /// Adds `object` to the world, returning the id that refers to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::TooManyObjects`] if the world already holds
+    /// [`max_objects`](Self::max_objects) objects.
+    pub fn add_object(&mut self, object: impl Into<Box<dyn Shape>>) -> crate::Result<ObjectId> {
+        if self.objects.len() >= self.max_objects {
+            return Err(WorldError::TooManyObjects {
+                count:     self.objects.len() + 1,
+                max_count: self.max_objects,
+            }
+            .into());
+        }
+
+        let id = ObjectId(self.next_id());
+        self.bounds.take();
+        self.objects.push(object.into());
+        self.object_ids.push(id);
+        Ok(id)
+    }
+
+    /// Adds `light` to the world, returning the id that refers to it.
+    pub fn add_light(&mut self, light: impl Into<Light>) -> LightId {
+        let id = LightId(self.next_id());
+        self.lights.push(light.into());
+        self.light_ids.push(id);
+        id
+    }
+
+    /// Returns the object with the given `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::ObjectNotFound`] if no object has that id.
+    pub fn get_object(&self, id: ObjectId) -> crate::Result<&dyn Shape> {
+        let index = self.object_index(id)?;
+        Ok(self.objects[index].as_ref())
+    }
+
+    /// Returns the object with the given `id` for in-place editing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::ObjectNotFound`] if no object has that id.
+    pub fn get_object_mut(&mut self, id: ObjectId) -> crate::Result<&mut dyn Shape> {
+        let index = self.object_index(id)?;
+        self.bounds.take();
+        Ok(self.objects[index].as_mut())
+    }
+
+    /// Removes the object with the given `id` from the world.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::ObjectNotFound`] if no object has that id.
+    pub fn remove_object(&mut self, id: ObjectId) -> crate::Result<()> {
+        let index = self.object_index(id)?;
+        self.bounds.take();
+        self.objects.remove(index);
+        self.object_ids.remove(index);
+        Ok(())
+    }
+
+    /// Returns the light with the given `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::LightNotFound`] if no light has that id.
+    pub fn get_light(&self, id: LightId) -> crate::Result<&Light> {
+        let index = self.light_index(id)?;
+        Ok(&self.lights[index])
+    }
+
+    /// Returns the light with the given `id` for in-place editing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::LightNotFound`] if no light has that id.
+    pub fn get_light_mut(&mut self, id: LightId) -> crate::Result<&mut Light> {
+        let index = self.light_index(id)?;
+        Ok(&mut self.lights[index])
+    }
+
+    /// Removes the light with the given `id` from the world.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::LightNotFound`] if no light has that id.
+    pub fn remove_light(&mut self, id: LightId) -> crate::Result<()> {
+        let index = self.light_index(id)?;
+        self.lights.remove(index);
+        self.light_ids.remove(index);
+        Ok(())
+    }
+
+    /// Returns the objects in the world.
+    #[inline]
+    pub fn objects(&self) -> &[Box<dyn Shape>] { &self.objects }
+
+    /// Returns the objects in the world for in-place editing.
+    #[inline]
+    pub fn objects_mut(&mut self) -> &mut [Box<dyn Shape>] {
+        self.bounds.take();
+        &mut self.objects
+    }
+
+    /// Splits every group in the world into a bounding volume hierarchy,
+    /// as [`Shape::divide`] does for a single shape.
+    pub fn divide(&mut self, threshold: usize) {
+        self.bounds.take();
+        for object in &mut self.objects {
+            object.divide(threshold);
+        }
+    }
+
+    /// Returns the id of the object that is `shape` or, for groups and CSG
+    /// shapes, contains it, or `None` if `shape` is not in the world.
+    ///
+    /// This maps the shape of an [`Intersection`] back to the object it
+    /// belongs to.
+    pub fn object_id_of(&self, shape: &dyn Shape) -> Option<ObjectId> {
+        let index = self
+            .objects
+            .iter()
+            .position(|object| includes(object.as_ref(), shape))?;
+        Some(self.object_ids[index])
+    }
+
+    /// Returns the lights in the world.
+    #[inline]
+    pub fn lights(&self) -> &[Light] { &self.lights }
+
+    /// Returns the lights in the world for in-place editing.
+    #[inline]
+    pub fn lights_mut(&mut self) -> &mut [Light] { &mut self.lights }
+
+    /// Checks that the world has something to render.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::EmptyScene`] if the world has no objects, or
+    /// [`WorldError::NoLights`] if it has objects but no lights.
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.objects.is_empty() {
+            return Err(WorldError::EmptyScene.into());
+        }
+        if self.lights.is_empty() {
+            return Err(WorldError::NoLights.into());
+        }
+        Ok(())
+    }
+
+    const fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn object_index(&self, id: ObjectId) -> crate::Result<usize> {
+        self.object_ids
+            .iter()
+            .position(|&other| other == id)
+            .ok_or_else(|| WorldError::ObjectNotFound { id: id.to_string() }.into())
+    }
+
+    fn light_index(&self, id: LightId) -> crate::Result<usize> {
+        self.light_ids
+            .iter()
+            .position(|&other| other == id)
+            .ok_or_else(|| WorldError::LightNotFound { id: id.to_string() }.into())
+    }
+}
+
+impl Default for World {
+    fn default() -> Self { Self::new() }
+}
+
+impl World {
+    /// Intersects `ray` with every object, returning all intersections sorted
+    /// by increasing `t`.
+    ///
+    /// Primitives whose world-space bounding box the ray misses are skipped
+    /// without testing their geometry. Groups and CSG shapes already check
+    /// their own bounds, so they are passed the ray directly.
+    pub fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let bounds = self.bounds.get_or_init(|| {
+            self.objects
+                .iter()
+                .map(|object| object.parent_space_bounds())
+                .collect()
+        });
+
+        intersections(
+            self.objects
+                .iter()
+                .zip(bounds)
+                .filter(|(object, bounds)| !object.children().is_empty() || bounds.intersects(ray))
+                .flat_map(|(object, _)| object.intersect(ray)),
+        )
+    }
+
+    /// Returns the color at a precomputed intersection, summing the
+    /// contribution of every light plus whatever the surface reflects and
+    /// transmits. Points hidden from a light receive only its ambient term.
+    ///
+    /// `remaining` bounds how many more bounces secondary rays may take.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color3 {
+        let material = comps.object.material();
+        let surface = self.lights.iter().fold(Color3::BLACK, |acc, light| {
+            let in_shadow = self.is_shadowed(light, comps.over_point);
+            acc + lighting(
+                material,
+                light,
+                comps.over_point,
+                comps.eyev,
+                comps.normalv,
+                in_shadow,
+            )
+        });
+
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        // Surfaces that both reflect and transmit split light between the two
+        // according to the Fresnel effect.
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = schlick(comps);
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    /// Returns the color reflected off the surface at `comps`, or black if
+    /// the surface is not reflective or no bounces remain.
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color3 {
+        let reflective = comps.object.material().reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return Color3::BLACK;
+        }
+
+        let reflect_ray = Ray::new(comps.over_point, comps.reflectv);
+        self.color_at(&reflect_ray, remaining - 1) * reflective
+    }
+
+    /// Returns the color transmitted through the surface at `comps`, or
+    /// black if the surface is opaque, no bounces remain, or the light is
+    /// totally internally reflected.
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color3 {
+        let transparency = comps.object.material().transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return Color3::BLACK;
+        }
+
+        let Some(direction) = (-comps.eyev).refract(comps.normalv, comps.n1 / comps.n2) else {
+            return Color3::BLACK;
+        };
+        let refract_ray = Ray::new(comps.under_point, direction);
+        self.color_at(&refract_ray, remaining - 1) * transparency
+    }
+
+    /// Like [`reflected_color`](Self::reflected_color), but reports running
+    /// out of bounces on a reflective surface instead of returning black.
+    ///
+    /// `depth` is the number of bounces already taken to reach `comps`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ShadingError::RecursionLimitExceeded`] if the surface is
+    /// reflective and `depth` has reached `max_depth`.
+    pub fn try_reflected_color(
+        &self,
+        comps: &Computations,
+        depth: usize,
+        max_depth: usize,
+    ) -> crate::Result<Color3> {
+        if comps.object.material().reflective == 0.0 {
+            return Ok(Color3::BLACK);
+        }
+        if depth >= max_depth {
+            return Err(ShadingError::RecursionLimitExceeded {
+                depth,
+                max_depth,
+                ray: "reflection".to_string(),
+            }
+            .into());
+        }
+
+        Ok(self.reflected_color(comps, max_depth - depth))
+    }
+
+    /// Returns whether some object lies between `point` and `light`.
+    pub fn is_shadowed(&self, light: &Light, point: Point3) -> bool {
+        let v = light.position() - point;
+        let distance = v.length();
+        let ray = Ray::new(point, v.normalize());
+
+        let xs = self.intersect(&ray);
+        hit(&xs).is_some_and(|hit| Interval::new(0.0, distance).surrounds(hit.t()))
+    }
+
+    /// Returns the color seen along `ray`, or the sky (black unless set by
+    /// [`with_sky_gradient`](Self::with_sky_gradient)) if it hits nothing.
+    ///
+    /// `remaining` bounds how many more bounces reflected rays may take.
+    pub fn color_at(&self, ray: &Ray, remaining: usize) -> Color3 {
+        let xs = self.intersect(ray);
+        match hit(&xs) {
+            Some(hit) => self.shade_hit(&Computations::prepare(hit, ray, &xs), remaining),
+            None => self.sky_color(ray),
+        }
+    }
+
+    /// Returns the sky color in the direction of `ray`.
+    fn sky_color(&self, ray: &Ray) -> Color3 {
+        self.sky.map_or(Color3::BLACK, |(top, bottom)| {
+            let t = 0.5 * (ray.direction().normalize().y() + 1.0);
+            bottom.lerp(top, t)
+        })
+    }
+
+    /// Returns the color seen along `ray`, allowing
+    /// [`DEFAULT_DEPTH`](Self::DEFAULT_DEPTH) bounces.
+    #[inline]
+    pub fn color_at_default(&self, ray: &Ray) -> Color3 { self.color_at(ray, Self::DEFAULT_DEPTH) }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+    use super::*;
+    use crate::error::TracerError;
+    use crate::geometry::{Group, Plane};
+    use crate::prelude::{PI, color, point, vector};
+
+    #[test]
+    fn test_creating_a_world() {
+        let w = World::new();
+        assert!(w.objects().is_empty());
+        assert!(w.lights().is_empty());
+    }
+
+    #[test]
+    fn test_adding_and_retrieving_objects_by_id() {
+        let mut w = World::new();
+        let a = w.add_object(Sphere::new()).unwrap();
+        let b = w
+            .add_object(Plane::new().with_transform(Mat4::translation(0.0, -1.0, 0.0)))
+            .unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(w.get_object(a).unwrap().transform(), Mat4::IDENTITY);
+        assert_eq!(
+            w.get_object(b).unwrap().transform(),
+            Mat4::translation(0.0, -1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_removing_an_object_keeps_other_ids_valid() {
+        let mut w = World::new();
+        let a = w.add_object(Sphere::new()).unwrap();
+        let b = w
+            .add_object(Sphere::new().with_transform(Mat4::scaling(2.0, 2.0, 2.0)))
+            .unwrap();
+
+        w.remove_object(a).unwrap();
+
+        assert_eq!(w.objects().len(), 1);
+        assert_eq!(w.get_object(b).unwrap().transform(), Mat4::scaling(2.0, 2.0, 2.0));
+        let err = w.get_object(a).unwrap_err();
+        let TracerError::World(err) = err else {
+            panic!("expected a world error, got {err:?}")
+        };
+        assert_eq!(err, WorldError::ObjectNotFound { id: a.to_string() });
+        assert!(w.remove_object(a).is_err());
+    }
+
+    #[test]
+    fn test_editing_an_object_by_id() {
+        let mut w = World::new();
+        let id = w.add_object(Sphere::new()).unwrap();
+        w.get_object_mut(id)
+            .unwrap()
+            .set_transform(Mat4::translation(1.0, 0.0, 0.0));
+        assert_eq!(w.objects()[0].transform(), Mat4::translation(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_adding_retrieving_and_removing_lights_by_id() {
+        let mut w = World::new();
+        let a = w.add_light(Light::point(point(0, 0, 0), color(1, 1, 1)));
+        let b = w.add_light(Light::point(point(1, 2, 3), color(0.5, 0.5, 0.5)));
+
+        assert_eq!(w.get_light(b).unwrap().position(), point(1, 2, 3));
+        *w.get_light_mut(a).unwrap() = Light::point(point(4, 5, 6), color(1, 1, 1));
+        assert_eq!(w.lights()[0].position(), point(4, 5, 6));
+
+        w.remove_light(a).unwrap();
+        assert_eq!(w.lights().len(), 1);
+        let err = w.remove_light(a).unwrap_err();
+        let TracerError::World(err) = err else {
+            panic!("expected a world error, got {err:?}")
+        };
+        assert_eq!(err, WorldError::LightNotFound { id: a.to_string() });
+    }
+
+    #[test]
+    fn test_adding_past_the_object_limit_fails() {
+        let mut w = World::new().with_max_objects(2);
+        w.add_object(Sphere::new()).unwrap();
+        w.add_object(Sphere::new()).unwrap();
+
+        let err = w.add_object(Sphere::new()).unwrap_err();
+        let TracerError::World(err) = err else {
+            panic!("expected a world error, got {err:?}")
+        };
+        assert_eq!(err, WorldError::TooManyObjects {
+            count:     3,
+            max_count: 2,
+        });
+        assert_eq!(w.objects().len(), 2);
+    }
+
+    #[test]
+    fn test_worlds_start_with_the_default_object_limit() {
+        assert_eq!(World::new().max_objects(), World::DEFAULT_MAX_OBJECTS);
+        assert_eq!(World::default().max_objects(), World::DEFAULT_MAX_OBJECTS);
+    }
+
+    #[test]
+    fn test_validating_an_empty_world() {
+        let mut w = World::new();
+        w.add_light(Light::point(point(0, 0, 0), color(1, 1, 1)));
+        let TracerError::World(err) = w.validate().unwrap_err() else {
+            panic!("expected a world error")
+        };
+        assert_eq!(err, WorldError::EmptyScene);
+    }
+
+    #[test]
+    fn test_validating_a_world_without_lights() {
+        let mut w = World::new();
+        w.add_object(Sphere::new()).unwrap();
+        let TracerError::World(err) = w.validate().unwrap_err() else {
+            panic!("expected a world error")
+        };
+        assert_eq!(err, WorldError::NoLights);
+    }
+
+    #[test]
+    fn test_validating_a_renderable_world() {
+        assert!(World::default_world().validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_world() {
+        let w = World::default_world();
+        let light = Light::point(point(-10, 10, -10), color(1, 1, 1));
+        assert_eq!(w.lights(), [light]);
+        assert_eq!(w.objects().len(), 2);
+        assert_eq!(w.objects()[0].material().color, color(0.8, 1.0, 0.6));
+        assert_eq!(w.objects()[1].transform(), Mat4::scaling(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_intersect_a_world_with_a_ray() {
+        let w = World::default_world();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let ts: Vec<_> = w.intersect(&r).iter().map(Intersection::t).collect();
+        assert_eq!(ts, [4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn test_bounds_rejection_does_not_change_intersections() {
+        let mut w = World::new();
+        w.add_object(Plane::new().with_transform(Mat4::translation(0.0, -6.0, 0.0)))
+            .unwrap();
+        for i in 0..60_u32 {
+            let f = f64::from(i);
+            let (x, y, z) = ((f * 7.3) % 12.0 - 6.0, (f * 3.1) % 8.0 - 4.0, (f * 5.7) % 10.0);
+            let radius = 0.2 + (f * 0.37) % 0.6;
+            let transform = Mat4::translation(x, y, z) * Mat4::scaling(radius, radius, radius);
+            w.add_object(Sphere::new().with_transform(transform)).unwrap();
+        }
+
+        let mut hits = 0;
+        for i in -10..=10 {
+            for j in -10..=10 {
+                let target = point(f64::from(i) * 0.8, f64::from(j) * 0.8, 5.0);
+                let origin = point(0, 0, -10);
+                let r = Ray::new(origin, (target - origin).normalize());
+
+                let unfiltered = intersections(w.objects().iter().flat_map(|o| o.intersect(&r)));
+                let xs = w.intersect(&r);
+                assert_eq!(xs, unfiltered, "ray through {target:?}");
+                hits += usize::from(xs.len() > 1);
+            }
+        }
+        assert!(hits > 0);
+
+        // Rays that start just outside a box and enter it at a shallow angle
+        w.add_object(Sphere::new().with_transform(Mat4::translation(20.0, 0.0, 0.0)))
+            .unwrap();
+        for r in [
+            Ray::new(point(21.000001, 0, -5), vector(-5e-6, 0, 1)),
+            Ray::new(point(20, -1.000001, -5), vector(0, 5e-6, 1)),
+            Ray::new(point(20, -5, 1.000001), vector(0, 1, -5e-6)),
+            Ray::new(point(18.999999, 5, 0), vector(5e-6, -1, 0)),
+        ] {
+            let unfiltered = intersections(w.objects().iter().flat_map(|o| o.intersect(&r)));
+            assert!(unfiltered.len() >= 2, "{r:?}");
+            assert_eq!(w.intersect(&r), unfiltered, "{r:?}");
+        }
+    }
+
+    #[test]
+    fn test_finding_the_object_that_contains_a_shape() {
+        let mut w = World::new();
+        let sphere = w.add_object(Sphere::new()).unwrap();
+        let group = w
+            .add_object(Group::new().with_child(Group::new().with_child(Sphere::new())))
+            .unwrap();
+
+        let nested = w.objects()[1].children()[0].children()[0].as_ref();
+        assert_eq!(w.object_id_of(w.objects()[0].as_ref()), Some(sphere));
+        assert_eq!(w.object_id_of(nested), Some(group));
+        assert_eq!(w.object_id_of(&Sphere::new()), None);
+    }
+
+    #[test]
+    fn test_dividing_a_world_keeps_its_primitives_and_intersections() {
+        fn primitives(shapes: &[Box<dyn Shape>]) -> usize {
+            shapes
+                .iter()
+                .map(|s| {
+                    if s.children().is_empty() {
+                        1
+                    } else {
+                        primitives(s.children())
+                    }
+                })
+                .sum()
+        }
+
+        let mut w = World::default_world();
+        let mut outer = Group::new().with_transform(Mat4::scaling(0.5, 0.5, 0.5));
+        for i in -3..=3 {
+            let mut row = Group::new().with_transform(Mat4::translation(0.0, f64::from(i) * 3.0, 4.0));
+            for j in -3..=3 {
+                row.add_child(Sphere::new().with_transform(Mat4::translation(
+                    f64::from(j) * 3.0,
+                    0.0,
+                    0.0,
+                )));
+            }
+            outer.add_child(row);
+        }
+        w.add_object(outer).unwrap();
+
+        let rays = [
+            Ray::new(point(0, 0, -5), vector(0, 0, 1)),
+            Ray::new(point(0, -1.5, -5), vector(0.05, 0, 1).normalize()),
+            Ray::new(point(-10, 0.2, 2), vector(1, 0, 0)),
+        ];
+        let ts = |w: &World| rays.map(|r| w.intersect(&r).iter().map(Intersection::t).collect::<Vec<_>>());
+        let (count, before) = (primitives(w.objects()), ts(&w));
+
+        w.divide(2);
+        assert_eq!(primitives(w.objects()), count);
+        assert_eq!(ts(&w), before);
+        assert!(before.iter().all(|xs| !xs.is_empty()));
+    }
+
+    #[test]
+    fn test_intersect_sees_objects_moved_after_a_previous_intersection() {
+        let mut w = World::new();
+        let id = w.add_object(Sphere::new()).unwrap();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert_eq!(w.intersect(&r).len(), 2);
+
+        w.get_object_mut(id)
+            .unwrap()
+            .set_transform(Mat4::translation(5.0, 0.0, 0.0));
+        assert!(w.intersect(&r).is_empty());
+
+        w.objects_mut()[0].set_transform(Mat4::IDENTITY);
+        assert_eq!(w.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn test_shading_an_intersection() {
+        let w = World::default_world();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let i = Intersection::new(4.0, w.objects()[0].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.38066, 0.47583, 0.2855));
+    }
+
+    #[test]
+    fn test_shading_an_intersection_from_the_inside() {
+        let mut w = World::default_world();
+        w.lights_mut()[0] = Light::point(point(0.0, 0.25, 0.0), color(1, 1, 1));
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        let i = Intersection::new(0.5, w.objects()[1].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.90498, 0.90498, 0.90498));
+    }
+
+    #[test]
+    fn test_no_shadow_when_nothing_is_collinear_with_point_and_light() {
+        let w = World::default_world();
+        assert!(!w.is_shadowed(&w.lights()[0], point(0, 10, 0)));
+    }
+
+    #[test]
+    fn test_shadow_when_an_object_is_between_the_point_and_the_light() {
+        let w = World::default_world();
+        assert!(w.is_shadowed(&w.lights()[0], point(10, -10, 10)));
+    }
+
+    #[test]
+    fn test_no_shadow_when_an_object_is_behind_the_light() {
+        let w = World::default_world();
+        assert!(!w.is_shadowed(&w.lights()[0], point(-20, 20, -20)));
+    }
+
+    #[test]
+    fn test_no_shadow_when_an_object_is_behind_the_point() {
+        let w = World::default_world();
+        assert!(!w.is_shadowed(&w.lights()[0], point(-2, 2, -2)));
+    }
+
+    #[test]
+    fn test_shade_hit_is_given_an_intersection_in_shadow() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(0, 0, -10), color(1, 1, 1)));
+        w.add_object(Sphere::new()).unwrap();
+        w.add_object(Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 10.0)))
+            .unwrap();
+        let r = Ray::new(point(0, 0, 5), vector(0, 0, 1));
+        let i = Intersection::new(4.0, w.objects()[1].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_shade_hit_sums_the_contribution_of_every_light() {
+        let lights = [
+            PointLight::new(point(-10, 10, -10), color(1, 1, 1)),
+            PointLight::new(point(5, 5, -5), color(0.5, 0.25, 0.0)),
+        ];
+        let shade = |lights: &[PointLight]| {
+            let mut w = World::default_world();
+            w.lights_mut()[0] = lights[0].into();
+            for &light in &lights[1..] {
+                w.add_light(light);
+            }
+            let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+            let i = Intersection::new(4.0, w.objects()[0].as_ref());
+            w.shade_hit(&Computations::prepare(&i, &r, &[i]), 5)
+        };
+
+        let both = shade(&lights);
+        assert_eq!(both, shade(&lights[..1]) + shade(&lights[1..]));
+        assert_ne!(both, shade(&lights[..1]));
+    }
+
+    #[test]
+    fn test_shade_hit_is_lit_by_one_light_while_in_the_shadow_of_another() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(0, 0, -10), color(1, 1, 1)));
+        w.add_light(PointLight::new(point(0, 0, 5), color(1, 1, 1)));
+        w.add_object(Sphere::new()).unwrap();
+        w.add_object(Sphere::new().with_transform(Mat4::translation(0.0, 0.0, 10.0)))
+            .unwrap();
+        let r = Ray::new(point(0, 0, 5), vector(0, 0, 1));
+        let i = Intersection::new(4.0, w.objects()[1].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert!(w.is_shadowed(&w.lights()[0], comps.over_point));
+        assert!(!w.is_shadowed(&w.lights()[1], comps.over_point));
+        // Ambient from the hidden light plus a full highlight from the other
+        assert_eq!(w.shade_hit(&comps, 5), color(2, 2, 2));
+    }
+
+    #[test]
+    fn test_color_when_a_ray_misses() {
+        let w = World::default_world();
+        let r = Ray::new(point(0, 0, -5), vector(0, 1, 0));
+        assert_eq!(w.color_at(&r, 5), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_color_when_a_ray_misses_blends_the_sky_gradient() {
+        let (top, bottom) = (color(0.5, 0.7, 1.0), color(1, 1, 1));
+        let w = World::default_world().with_sky_gradient(top, bottom);
+        let color_towards = |direction| w.color_at(&Ray::new(point(0, 0, -5), direction), 5);
+
+        assert_eq!(color_towards(vector(0, 1, 0)), top);
+        assert_eq!(color_towards(vector(0, -2, 0)), bottom);
+        assert_eq!(color_towards(vector(1, 0, 0)), color(0.75, 0.85, 1.0));
+        // Rays that hit something are shaded as before
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert_eq!(w.color_at(&r, 5), World::default_world().color_at(&r, 5));
+    }
+
+    #[test]
+    fn test_color_when_a_ray_hits() {
+        let w = World::default_world();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        assert_eq!(w.color_at(&r, 5), color(0.38066, 0.47583, 0.2855));
+    }
+
+    #[test]
+    fn test_color_with_an_intersection_behind_the_ray() {
+        let mut w = World::default_world();
+        for object in w.objects_mut() {
+            let material = Material {
+                ambient: 1.0,
+                ..*object.material()
+            };
+            object.set_material(material);
+        }
+        let r = Ray::new(point(0.0, 0.0, 0.75), vector(0, 0, -1));
+        assert_eq!(w.color_at(&r, 5), w.objects()[1].material().color);
+    }
+
+    fn reflective_plane() -> Plane {
+        Plane::new()
+            .with_material(Material::new().with_reflective(0.5).unwrap())
+            .with_transform(Mat4::translation(0.0, -1.0, 0.0))
+    }
+
+    #[test]
+    fn test_reflected_color_for_a_nonreflective_material() {
+        let mut w = World::default_world();
+        let material = Material {
+            ambient: 1.0,
+            ..*w.objects()[1].material()
+        };
+        w.objects_mut()[1].set_material(material);
+        let r = Ray::new(point(0, 0, 0), vector(0, 0, 1));
+        let i = Intersection::new(1.0, w.objects()[1].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.reflected_color(&comps, 5), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_reflected_color_for_a_reflective_material() {
+        let mut w = World::default_world();
+        w.add_object(reflective_plane()).unwrap();
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, w.objects()[2].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        // The book prints color(0.19032, 0.2379, 0.14274), truncated beyond
+        // what our comparison epsilon tolerates.
+        assert_eq!(w.reflected_color(&comps, 5), color(0.19033, 0.23792, 0.14275));
+    }
+
+    #[test]
+    fn test_shade_hit_with_a_reflective_material() {
+        let mut w = World::default_world();
+        w.add_object(reflective_plane()).unwrap();
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, w.objects()[2].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.87676, 0.92434, 0.82917));
+    }
+
+    #[test]
+    fn test_color_at_with_mutually_reflective_surfaces() {
+        let mirror = Material::new().with_reflective(1.0).unwrap();
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(0, 0, 0), color(1, 1, 1)));
+        w.add_object(
+            Plane::new()
+                .with_material(mirror)
+                .with_transform(Mat4::translation(0.0, -1.0, 0.0)),
+        )
+        .unwrap();
+        w.add_object(
+            Plane::new()
+                .with_material(mirror)
+                .with_transform(Mat4::rotation_x(PI) * Mat4::translation(0.0, -1.0, 0.0)),
+        )
+        .unwrap();
+        let r = Ray::new(point(0, 0, 0), vector(0, 1, 0));
+        let c = w.color_at(&r, 5);
+        assert!(c.r().is_finite() && c.g().is_finite() && c.b().is_finite());
+    }
+
+    #[test]
+    fn test_color_at_default_terminates_between_parallel_mirrors() {
+        let mirror = Material::new().with_reflective(1.0).unwrap();
+        let mut w = World::new();
+        w.add_light(Light::point(point(0, 0, 0), color(1, 1, 1)));
+        w.add_object(
+            Plane::new()
+                .with_material(mirror)
+                .with_transform(Mat4::translation(0.0, -1.0, 0.0)),
+        )
+        .unwrap();
+        w.add_object(
+            Plane::new()
+                .with_material(mirror)
+                .with_transform(Mat4::translation(0.0, 1.0, 0.0)),
+        )
+        .unwrap();
+        let r = Ray::new(point(0, 0, 0), vector(0, 1, 0));
+        let c = w.color_at_default(&r);
+        assert!(c.is_finite());
+        assert_eq!(c, w.color_at(&r, World::DEFAULT_DEPTH));
+    }
+
+    #[test]
+    fn test_no_remaining_bounces_drops_reflection_and_refraction() {
+        let mut w = World::default_world();
+        w.add_object(reflective_plane()).unwrap();
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, w.objects()[2].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+
+        assert_eq!(w.reflected_color(&comps, 0), Color3::BLACK);
+        assert_eq!(w.refracted_color(&comps, 0), Color3::BLACK);
+        assert_ne!(w.reflected_color(&comps, 1), Color3::BLACK);
+    }
+
+    #[test]
+    fn test_reflected_color_at_the_maximum_recursive_depth() {
+        let mut w = World::default_world();
+        w.add_object(reflective_plane()).unwrap();
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, w.objects()[2].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+        assert_eq!(w.reflected_color(&comps, 0), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_try_reflected_color_reports_the_recursion_limit() {
+        let mut w = World::default_world();
+        w.add_object(reflective_plane()).unwrap();
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let i = Intersection::new(SQRT_2, w.objects()[2].as_ref());
+        let comps = Computations::prepare(&i, &r, &[i]);
+
+        let err = w.try_reflected_color(&comps, 5, 5).unwrap_err();
+        let TracerError::Shading(err) = err else {
+            panic!("expected a shading error, got {err:?}")
+        };
+        assert_eq!(err, ShadingError::RecursionLimitExceeded {
+            depth:     5,
+            max_depth: 5,
+            ray:       "reflection".to_string(),
+        });
+        assert_eq!(
+            w.try_reflected_color(&comps, 0, 5).unwrap(),
+            w.reflected_color(&comps, 5)
+        );
+    }
+
+    #[test]
+    fn test_refracted_color_with_an_opaque_surface() {
+        let w = World::default_world();
+        let shape = w.objects()[0].as_ref();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let xs = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.refracted_color(&comps, 5), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_refracted_color_at_the_maximum_recursive_depth() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(-10, 10, -10), color(1, 1, 1)));
+        w.add_object(Sphere::glass()).unwrap();
+        let shape = w.objects()[0].as_ref();
+        let r = Ray::new(point(0, 0, -5), vector(0, 0, 1));
+        let xs = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.refracted_color(&comps, 0), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_refracted_color_under_total_internal_reflection() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point(-10, 10, -10), color(1, 1, 1)));
+        w.add_object(Sphere::glass()).unwrap();
+        let shape = w.objects()[0].as_ref();
+        let r = Ray::new(point(0.0, 0.0, FRAC_1_SQRT_2), vector(0, 1, 0));
+        let xs = [
+            Intersection::new(-FRAC_1_SQRT_2, shape),
+            Intersection::new(FRAC_1_SQRT_2, shape),
+        ];
+        // Inside the sphere, so look at the second intersection.
+        let comps = Computations::prepare(&xs[1], &r, &xs);
+        assert_eq!(w.refracted_color(&comps, 5), color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_shade_hit_with_a_transparent_material() {
+        let mut w = World::default_world();
+        let floor = Material::new()
+            .with_transparency(0.5)
+            .unwrap()
+            .with_refractive_index(1.5);
+        w.add_object(
+            Plane::new()
+                .with_material(floor)
+                .with_transform(Mat4::translation(0.0, -1.0, 0.0)),
+        )
+        .unwrap();
+        let ball = Material::new()
+            .with_color(color(1, 0, 0))
+            .with_ambient(0.5)
+            .unwrap();
+        w.add_object(
+            Sphere::new()
+                .with_material(ball)
+                .with_transform(Mat4::translation(0.0, -3.5, -0.5)),
+        )
+        .unwrap();
+
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let xs = [Intersection::new(SQRT_2, w.objects()[2].as_ref())];
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.93642, 0.68642, 0.68642));
+    }
+
+    #[test]
+    fn test_shade_hit_with_a_reflective_transparent_material() {
+        let mut w = World::default_world();
+        let floor = Material::new()
+            .with_reflective(0.5)
+            .and_then(|m| m.with_transparency(0.5))
+            .unwrap()
+            .with_refractive_index(1.5);
+        w.add_object(
+            Plane::new()
+                .with_material(floor)
+                .with_transform(Mat4::translation(0.0, -1.0, 0.0)),
+        )
+        .unwrap();
+        let ball = Material::new()
+            .with_color(color(1, 0, 0))
+            .with_ambient(0.5)
+            .unwrap();
+        w.add_object(
+            Sphere::new()
+                .with_material(ball)
+                .with_transform(Mat4::translation(0.0, -3.5, -0.5)),
+        )
+        .unwrap();
+
+        let r = Ray::new(point(0, 0, -3), vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        let xs = [Intersection::new(SQRT_2, w.objects()[2].as_ref())];
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.shade_hit(&comps, 5), color(0.93391, 0.69643, 0.69243));
+    }
+}
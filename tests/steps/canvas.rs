@@ -38,7 +38,7 @@ fn when_canvas_to_ppm(world: &mut TestWorld, key: String, canvas: String) {
 fn when_pixel_is_color(world: &mut TestWorld, name: String, r: f64, g: f64, b: f64) {
     let canvas = world.get_mut::<Canvas>(&name).expect("Canvas not found");
     let color = color(r, g, b);
-    canvas.pixels_mut().iter_mut().for_each(|pixel| *pixel = color);
+    canvas.fill(color);
 }
 
 // ===============================================================================
@@ -66,7 +66,7 @@ fn then_pixel_at_equals_color(world: &mut TestWorld, canvas: String, x: usize, y
     let expected = *world.get::<Color>(&color).unwrap();
     let canvas = world.get::<Canvas>(&canvas).unwrap();
 
-    let actual = canvas[y][x];
+    let actual = canvas.pixel_at(x, y);
     assert_eq!(actual, expected);
 }
 
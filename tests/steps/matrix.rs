@@ -43,6 +43,45 @@ fn given_matrix(world: &mut TestWorld, step: &Step, key: String) {
     }
 }
 
+// ===============================================================================
+// Given Steps - Matrix Operations
+// ===============================================================================
+#[given(
+    regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) ← submatrix\(([a-zA-Z_][a-zA-Z0-9_]*), ([-+]?\d+), ([-+]?\d+)\)$"
+)]
+fn given_submatrix(world: &mut TestWorld, key: String, name: String, row: usize, col: usize) {
+    if let Some(m) = world.get::<Mat4>(&name) {
+        let value = m.submatrix(row, col);
+        world.insert(&key, value);
+    } else if let Some(m) = world.get::<Mat3>(&name) {
+        let value = m.submatrix(row, col);
+        world.insert(&key, value);
+    } else {
+        panic!("Matrix {name} not found or too small to take a submatrix");
+    }
+}
+
+#[given(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) ← transpose\(identity_matrix\)$")]
+fn given_transposed_identity(world: &mut TestWorld, key: String) {
+    world.insert(&key, Mat4::IDENTITY.transpose());
+}
+
+#[given(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) ← inverse\(([a-zA-Z_][a-zA-Z0-9_]*)\)$")]
+fn given_inverse(world: &mut TestWorld, key: String, name: String) {
+    let m = world.get::<Mat4>(&name).expect("4x4 matrix not found");
+    let value = m.inverse().expect("Matrix is not invertible");
+    world.insert(&key, value);
+}
+
+#[given(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) ← ([a-zA-Z_][a-zA-Z0-9_]*) \* ([a-zA-Z_][a-zA-Z0-9_]*)$")]
+fn given_matrix_product(world: &mut TestWorld, key: String, a: String, b: String) {
+    let (Some(ma), Some(mb)) = (world.get::<Mat4>(&a), world.get::<Mat4>(&b)) else {
+        panic!("Could not retrieve 4x4 matrices {a} and {b}");
+    };
+    let value = ma * mb;
+    world.insert(&key, value);
+}
+
 // ===============================================================================
 // Then Steps - Matrix Element Access
 // ===============================================================================
@@ -52,9 +91,9 @@ fn then_element_at_idx_is(world: &mut TestWorld, key: String, row: usize, col: u
         let get = |world: &TestWorld, key: &String| -> Option<f64> {
             world
                 .get::<Mat2>(key)
-                .map(|m| m[(row, col)])
-                .or_else(|| world.get::<Mat3>(key).map(|m| m[(row, col)]))
-                .or_else(|| world.get::<Mat4>(key).map(|m| m[(row, col)]))
+                .and_then(|m| m.get(row, col))
+                .or_else(|| world.get::<Mat3>(key).and_then(|m| m.get(row, col)))
+                .or_else(|| world.get::<Mat4>(key).and_then(|m| m.get(row, col)))
         };
 
         get(world, &key).unwrap_or(0.0)
@@ -66,12 +105,166 @@ fn then_element_at_idx_is(world: &mut TestWorld, key: String, row: usize, col: u
     );
 }
 
+// B[3,2] = -160/532
+#[then(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*)\[([-+]?\d+),([-+]?\d+)\] = ([-+]?\d*\.?\d+)/(\d*\.?\d+)$")]
+fn then_element_at_idx_is_fraction(
+    world: &mut TestWorld,
+    key: String,
+    row: usize,
+    col: usize,
+    numerator: f64,
+    denominator: f64,
+) {
+    then_element_at_idx_is(world, key, row, col, numerator / denominator);
+}
+
+// ===============================================================================
+// Then Steps - Matrix Operations
+// ===============================================================================
+#[then(regex = r"^determinant\(([a-zA-Z_][a-zA-Z0-9_]*)\) = ([-+]?\d*\.?\d+)$")]
+fn then_determinant_is(world: &mut TestWorld, key: String, expected: f64) {
+    let actual = world
+        .get::<Mat2>(&key)
+        .map(|m| m.determinant())
+        .or_else(|| world.get::<Mat3>(&key).map(|m| m.determinant()))
+        .or_else(|| world.get::<Mat4>(&key).map(|m| m.determinant()))
+        .unwrap_or_else(|| panic!("Matrix {key} not found"));
+
+    assert!(
+        is_equal(actual, expected),
+        "Expected determinant({key}) = {expected}, but got {actual}",
+    );
+}
+
+#[then(
+    regex = r"^(minor|cofactor)\(([a-zA-Z_][a-zA-Z0-9_]*), ([-+]?\d+), ([-+]?\d+)\) = ([-+]?\d*\.?\d+)$"
+)]
+fn then_minor_or_cofactor_is(
+    world: &mut TestWorld,
+    op: String,
+    key: String,
+    row: usize,
+    col: usize,
+    expected: f64,
+) {
+    let minor = op == "minor";
+    let actual = world
+        .get::<Mat3>(&key)
+        .map(|m| {
+            if minor {
+                m.minor(row, col)
+            } else {
+                m.cofactor(row, col)
+            }
+        })
+        .or_else(|| {
+            world.get::<Mat4>(&key).map(|m| {
+                if minor {
+                    m.minor(row, col)
+                } else {
+                    m.cofactor(row, col)
+                }
+            })
+        })
+        .unwrap_or_else(|| panic!("Matrix {key} not found"));
+
+    assert!(
+        is_equal(actual, expected),
+        "Expected {op}({key}, {row}, {col}) = {expected}, but got {actual}",
+    );
+}
+
+#[then(
+    regex = r"^submatrix\(([a-zA-Z_][a-zA-Z0-9_]*), ([-+]?\d+), ([-+]?\d+)\) is the following ([-+]?\d+)x([-+]?\d+) matrix:$"
+)]
+fn then_submatrix_should_be(
+    world: &mut TestWorld,
+    step: &Step,
+    key: String,
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+) {
+    let table = step.table.clone().expect("Matrix data table is required");
+    let buffer = parse_matrix_table(&table);
+
+    match rows * cols {
+        4 => {
+            let m = world.get::<Mat3>(&key).expect("3x3 matrix not found");
+            let expected = Mat2::try_from(&buffer[..]).expect("Invalid 2x2 matrix");
+            assert_eq!(m.submatrix(row, col), expected);
+        },
+        9 => {
+            let m = world.get::<Mat4>(&key).expect("4x4 matrix not found");
+            let expected = Mat3::try_from(&buffer[..]).expect("Invalid 3x3 matrix");
+            assert_eq!(m.submatrix(row, col), expected);
+        },
+        n => panic!("Unsupported submatrix size {rows}x{cols} = {n} elements"),
+    }
+}
+
+#[then(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) is invertible$")]
+fn then_matrix_is_invertible(world: &mut TestWorld, key: String) {
+    let m = world.get::<Mat4>(&key).expect("4x4 matrix not found");
+    assert!(m.invertible(), "Expected {key} to be invertible");
+}
+
+#[then(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) is not invertible$")]
+fn then_matrix_is_not_invertible(world: &mut TestWorld, key: String) {
+    let m = world.get::<Mat4>(&key).expect("4x4 matrix not found");
+    assert!(!m.invertible(), "Expected {key} not to be invertible");
+}
+
+#[then(regex = r"^(transpose|inverse)\(([a-zA-Z_][a-zA-Z0-9_]*)\) is the following (?:4x4 )?matrix:$")]
+fn then_unary_op_should_be(world: &mut TestWorld, step: &Step, op: String, key: String) {
+    let table = step.table.clone().expect("Matrix data table is required");
+    let expected = Mat4::try_from(&parse_matrix_table(&table)[..]).expect("Invalid 4x4 matrix");
+
+    let m = world.get::<Mat4>(&key).expect("4x4 matrix not found");
+    let actual = match op.as_str() {
+        "transpose" => m.transpose(),
+        _ => m.inverse().expect("Matrix is not invertible"),
+    };
+    assert_eq!(actual, expected);
+}
+
+#[then(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) is the following 4x4 matrix:$")]
+fn then_matrix_should_be(world: &mut TestWorld, step: &Step, key: String) {
+    let table = step.table.clone().expect("Matrix data table is required");
+    let expected = Mat4::try_from(&parse_matrix_table(&table)[..]).expect("Invalid 4x4 matrix");
+
+    let actual = world.get::<Mat4>(&key).expect("4x4 matrix not found");
+    assert_eq!(*actual, expected);
+}
+
+// C * inverse(B) = A
+#[then(
+    regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) \* inverse\(([a-zA-Z_][a-zA-Z0-9_]*)\) = ([a-zA-Z_][a-zA-Z0-9_]*)$"
+)]
+fn then_product_with_inverse_should_be(world: &mut TestWorld, a: String, b: String, expected: String) {
+    let (Some(ma), Some(mb), Some(expected)) = (
+        world.get::<Mat4>(&a),
+        world.get::<Mat4>(&b),
+        world.get::<Mat4>(&expected),
+    ) else {
+        panic!("Could not retrieve 4x4 matrices {a}, {b} and {expected}");
+    };
+
+    assert_eq!(ma * mb.inverse().expect("Matrix is not invertible"), *expected);
+}
+
 // ===============================================================================
 // Then Steps - Matrix Equality
 // ===============================================================================
 
 #[then(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) = ([a-zA-Z_][a-zA-Z0-9_]*)$")]
 fn then_matrices_should_be_equal(world: &mut TestWorld, a: String, b: String) {
+    if b == "identity_matrix" {
+        let m = world.get::<Mat4>(&a).expect("4x4 matrix not found");
+        return assert_eq!(*m, Mat4::IDENTITY);
+    }
+
     if let (Some(ma), Some(mb)) = (world.get::<Mat4>(&a), world.get::<Mat4>(&b)) {
         assert_eq!(ma, mb);
     } else if let (Some(ma), Some(mb)) = (world.get::<Mat3>(&a), world.get::<Mat3>(&b)) {
@@ -171,6 +364,14 @@ fn then_matrix_product_identity_should_be(world: &mut TestWorld, key: String, ke
     }
 }
 
+// identity_matrix * a = a
+#[then(regex = r"^identity_matrix \* ([a-zA-Z_][a-zA-Z0-9_]*) = ([a-zA-Z_][a-zA-Z0-9_]*)$")]
+fn then_identity_mul_tuple_should_be(world: &mut TestWorld, key: String, expected: String) {
+    let tup = *world.get::<Tuple4>(&key).expect("Tuple not found");
+    let expected = *world.get::<Tuple4>(&expected).expect("Tuple not found");
+    assert_eq!(Mat4::IDENTITY * tup, expected);
+}
+
 // A * b = tuple(18, 24, 33, 1)
 #[then(
     regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) \* ([a-zA-Z_][a-zA-Z0-9_]*) = tuple\(([^,]+), ([^,]+), ([^,]+), ([^)]+)\)$"
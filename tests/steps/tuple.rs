@@ -43,10 +43,9 @@ fn given_vector_sqrt2_half(world: &mut TestWorld, name: String) {
 #[when(regex = r"^([a-zA-Z_][a-zA-Z0-9_]*) ← normalize\(([a-zA-Z_][a-zA-Z0-9_]*)\)$")]
 fn when_normalize(world: &mut TestWorld, key: String, name: String) {
     let v = world.get::<Tuple4>(&name).unwrap();
-    let v = Vec3::try_from(v).unwrap();
 
-    let value = v.try_normalize().unwrap();
-    world.insert(&key, Tuple4::from(value));
+    let value = v.normalize();
+    world.insert(&key, value);
 }
 
 #[when(
@@ -305,18 +304,16 @@ fn then_div_equals_tuple(world: &mut TestWorld, name: String, scalar: f64, x: f6
 #[then(regex = r"^magnitude\(([a-zA-Z_][a-zA-Z0-9_]*)\) = ([-+]?\d*\.?\d+)$")]
 fn then_magnitude_equals(world: &mut TestWorld, name: String, expected: f64) {
     let v = world.get::<Tuple4>(&name).unwrap();
-    let v = Vec3::try_from(v).unwrap();
 
-    let actual = v.length();
+    let actual = v.magnitude();
     assert_eq!(actual, expected);
 }
 
 #[then(regex = r"^magnitude\(([a-zA-Z_][a-zA-Z0-9_]*)\) = √(\d+)$")]
 fn then_magnitude_equals_sqrt(world: &mut TestWorld, name: String, value: f64) {
     let v = world.get::<Tuple4>(&name).unwrap();
-    let v = Vec3::try_from(v).unwrap();
 
-    let actual = v.length();
+    let actual = v.magnitude();
     let expected = value.sqrt();
     assert_eq!(actual, expected);
 }
@@ -327,10 +324,9 @@ fn then_magnitude_equals_sqrt(world: &mut TestWorld, name: String, value: f64) {
 )]
 fn then_normalize_equals_vector(world: &mut TestWorld, name: String, x: f64, y: f64, z: f64) {
     let v = world.get::<Tuple4>(&name).unwrap();
-    let v = Vec3::try_from(v).unwrap();
 
-    let actual = v.try_normalize();
-    let expected = Some(vector(x, y, z));
+    let actual = v.normalize();
+    let expected = Tuple4::from(vector(x, y, z));
     assert_eq!(actual, expected);
 }
 
@@ -339,21 +335,18 @@ fn then_normalize_equals_vector(world: &mut TestWorld, name: String, x: f64, y:
 )]
 fn then_normalize_approximately_equals_vector(world: &mut TestWorld, name: String, x: f64, y: f64, z: f64) {
     let v = world.get::<Tuple4>(&name).unwrap();
-    let v = Vec3::try_from(v).unwrap();
 
-    let actual = v.try_normalize();
-    let expected = Some(vector(x, y, z));
+    let actual = v.normalize();
+    let expected = Tuple4::from(vector(x, y, z));
     assert_eq!(actual, expected);
 }
 
 #[then(regex = r"^dot\(([a-zA-Z_][a-zA-Z0-9_]*), ([a-zA-Z_][a-zA-Z0-9_]*)\) = ([-+]?\d*\.?\d+)$")]
 fn then_dot_equals(world: &mut TestWorld, a: String, b: String, expected: f64) {
     let v1 = world.get::<Tuple4>(&a).unwrap();
-    let v1 = Vec3::try_from(v1).unwrap();
     let v2 = world.get::<Tuple4>(&b).unwrap();
-    let v2 = Vec3::try_from(v2).unwrap();
 
-    let actual = v1.dot(v2);
+    let actual = v1.dot(*v2);
     assert_eq!(actual, expected);
 }
 
@@ -362,11 +355,9 @@ fn then_dot_equals(world: &mut TestWorld, a: String, b: String, expected: f64) {
 )]
 fn then_cross_equals_vector(world: &mut TestWorld, a: String, b: String, x: f64, y: f64, z: f64) {
     let v1 = world.get::<Tuple4>(&a).unwrap();
-    let v1 = Vec3::try_from(v1).unwrap();
     let v2 = world.get::<Tuple4>(&b).unwrap();
-    let v2 = Vec3::try_from(v2).unwrap();
 
-    let actual = v1.cross(v2);
-    let expected = vector(x, y, z);
+    let actual = v1.cross(*v2);
+    let expected = Tuple4::from(vector(x, y, z));
     assert_eq!(actual, expected);
 }